@@ -0,0 +1,90 @@
+// Ether volume: ray-marches one channel of the ether grid through its bounding box,
+// or draws it on an axis-aligned slice. The channel arrives normalized to 0..1.
+
+#import bevy_pbr::mesh_view_bindings::view
+#import bevy_pbr::forward_io::VertexOutput
+
+struct EtherVolumeParams {
+    colormap: array<vec4<f32>, 9>,
+    box_min: vec4<f32>,
+    box_max: vec4<f32>,
+    dims: vec4<f32>,
+    mode: u32,   // 0 volume, 1 slice
+    invert: u32, // 1 makes low values dense
+    steps: u32,
+    opacity: f32, // Extinction per cell at full value
+};
+
+@group(2) @binding(0) var<uniform> params: EtherVolumeParams;
+@group(2) @binding(1) var volume_texture: texture_3d<f32>;
+@group(2) @binding(2) var volume_sampler: sampler;
+
+fn sample_colormap(t: f32) -> vec3<f32> {
+    let x = clamp(t, 0.0, 1.0) * 8.0;
+    let i = min(u32(floor(x)), 7u);
+    return mix(params.colormap[i].rgb, params.colormap[i + 1u].rgb, x - f32(i));
+}
+
+// Nodes sit on texel centres: node 0 at 0.5 / n, the last node at 1 - 0.5 / n
+fn sample_volume(world: vec3<f32>) -> f32 {
+    let n = params.dims.xyz;
+    let local = (world - params.box_min.xyz) / (params.box_max.xyz - params.box_min.xyz);
+    let uvw = (local * (n - 1.0) + 0.5) / n;
+    return textureSampleLevel(volume_texture, volume_sampler, uvw, 0.0).r;
+}
+
+fn density(value: f32) -> f32 {
+    if params.invert == 1u {
+        return 1.0 - value;
+    }
+    return value;
+}
+
+fn inside(world: vec3<f32>) -> bool {
+    return all(world >= params.box_min.xyz - 1e-3) && all(world <= params.box_max.xyz + 1e-3);
+}
+
+@fragment
+fn fragment(mesh: VertexOutput, @builtin(front_facing) is_front: bool) -> @location(0) vec4<f32> {
+    let world = mesh.world_position.xyz;
+
+    // 1. SLICE: the channel through the colormap, nearly opaque
+    if params.mode == 1u {
+        if !inside(world) {
+            discard;
+        }
+        let value = sample_volume(world);
+        return vec4<f32>(sample_colormap(value) * 0.9, 0.9);
+    }
+
+    // 2. VOLUME: one ray per back-face fragment, from the camera (or the box entry) to the exit
+    if is_front {
+        discard;
+    }
+    let origin = view.world_position;
+    let direction = normalize(world - origin);
+    let inverse = 1.0 / direction;
+    let t0 = (params.box_min.xyz - origin) * inverse;
+    let t1 = (params.box_max.xyz - origin) * inverse;
+    let near = max(max(min(t0.x, t1.x), min(t0.y, t1.y)), max(min(t0.z, t1.z), 0.0));
+    let far = min(min(max(t0.x, t1.x), max(t0.y, t1.y)), max(t0.z, t1.z));
+    if far <= near {
+        discard;
+    }
+
+    // 3. COMPOSITE front to back (premultiplied)
+    let cell = (params.box_max.x - params.box_min.x) / max(params.dims.x - 1.0, 1.0);
+    let step = (far - near) / f32(params.steps);
+    var color = vec3<f32>(0.0);
+    var alpha = 0.0;
+    for (var i = 0u; i < params.steps; i = i + 1u) {
+        let value = sample_volume(origin + direction * (near + (f32(i) + 0.5) * step));
+        let a = 1.0 - exp(-params.opacity * density(value) * step / cell);
+        color += (1.0 - alpha) * a * sample_colormap(value);
+        alpha += (1.0 - alpha) * a;
+        if alpha > 0.99 {
+            break;
+        }
+    }
+    return vec4<f32>(color, alpha);
+}
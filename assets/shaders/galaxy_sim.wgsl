@@ -16,8 +16,131 @@ struct GalaxyUniforms {
     pinch_strength: f32,
     phi_value: f32,
     arms: f32,
+    // Quasar jet (mirrors JetModel in src/simulation/jet.rs)
+    jet_axis_x: f32,
+    jet_axis_y: f32,
+    jet_axis_z: f32,
+    jet_speed: f32,
+    jet_launch_radius: f32,
+    jet_opening_angle: f32,
+    jet_sheath_speed: f32,
+    jet_deceleration_length: f32,
+    jet_reentry_speed: f32,
+    jet_reentry_distance: f32,
+    jet_reentry_radius_min: f32,
+    jet_reentry_radius_max: f32,
+    // Color map (mirrors ColorField in src/simulation/color_map.rs)
+    color_field: f32,
+    color_min: f32,
+    color_max: f32,
+    colormap: array<vec4<f32>, 9>,
+    // Cores for the energy colors (mirrors GalaxyUniforms::with_cores)
+    cores: array<vec4<f32>, 4>, // xyz position, w suction
+    core_velocities: array<vec4<f32>, 4>,
+    core_count: f32,
 };
 
+const TAU: f32 = 6.28318530718;
+const PI: f32 = 3.14159265359;
+
+// ColorField ids
+const FIELD_SPEED: i32 = 1;
+const FIELD_RADIUS: i32 = 2;
+const FIELD_ARM: i32 = 3;
+const FIELD_ENERGY: i32 = 6;
+const FIELD_SPIRAL_DEVIATION: i32 = 7;
+const FIELD_TEMPERATURE: i32 = 8;
+
+// PCG hash -> uniform float in [0, 1)
+fn random(seed: u32) -> f32 {
+    let state = seed * 747796405u + 2891336453u;
+    let word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return f32((word >> 22u) ^ word) / 4294967296.0;
+}
+
+// Rotation taking +Y onto `axis` (same as Quat::from_rotation_arc(Y, axis))
+fn rotate_from_y(v: vec3<f32>, axis: vec3<f32>) -> vec3<f32> {
+    if (axis.y < -0.9999) {
+        return vec3<f32>(v.x, -v.y, -v.z);
+    }
+    let k = 1.0 / (1.0 + axis.y);
+    let x = vec3<f32>(axis.z * axis.z * k + axis.y, -axis.x, -axis.x * axis.z * k);
+    let z = vec3<f32>(-axis.x * axis.z * k, -axis.z, axis.x * axis.x * k + axis.y);
+    return x * v.x + axis * v.y + z * v.z;
+}
+
+// JetModel::launch_velocity
+fn jet_launch_velocity(u: vec3<f32>) -> vec3<f32> {
+    let axis = vec3<f32>(uniforms.jet_axis_x, uniforms.jet_axis_y, uniforms.jet_axis_z);
+    let lobe = select(-axis, axis, u.x < 0.5);
+    let edge = sqrt(u.y);
+    let theta = uniforms.jet_opening_angle * edge;
+    let azimuth = TAU * u.z;
+    let local = vec3<f32>(sin(theta) * cos(azimuth), cos(theta), sin(theta) * sin(azimuth));
+    let profile = 1.0 - (1.0 - uniforms.jet_sheath_speed) * edge * edge;
+    return rotate_from_y(local, lobe) * uniforms.jet_speed * profile;
+}
+
+
+// Native colors: speed-based red -> yellow -> cyan
+fn native_color(speed: f32) -> vec3<f32> {
+    if (speed < 20.0) {
+        let t = speed / 20.0;
+        return mix(vec3<f32>(1.0, 0.0, 0.0), vec3<f32>(1.0, 1.0, 0.0), t); // Red to Yellow
+    }
+    let t = min((speed - 20.0) / 20.0, 1.0);
+    return mix(vec3<f32>(1.0, 1.0, 0.0), vec3<f32>(0.0, 1.0, 1.0), t); // Yellow to Cyan
+}
+
+// Kinetic energy relative to the nearest core plus suction_potential, as map_particle_colors
+fn specific_energy(pos: vec3<f32>, vel: vec3<f32>) -> f32 {
+    var potential = 0.0;
+    var nearest = 1e30;
+    var core_velocity = vec3<f32>(0.0);
+    for (var i = 0u; i < min(u32(uniforms.core_count), 4u); i++) {
+        let core = uniforms.cores[i];
+        let r = distance(core.xyz, pos);
+        potential += core.w * select(r - 1.0, log(r), r > 1.0);
+        if (r < nearest) {
+            nearest = r;
+            core_velocity = uniforms.core_velocities[i].xyz;
+        }
+    }
+    let relative = vel - core_velocity;
+    return 0.5 * dot(relative, relative) + potential;
+}
+
+// Colormap::sample
+fn sample_colormap(t: f32) -> vec3<f32> {
+    let x = clamp(t, 0.0, 1.0) * 8.0;
+    let i = min(u32(floor(x)), 7u);
+    return mix(uniforms.colormap[i].rgb, uniforms.colormap[i + 1u].rgb, x - f32(i));
+}
+
+// Same definitions as map_particle_colors, for a galaxy at the origin turning around +Y.
+// Age and density aren't known here, so they keep the native colors.
+fn particle_color(particle: Particle) -> vec3<f32> {
+    let pos = particle.pos.xyz;
+    let speed = length(particle.vel.xyz);
+    let r = max(length(pos.xz), 1e-3);
+    let phase = atan2(pos.z, pos.x) - log(r) * uniforms.phi_value;
+    let nearest_arm = round(phase * uniforms.arms / TAU);
+    let tangent = normalize(vec3<f32>(-pos.z, 0.0, pos.x));
+
+    var value: f32;
+    switch (i32(uniforms.color_field)) {
+        case FIELD_SPEED: { value = speed; }
+        case FIELD_RADIUS: { value = length(pos); }
+        case FIELD_ARM: { value = nearest_arm - floor(nearest_arm / uniforms.arms) * uniforms.arms; }
+        case FIELD_ENERGY: { value = specific_energy(pos, particle.vel.xyz); }
+        case FIELD_SPIRAL_DEVIATION: { value = abs(phase * uniforms.arms - nearest_arm * TAU) / PI; }
+        case FIELD_TEMPERATURE: { value = length(particle.vel.xyz - tangent * dot(particle.vel.xyz, tangent)); }
+        default: { return native_color(speed); }
+    }
+    let span = uniforms.color_max - uniforms.color_min;
+    let t = select(0.5, (value - uniforms.color_min) / span, abs(span) > 1e-12);
+    return sample_colormap(t);
+}
 
 @compute @workgroup_size(64)
 fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
@@ -30,12 +153,29 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let pos = particle.pos.xyz;
     let r = length(pos.xz);
 
-    // Quasar Recycling: If too close to center, respawn and shoot up/down
-    if (r < 2.0) {
-        particle.pos = vec4<f32>(0.0, 0.0, 0.0, 1.0);
-        // Random Y direction (±80.0)
-        let y_vel = select(-80.0, 80.0, (id % 2u) == 0u);
-        particle.vel = vec4<f32>(0.0, y_vel, 0.0, 0.0);
+    // vel.w flags jet-borne material (1.0) vs disk material (0.0)
+    let seed = id * 4u + bitcast<u32>(uniforms.time) * 19349663u;
+
+    if (particle.vel.w > 0.5) {
+        // JetModel::stalled -> rain back onto the outer disk (JetModel::reentry_position)
+        let speed = length(particle.vel.xyz);
+        if (speed < uniforms.jet_reentry_speed || length(pos) > uniforms.jet_reentry_distance) {
+            let radius = mix(uniforms.jet_reentry_radius_min, uniforms.jet_reentry_radius_max, random(seed));
+            let angle = TAU * random(seed + 1u);
+            let landing = vec3<f32>(radius * cos(angle), 0.0, radius * sin(angle));
+            let tangent = normalize(vec3<f32>(-landing.z, 0.0, landing.x));
+            particle.pos = vec4<f32>(landing, 1.0);
+            particle.vel = vec4<f32>(tangent * 15.0, 0.0);
+        } else {
+            // JetModel::decelerate: entrainment of the surrounding ether
+            let decay = exp(-speed * uniforms.dt / uniforms.jet_deceleration_length);
+            particle.vel = vec4<f32>(particle.vel.xyz * decay, 1.0);
+        }
+    } else if (length(pos) < uniforms.jet_launch_radius) {
+        // Quasar Recycling: fire up the precessing jet cone
+        let jet_velocity = jet_launch_velocity(vec3<f32>(random(seed), random(seed + 1u), random(seed + 2u)));
+        particle.pos = vec4<f32>(normalize(jet_velocity) * uniforms.jet_launch_radius, 1.0);
+        particle.vel = vec4<f32>(jet_velocity, 1.0);
     } else {
         // --- 1. GEOMETRY ---
         let r = length(pos.xz);
@@ -80,17 +220,7 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     particle.pos.y += particle.vel.y * uniforms.dt;
     particle.pos.z += particle.vel.z * uniforms.dt;
 
-    // Calculate color based on speed
-    let speed = length(particle.vel.xyz);
-    var color: vec3<f32>;
-    if (speed < 20.0) {
-        let t = speed / 20.0;
-        color = mix(vec3<f32>(1.0, 0.0, 0.0), vec3<f32>(1.0, 1.0, 0.0), t); // Red to Yellow
-    } else {
-        let t = min((speed - 20.0) / 20.0, 1.0);
-        color = mix(vec3<f32>(1.0, 1.0, 0.0), vec3<f32>(0.0, 1.0, 1.0), t); // Yellow to Cyan
-    }
-    particle.color = vec4<f32>(color, 1.0);
+    particle.color = vec4<f32>(particle_color(particle), 1.0);
 
     // Store updated particle
     particles[id] = particle;
@@ -27,7 +27,7 @@ fn vertex(vertex: Vertex) -> VertexOutput {
     let world_position = instance_transform * vec4<f32>(vertex.position, 1.0);
 
     var out: VertexOutput;
-    out.clip_position = mesh_view_bindings::view.view_proj * world_position;
+    out.clip_position = mesh_view_bindings::view.clip_from_world * world_position;
     out.color = vertex.instance_color;
     return out;
 }
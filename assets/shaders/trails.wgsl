@@ -0,0 +1,77 @@
+// Trail ribbons: one triangle strip per trail, expanded from the ring buffer.
+// Vertex 2k and 2k+1 are the two edges of the ribbon at the k-th newest point.
+
+#import bevy_pbr::mesh_view_bindings::view
+
+struct TrailParams {
+    head_color: vec4<f32>,
+    tail_color: vec4<f32>,
+    width: f32,
+    fade: f32,
+    length: u32,
+    _padding: u32,
+};
+
+@group(1) @binding(0) var<uniform> params: TrailParams;
+@group(1) @binding(1) var<storage, read> points: array<vec4<f32>>;
+@group(1) @binding(2) var<storage, read> rings: array<vec2<u32>>; // (newest index, count)
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+};
+
+// The point `age` samples back from the newest
+fn trail_point(slot: u32, ring: vec2<u32>, age: u32) -> vec3<f32> {
+    let index = (ring.x + params.length - age) % params.length;
+    return points[slot * params.length + index].xyz;
+}
+
+@vertex
+fn vertex(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) slot: u32,
+) -> VertexOutput {
+    var out: VertexOutput;
+    let ring = rings[slot];
+
+    // Empty and one-point trails collapse to nothing
+    if ring.y < 2u {
+        out.clip_position = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+        out.color = vec4<f32>(0.0);
+        return out;
+    }
+
+    // Past the oldest point every vertex repeats it, so the extra triangles are degenerate
+    let last = ring.y - 1u;
+    let age = min(vertex_index / 2u, last);
+    let position = trail_point(slot, ring, age);
+    let newer = trail_point(slot, ring, select(age - 1u, age, age == 0u));
+    let older = trail_point(slot, ring, min(age + 1u, last));
+
+    // 1. RIBBON faces the camera, across the direction of travel
+    let tangent = newer - older;
+    let to_camera = view.world_position - position;
+    let across = cross(tangent, to_camera);
+    let across_length = length(across);
+    var side = vec3<f32>(0.0);
+    if across_length > 1e-6 {
+        side = across / across_length;
+    }
+    let edge = select(-0.5, 0.5, vertex_index % 2u == 1u);
+    let world_position = position + side * edge * params.width;
+
+    // 2. FADE from head to tail
+    let t = f32(age) / f32(last);
+    let brightness = pow(1.0 - t, params.fade);
+    let color = mix(params.head_color, params.tail_color, t).rgb * brightness;
+
+    out.clip_position = view.clip_from_world * vec4<f32>(world_position, 1.0);
+    out.color = vec4<f32>(color, 0.0);
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
@@ -0,0 +1,225 @@
+use bevy::math::Vec3;
+use std::fmt::Write as _;
+use std::ops::{Add, Mul, Sub};
+use std::path::Path;
+use crate::io::ImportError;
+
+// Timing of the segment that starts at a keyframe
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    In,    // Accelerates away from the keyframe
+    Out,   // Decelerates into the next one
+    InOut, // Both: the camera settles on every keyframe
+}
+
+impl Easing {
+    pub fn apply(self, u: f32) -> f32 {
+        let u = u.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => u,
+            Easing::In => u * u,
+            Easing::Out => u * (2.0 - u),
+            Easing::InOut => u * u * (3.0 - 2.0 * u),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Easing::Linear => "linear",
+            Easing::In => "in",
+            Easing::Out => "out",
+            Easing::InOut => "inout",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [Easing::Linear, Easing::In, Easing::Out, Easing::InOut]
+            .into_iter()
+            .find(|easing| easing.name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraPose {
+    pub position: Vec3,
+    pub look_at: Vec3,
+    pub fov: f32, // Vertical, radians
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraKeyframe {
+    pub time: f32, // Seconds from the start of the path
+    pub pose: CameraPose,
+    pub easing: Easing,
+}
+
+// Keyframes sorted by time, interpolated by Catmull–Rom splines
+#[derive(Clone, Debug, Default)]
+pub struct CameraPath {
+    pub keyframes: Vec<CameraKeyframe>,
+}
+
+// Cubic Hermite segment with Catmull–Rom tangents for uneven keyframe spacing
+fn catmull_rom<T>(p: [T; 4], t: [f32; 4], u: f32) -> T
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    let span = t[2] - t[1];
+    let tangent = |a: usize, b: usize| (p[b] - p[a]) * (span / (t[b] - t[a]).max(f32::EPSILON));
+    let (m1, m2) = (tangent(0, 2), tangent(1, 3));
+    let (u2, u3) = (u * u, u * u * u);
+    p[1] * (2.0 * u3 - 3.0 * u2 + 1.0)
+        + m1 * (u3 - 2.0 * u2 + u)
+        + p[2] * (-2.0 * u3 + 3.0 * u2)
+        + m2 * (u3 - u2)
+}
+
+impl CameraPath {
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |key| key.time)
+    }
+
+    pub fn push(&mut self, keyframe: CameraKeyframe) {
+        self.keyframes.push(keyframe);
+        self.keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+
+    // Pose at `time`, held at the ends; None for an empty path
+    pub fn sample(&self, time: f32) -> Option<CameraPose> {
+        let keys = &self.keyframes;
+        let last = keys.len().checked_sub(1)?;
+        if time <= keys[0].time || last == 0 {
+            return Some(keys[0].pose);
+        }
+        if time >= keys[last].time {
+            return Some(keys[last].pose);
+        }
+
+        // 1. SEGMENT and its eased local parameter
+        let i = keys.partition_point(|key| key.time <= time) - 1;
+        let span = (keys[i + 1].time - keys[i].time).max(f32::EPSILON);
+        let u = keys[i].easing.apply((time - keys[i].time) / span);
+
+        // 2. NEIGHBOURS, mirrored at the ends so the first and last segments stay straight
+        let index = [i.saturating_sub(1), i, i + 1, (i + 2).min(last)];
+        let key = |j: usize| keys[index[j]];
+        let mut times = index.map(|k| keys[k].time);
+        if i == 0 {
+            times[0] = 2.0 * times[1] - times[2];
+        }
+        if i + 1 == last {
+            times[3] = 2.0 * times[2] - times[1];
+        }
+        let point = |j: usize, f: fn(&CameraPose) -> Vec3| {
+            match (j, i == 0, i + 1 == last) {
+                (0, true, _) => f(&key(1).pose) * 2.0 - f(&key(2).pose),
+                (3, _, true) => f(&key(2).pose) * 2.0 - f(&key(1).pose),
+                _ => f(&key(j).pose),
+            }
+        };
+        let spline = |f: fn(&CameraPose) -> Vec3| catmull_rom([0, 1, 2, 3].map(|j| point(j, f)), times, u);
+
+        Some(CameraPose {
+            position: spline(|pose| pose.position),
+            look_at: spline(|pose| pose.look_at),
+            fov: spline(|pose| Vec3::splat(pose.fov)).x,
+        })
+    }
+}
+
+// Text format, one keyframe per line:
+// time x y z look_x look_y look_z fov_degrees [linear|in|out|inout]
+pub fn parse_camera_path(text: &str) -> Result<CameraPath, ImportError> {
+    let mut path = CameraPath::default();
+    for (line_number, line) in text.lines().enumerate() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.is_empty() || fields[0].starts_with('#') {
+            continue;
+        }
+        let error = || ImportError::Format(format!("line {}: expected 8 numbers and an optional easing", line_number + 1));
+        let numbers: Vec<f32> = fields.iter().take(8).map(|field| field.parse()).collect::<Result<_, _>>().map_err(|_| error())?;
+        if numbers.len() < 8 {
+            return Err(error());
+        }
+        let easing = match fields.get(8) {
+            Some(name) => Easing::from_name(name).ok_or_else(error)?,
+            None => Easing::Linear,
+        };
+        path.push(CameraKeyframe {
+            time: numbers[0],
+            pose: CameraPose {
+                position: Vec3::new(numbers[1], numbers[2], numbers[3]),
+                look_at: Vec3::new(numbers[4], numbers[5], numbers[6]),
+                fov: numbers[7].to_radians(),
+            },
+            easing,
+        });
+    }
+    Ok(path)
+}
+
+pub fn read_camera_path(path: &Path) -> Result<CameraPath, ImportError> {
+    parse_camera_path(&std::fs::read_to_string(path)?)
+}
+
+pub fn write_camera_path(path: &Path, camera_path: &CameraPath) -> std::io::Result<()> {
+    let mut out = String::from("# time x y z look_x look_y look_z fov_degrees easing\n");
+    for key in camera_path.keyframes.iter() {
+        let (p, l) = (key.pose.position, key.pose.look_at);
+        let _ = writeln!(
+            out,
+            "{} {} {} {} {} {} {} {} {}",
+            key.time, p.x, p.y, p.z, l.x, l.y, l.z, key.pose.fov.to_degrees(), key.easing.name()
+        );
+    }
+    std::fs::write(path, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(time: f32, x: f32, easing: Easing) -> CameraKeyframe {
+        CameraKeyframe {
+            time,
+            pose: CameraPose { position: Vec3::new(x, 10.0, 0.0), look_at: Vec3::ZERO, fov: 0.8 },
+            easing,
+        }
+    }
+
+    #[test]
+    fn spline_passes_through_keyframes_and_eases_between_them() {
+        let path = CameraPath {
+            keyframes: vec![key(0.0, 0.0, Easing::Linear), key(1.0, 10.0, Easing::InOut), key(2.0, 20.0, Easing::Linear)],
+        };
+        for keyframe in path.keyframes.iter() {
+            let pose = path.sample(keyframe.time).unwrap();
+            assert!((pose.position - keyframe.pose.position).length() < 1e-4);
+        }
+        // Evenly spaced collinear keys on a linear segment move at constant speed
+        assert!((path.sample(0.5).unwrap().position.x - 5.0).abs() < 1e-3);
+        // The eased segment starts slowly (linear timing would be 2 units in)
+        assert!(path.sample(1.2).unwrap().position.x - 10.0 < 0.6 * 2.0);
+        assert_eq!(path.sample(-1.0).unwrap(), path.keyframes[0].pose);
+        assert_eq!(path.sample(9.0).unwrap(), path.keyframes[2].pose);
+        assert!(CameraPath::default().sample(0.0).is_none());
+    }
+
+    #[test]
+    fn files_round_trip() {
+        let mut path = CameraPath::default();
+        path.push(key(2.0, 5.0, Easing::Out));
+        path.push(key(0.0, -5.0, Easing::InOut));
+        let file = std::env::temp_dir().join(format!("physics_engine_camera_path_test_{}.txt", std::process::id()));
+        write_camera_path(&file, &path).unwrap();
+        let read = read_camera_path(&file).unwrap();
+        let _ = std::fs::remove_file(&file);
+
+        assert_eq!(read.keyframes.len(), 2);
+        assert_eq!(read.keyframes[0].easing, Easing::InOut);
+        assert!((read.keyframes[1].pose.fov - 0.8).abs() < 1e-5);
+        assert!(parse_camera_path("0 1 2 3\n").is_err());
+        assert!(parse_camera_path("0 1 2 3 4 5 6 45 wobble\n").is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::VecDeque;
+use std::io::Write;
+
+// Fixed-capacity history of named diagnostic columns, one row per sample.
+// The oldest row is dropped once the log is full.
+pub struct DiagnosticLog {
+    columns: Vec<&'static str>,
+    capacity: usize,
+    rows: VecDeque<Vec<f32>>,
+}
+
+impl DiagnosticLog {
+    pub fn new(columns: &[&'static str], capacity: usize) -> Self {
+        Self {
+            columns: columns.to_vec(),
+            capacity: capacity.max(1),
+            rows: VecDeque::with_capacity(capacity.max(1)),
+        }
+    }
+
+    pub fn columns(&self) -> &[&'static str] {
+        &self.columns
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    // Missing values are stored as NaN, extra ones are ignored
+    pub fn push(&mut self, values: &[f32]) {
+        if self.rows.len() == self.capacity {
+            self.rows.pop_front();
+        }
+        let row = (0..self.columns.len()).map(|i| values.get(i).copied().unwrap_or(f32::NAN)).collect();
+        self.rows.push_back(row);
+    }
+
+    // Oldest first; empty for an unknown column
+    pub fn column(&self, name: &str) -> Vec<f32> {
+        let Some(index) = self.columns.iter().position(|column| *column == name) else {
+            return Vec::new();
+        };
+        self.rows.iter().map(|row| row[index]).collect()
+    }
+
+    pub fn write_csv(&self, out: &mut impl Write) -> std::io::Result<()> {
+        writeln!(out, "{}", self.columns.join(","))?;
+        for row in self.rows.iter() {
+            let values: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+            writeln!(out, "{}", values.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_newest_rows_and_writes_them_as_csv() {
+        let mut log = DiagnosticLog::new(&["time", "energy"], 3);
+        for i in 0..5 {
+            log.push(&[i as f32, i as f32 * 10.0]);
+        }
+        log.push(&[5.0]);
+        assert_eq!(log.len(), 3);
+        assert_eq!(log.column("time"), vec![3.0, 4.0, 5.0]);
+        assert!(log.column("energy")[2].is_nan());
+        assert!(log.column("missing").is_empty());
+
+        let mut csv = Vec::new();
+        log.write_csv(&mut csv).unwrap();
+        assert_eq!(String::from_utf8(csv).unwrap(), "time,energy\n3,30\n4,40\n5,NaN\n");
+    }
+}
@@ -0,0 +1,237 @@
+use bevy::math::{Vec3, Vec4};
+use std::fmt::Write as _;
+use std::io::Write;
+use std::path::Path;
+
+// One particle as it is written out
+#[derive(Clone, Copy, Debug)]
+pub struct ExportRecord {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub color: Vec4, // Linear RGBA
+    pub population: u32,
+}
+
+// Which quantities go into the files
+#[derive(Clone, Copy, Debug)]
+pub struct ExportFields {
+    pub position: bool,
+    pub velocity: bool,
+    pub color: bool,
+    pub population: bool,
+}
+
+impl Default for ExportFields {
+    fn default() -> Self {
+        Self { position: true, velocity: true, color: true, population: true }
+    }
+}
+
+impl ExportFields {
+    // Scalar float columns in file order
+    fn float_columns(&self) -> Vec<&'static str> {
+        let mut columns = Vec::new();
+        if self.position {
+            columns.extend(["x", "y", "z"]);
+        }
+        if self.velocity {
+            columns.extend(["vx", "vy", "vz"]);
+        }
+        if self.color {
+            columns.extend(["r", "g", "b", "a"]);
+        }
+        columns
+    }
+
+    fn float_values(&self, record: &ExportRecord) -> Vec<f32> {
+        let mut values = Vec::new();
+        if self.position {
+            values.extend(record.position.to_array());
+        }
+        if self.velocity {
+            values.extend(record.velocity.to_array());
+        }
+        if self.color {
+            values.extend(record.color.to_array());
+        }
+        values
+    }
+}
+
+pub fn write_csv(path: &Path, records: &[ExportRecord], fields: &ExportFields) -> std::io::Result<()> {
+    let mut out = String::new();
+    let mut header: Vec<&str> = fields.float_columns();
+    if fields.population {
+        header.push("population");
+    }
+    out.push_str(&header.join(","));
+    out.push('\n');
+
+    for record in records {
+        let mut row: Vec<String> = fields.float_values(record).iter().map(|v| v.to_string()).collect();
+        if fields.population {
+            row.push(record.population.to_string());
+        }
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    std::fs::write(path, out)
+}
+
+// NumPy structured array (format 1.0): np.load(path)["x"], ...["population"]
+pub fn write_npy(path: &Path, records: &[ExportRecord], fields: &ExportFields) -> std::io::Result<()> {
+    let mut descr: Vec<String> = fields.float_columns().iter().map(|name| format!("('{name}', '<f4')")).collect();
+    if fields.population {
+        descr.push("('population', '<u4')".to_string());
+    }
+    let mut header = format!(
+        "{{'descr': [{}], 'fortran_order': False, 'shape': ({},), }}",
+        descr.join(", "),
+        records.len()
+    );
+    // Magic (6) + version (2) + header length (2) + header + '\n' is padded to 64 bytes
+    let unpadded = 10 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    let mut out = Vec::with_capacity(10 + header.len() + records.len() * 44);
+    out.extend_from_slice(b"\x93NUMPY\x01\x00");
+    out.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    out.extend_from_slice(header.as_bytes());
+    for record in records {
+        for value in fields.float_values(record) {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        if fields.population {
+            out.extend_from_slice(&record.population.to_le_bytes());
+        }
+    }
+    std::fs::write(path, out)
+}
+
+// VTK XML UnstructuredGrid of vertex cells (ParaView: open the .vtu or the .pvd series).
+// Points are always written; the other fields become point data.
+pub fn write_vtu(path: &Path, records: &[ExportRecord], fields: &ExportFields) -> std::io::Result<()> {
+    let n = records.len();
+    let mut out = String::new();
+    let _ = writeln!(out, r#"<?xml version="1.0"?>"#);
+    let _ = writeln!(out, r#"<VTKFile type="UnstructuredGrid" version="0.1" byte_order="LittleEndian">"#);
+    let _ = writeln!(out, "<UnstructuredGrid>");
+    let _ = writeln!(out, r#"<Piece NumberOfPoints="{n}" NumberOfCells="{n}">"#);
+
+    // 1. POINTS
+    let _ = writeln!(out, "<Points>");
+    let _ = writeln!(out, r#"<DataArray type="Float32" NumberOfComponents="3" format="ascii">"#);
+    for record in records {
+        let p = record.position;
+        let _ = writeln!(out, "{} {} {}", p.x, p.y, p.z);
+    }
+    let _ = writeln!(out, "</DataArray>");
+    let _ = writeln!(out, "</Points>");
+
+    // 2. CELLS (one vertex per point)
+    let _ = writeln!(out, "<Cells>");
+    let _ = writeln!(out, r#"<DataArray type="Int32" Name="connectivity" format="ascii">"#);
+    for i in 0..n {
+        let _ = writeln!(out, "{i}");
+    }
+    let _ = writeln!(out, "</DataArray>");
+    let _ = writeln!(out, r#"<DataArray type="Int32" Name="offsets" format="ascii">"#);
+    for i in 1..=n {
+        let _ = writeln!(out, "{i}");
+    }
+    let _ = writeln!(out, "</DataArray>");
+    let _ = writeln!(out, r#"<DataArray type="UInt8" Name="types" format="ascii">"#);
+    for _ in 0..n {
+        let _ = writeln!(out, "1"); // VTK_VERTEX
+    }
+    let _ = writeln!(out, "</DataArray>");
+    let _ = writeln!(out, "</Cells>");
+
+    // 3. POINT DATA
+    let _ = writeln!(out, "<PointData>");
+    if fields.velocity {
+        let _ = writeln!(out, r#"<DataArray type="Float32" Name="velocity" NumberOfComponents="3" format="ascii">"#);
+        for record in records {
+            let v = record.velocity;
+            let _ = writeln!(out, "{} {} {}", v.x, v.y, v.z);
+        }
+        let _ = writeln!(out, "</DataArray>");
+    }
+    if fields.color {
+        let _ = writeln!(out, r#"<DataArray type="Float32" Name="color" NumberOfComponents="4" format="ascii">"#);
+        for record in records {
+            let c = record.color;
+            let _ = writeln!(out, "{} {} {} {}", c.x, c.y, c.z, c.w);
+        }
+        let _ = writeln!(out, "</DataArray>");
+    }
+    if fields.population {
+        let _ = writeln!(out, r#"<DataArray type="UInt32" Name="population" format="ascii">"#);
+        for record in records {
+            let _ = writeln!(out, "{}", record.population);
+        }
+        let _ = writeln!(out, "</DataArray>");
+    }
+    let _ = writeln!(out, "</PointData>");
+
+    let _ = writeln!(out, "</Piece>");
+    let _ = writeln!(out, "</UnstructuredGrid>");
+    let _ = writeln!(out, "</VTKFile>");
+    std::fs::write(path, out)
+}
+
+// ParaView collection tying a .vtu series to simulation time
+pub fn write_pvd(path: &Path, steps: &[(f32, String)]) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    writeln!(file, r#"<?xml version="1.0"?>"#)?;
+    writeln!(file, r#"<VTKFile type="Collection" version="0.1" byte_order="LittleEndian">"#)?;
+    writeln!(file, "<Collection>")?;
+    for (time, name) in steps {
+        writeln!(file, r#"<DataSet timestep="{time}" group="" part="0" file="{name}"/>"#)?;
+    }
+    writeln!(file, "</Collection>")?;
+    writeln!(file, "</VTKFile>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<ExportRecord> {
+        vec![
+            ExportRecord { position: Vec3::new(1.0, 2.0, 3.0), velocity: Vec3::X, color: Vec4::ONE, population: 1 },
+            ExportRecord { position: Vec3::new(-1.0, 0.5, 0.0), velocity: Vec3::Y, color: Vec4::ZERO, population: 4 },
+        ]
+    }
+
+    #[test]
+    fn npy_header_is_aligned_and_sized() {
+        let path = std::env::temp_dir().join(format!("physics_engine_export_test_{}.npy", std::process::id()));
+        let fields = ExportFields { color: false, ..ExportFields::default() };
+        write_npy(&path, &records(), &fields).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(&bytes[..8], b"\x93NUMPY\x01\x00");
+        let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        let header = std::str::from_utf8(&bytes[10..10 + header_len]).unwrap();
+        assert!(header.contains("('population', '<u4')") && header.contains("'shape': (2,)"));
+        // 6 floats + 1 u32 per record
+        assert_eq!(bytes.len() - 10 - header_len, 2 * 28);
+    }
+
+    #[test]
+    fn csv_round_trips_through_the_table_importer() {
+        let path = std::env::temp_dir().join(format!("physics_engine_export_test_{}.csv", std::process::id()));
+        write_csv(&path, &records(), &ExportFields::default()).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let imported = crate::io::table::parse_table(&text, &Default::default()).unwrap();
+        assert_eq!(imported.len(), 2);
+        assert_eq!(imported[0].position, Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(imported[1].velocity, Vec3::Y);
+    }
+}
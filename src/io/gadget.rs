@@ -0,0 +1,262 @@
+use bevy::math::Vec3;
+use std::path::{Path, PathBuf};
+use crate::io::{ImportError, ImportedParticle};
+
+// GADGET-2 snapshot reader (SnapFormat 1 and 2, either byte order, multi-file snapshots).
+// Blocks read: HEAD, POS, VEL, ID (skipped), MASS (only for types with a zero mass-table entry).
+
+const HEADER_SIZE: usize = 256;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GadgetHeader {
+    pub npart: [u32; 6],      // Particles of each type in this file
+    pub mass_table: [f64; 6], // Per-type mass; 0 = individual masses in the MASS block
+    pub time: f64,            // Scale factor a for cosmological runs
+    pub redshift: f64,
+    pub num_files: u32,
+    pub box_size: f64,
+}
+
+pub struct GadgetSnapshot {
+    pub header: GadgetHeader,
+    pub particles: Vec<ImportedParticle>,
+}
+
+// Reads `path`, or every `path.N` file if the snapshot is split.
+// Cosmological snapshots store u = v / √a; `cosmological` converts back to peculiar velocity.
+pub fn read_snapshot(path: &Path, cosmological: bool) -> Result<GadgetSnapshot, ImportError> {
+    let mut snapshot = parse_snapshot(&std::fs::read(path)?)?;
+
+    // Multi-file snapshots: snapshot_000.0, snapshot_000.1, ...
+    if snapshot.header.num_files > 1 && path.extension().is_some_and(|ext| ext == "0") {
+        for file in 1..snapshot.header.num_files {
+            let part_path: PathBuf = path.with_extension(file.to_string());
+            let part = parse_snapshot(&std::fs::read(&part_path)?)?;
+            snapshot.particles.extend(part.particles);
+        }
+    }
+
+    if cosmological {
+        let factor = snapshot.header.time.sqrt() as f32;
+        for particle in snapshot.particles.iter_mut() {
+            particle.velocity *= factor;
+        }
+    }
+    Ok(snapshot)
+}
+
+pub fn parse_snapshot(bytes: &[u8]) -> Result<GadgetSnapshot, ImportError> {
+    let mut reader = RecordReader::detect(bytes)?;
+
+    // 1. HEADER
+    let head = reader.next_block()?;
+    if head.len() < HEADER_SIZE {
+        return Err(ImportError::Format(format!("header block is {} bytes, expected {HEADER_SIZE}", head.len())));
+    }
+    let mut header = GadgetHeader::default();
+    for i in 0..6 {
+        header.npart[i] = reader.u32_at(head, 4 * i);
+        header.mass_table[i] = reader.f64_at(head, 24 + 8 * i);
+    }
+    header.time = reader.f64_at(head, 72);
+    header.redshift = reader.f64_at(head, 80);
+    header.num_files = reader.u32_at(head, 124);
+    header.box_size = reader.f64_at(head, 128);
+
+    let total: usize = header.npart.iter().map(|&n| n as usize).sum();
+
+    // 2. POSITIONS and VELOCITIES (float3 per particle, ordered by type)
+    let positions = reader.vec3_block(total, "POS")?;
+    let velocities = reader.vec3_block(total, "VEL")?;
+
+    // 3. IDS (not needed, but the block must be stepped over)
+    if total > 0 {
+        reader.next_block()?;
+    }
+
+    // 4. MASSES for the types without a fixed mass
+    let variable_mass: usize = (0..6)
+        .filter(|&i| header.mass_table[i] == 0.0)
+        .map(|i| header.npart[i] as usize)
+        .sum();
+    let masses = if variable_mass > 0 {
+        let block = reader.next_block()?;
+        if block.len() < variable_mass * 4 {
+            return Err(ImportError::Format(format!("MASS block holds {} bytes, expected {}", block.len(), variable_mass * 4)));
+        }
+        (0..variable_mass).map(|i| reader.f32_at(block, 4 * i)).collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut particles = Vec::with_capacity(total);
+    let mut index = 0;
+    let mut mass_index = 0;
+    for kind in 0..6 {
+        for _ in 0..header.npart[kind] {
+            let mass = if header.mass_table[kind] == 0.0 {
+                mass_index += 1;
+                masses[mass_index - 1]
+            } else {
+                header.mass_table[kind] as f32
+            };
+            particles.push(ImportedParticle {
+                position: positions[index],
+                velocity: velocities[index],
+                mass,
+                kind: kind as u32,
+            });
+            index += 1;
+        }
+    }
+
+    Ok(GadgetSnapshot { header, particles })
+}
+
+// Walks the Fortran records (4-byte length, payload, 4-byte length)
+struct RecordReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    big_endian: bool,
+    labelled: bool, // SnapFormat 2: every block is preceded by an 8-byte "LABL" + size record
+}
+
+impl<'a> RecordReader<'a> {
+    fn detect(bytes: &'a [u8]) -> Result<Self, ImportError> {
+        if bytes.len() < 4 {
+            return Err(ImportError::Format("file too short for a GADGET snapshot".into()));
+        }
+        let marker = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        let (big_endian, labelled) = match (u32::from_le_bytes(marker), u32::from_be_bytes(marker)) {
+            (256, _) => (false, false),
+            (8, _) => (false, true),
+            (_, 256) => (true, false),
+            (_, 8) => (true, true),
+            (other, _) => return Err(ImportError::Format(format!("unrecognised first record length {other}"))),
+        };
+        Ok(Self { bytes, offset: 0, big_endian, labelled })
+    }
+
+    fn read_u32(&self, at: usize) -> Result<u32, ImportError> {
+        let slice = self
+            .bytes
+            .get(at..at + 4)
+            .ok_or_else(|| ImportError::Format("unexpected end of file".into()))?;
+        Ok(self.u32_at(slice, 0))
+    }
+
+    fn record(&mut self) -> Result<&'a [u8], ImportError> {
+        let length = self.read_u32(self.offset)? as usize;
+        let start = self.offset + 4;
+        let end = start + length;
+        if self.read_u32(end)? as usize != length {
+            return Err(ImportError::Format(format!("corrupt record markers at byte {}", self.offset)));
+        }
+        self.offset = end + 4;
+        Ok(&self.bytes[start..end])
+    }
+
+    fn next_block(&mut self) -> Result<&'a [u8], ImportError> {
+        if self.labelled {
+            self.record()?; // Block label
+        }
+        self.record()
+    }
+
+    fn vec3_block(&mut self, count: usize, name: &str) -> Result<Vec<Vec3>, ImportError> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        let block = self.next_block()?;
+        if block.len() < count * 12 {
+            return Err(ImportError::Format(format!("{name} block holds {} bytes, expected {}", block.len(), count * 12)));
+        }
+        Ok((0..count)
+            .map(|i| Vec3::new(self.f32_at(block, 12 * i), self.f32_at(block, 12 * i + 4), self.f32_at(block, 12 * i + 8)))
+            .collect())
+    }
+
+    fn u32_at(&self, block: &[u8], at: usize) -> u32 {
+        let raw = [block[at], block[at + 1], block[at + 2], block[at + 3]];
+        if self.big_endian { u32::from_be_bytes(raw) } else { u32::from_le_bytes(raw) }
+    }
+
+    fn f32_at(&self, block: &[u8], at: usize) -> f32 {
+        f32::from_bits(self.u32_at(block, at))
+    }
+
+    fn f64_at(&self, block: &[u8], at: usize) -> f64 {
+        let mut raw = [0u8; 8];
+        raw.copy_from_slice(&block[at..at + 8]);
+        if self.big_endian { f64::from_be_bytes(raw) } else { f64::from_le_bytes(raw) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Builds a two-particle snapshot: one gas particle (individual mass) and one halo particle (mass table)
+    fn snapshot_bytes(big_endian: bool, labelled: bool) -> Vec<u8> {
+        let u32_bytes = |v: u32| if big_endian { v.to_be_bytes() } else { v.to_le_bytes() };
+        let f32_bytes = |v: f32| u32_bytes(v.to_bits());
+        let f64_bytes = |v: f64| if big_endian { v.to_be_bytes() } else { v.to_le_bytes() };
+
+        let mut header = vec![0u8; HEADER_SIZE];
+        header[0..4].copy_from_slice(&u32_bytes(1)); // 1 gas
+        header[4..8].copy_from_slice(&u32_bytes(1)); // 1 halo
+        header[32..40].copy_from_slice(&f64_bytes(2.5)); // Halo mass from the table
+        header[72..80].copy_from_slice(&f64_bytes(0.25)); // a
+        header[124..128].copy_from_slice(&u32_bytes(1));
+
+        let floats = |values: &[f32]| values.iter().flat_map(|&v| f32_bytes(v)).collect::<Vec<u8>>();
+        let blocks: Vec<(&[u8; 4], Vec<u8>)> = vec![
+            (b"HEAD", header),
+            (b"POS ", floats(&[1.0, 2.0, 3.0, -4.0, -5.0, -6.0])),
+            (b"VEL ", floats(&[10.0, 0.0, 0.0, 0.0, 20.0, 0.0])),
+            (b"ID  ", [1u32, 2].iter().flat_map(|&id| u32_bytes(id)).collect()),
+            (b"MASS", floats(&[0.5])),
+        ];
+
+        let mut bytes = Vec::new();
+        for (label, payload) in blocks {
+            if labelled {
+                bytes.extend(u32_bytes(8));
+                bytes.extend(label);
+                bytes.extend(u32_bytes(payload.len() as u32 + 8));
+                bytes.extend(u32_bytes(8));
+            }
+            bytes.extend(u32_bytes(payload.len() as u32));
+            bytes.extend(&payload);
+            bytes.extend(u32_bytes(payload.len() as u32));
+        }
+        bytes
+    }
+
+    #[test]
+    fn reads_every_layout() {
+        for big_endian in [false, true] {
+            for labelled in [false, true] {
+                let snapshot = parse_snapshot(&snapshot_bytes(big_endian, labelled)).unwrap();
+                assert_eq!(snapshot.header.npart, [1, 1, 0, 0, 0, 0]);
+                assert_eq!(snapshot.header.time, 0.25);
+
+                let gas = snapshot.particles[0];
+                assert_eq!((gas.kind, gas.mass), (0, 0.5));
+                assert_eq!(gas.position, Vec3::new(1.0, 2.0, 3.0));
+                assert_eq!(gas.velocity, Vec3::new(10.0, 0.0, 0.0));
+
+                let halo = snapshot.particles[1];
+                assert_eq!((halo.kind, halo.mass), (1, 2.5));
+                assert_eq!(halo.position, Vec3::new(-4.0, -5.0, -6.0));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        let bytes = snapshot_bytes(false, false);
+        assert!(parse_snapshot(&bytes[..bytes.len() / 2]).is_err());
+        assert!(parse_snapshot(&[1, 2]).is_err());
+    }
+}
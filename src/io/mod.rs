@@ -0,0 +1,64 @@
+use bevy::math::Vec3;
+use std::fmt;
+
+pub mod camera_path;
+pub mod diagnostic_log;
+pub mod export;
+pub mod gadget;
+pub mod png;
+pub mod table;
+
+// One body read from a file, before it is mapped onto a population
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImportedParticle {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub mass: f32,
+    pub kind: u32, // GADGET particle type (0 = gas, 1 = halo, 2 = disk, 3 = bulge, 4 = stars, 5 = boundary); 0 for tables
+}
+
+// Multiplicative factors taking file units to simulation units
+#[derive(Clone, Copy, Debug)]
+pub struct UnitConversion {
+    pub length: f32,
+    pub velocity: f32,
+    pub mass: f32,
+}
+
+impl Default for UnitConversion {
+    fn default() -> Self {
+        Self { length: 1.0, velocity: 1.0, mass: 1.0 }
+    }
+}
+
+impl UnitConversion {
+    pub fn apply(&self, particle: ImportedParticle) -> ImportedParticle {
+        ImportedParticle {
+            position: particle.position * self.length,
+            velocity: particle.velocity * self.velocity,
+            mass: particle.mass * self.mass,
+            kind: particle.kind,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ImportError {
+    Io(std::io::Error),
+    Format(String),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportError::Io(error) => write!(f, "{error}"),
+            ImportError::Format(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for ImportError {
+    fn from(error: std::io::Error) -> Self {
+        ImportError::Io(error)
+    }
+}
@@ -0,0 +1,40 @@
+use std::path::Path;
+
+// GPU copies pad each row to 256 bytes; keeps the first `row_bytes` of every row
+pub fn unpad_rows(data: &[u8], padded_row_bytes: usize, row_bytes: usize, rows: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(row_bytes * rows);
+    for row in data.chunks(padded_row_bytes).take(rows) {
+        out.extend_from_slice(&row[..row_bytes]);
+    }
+    out
+}
+
+// 8-bit sRGB RGBA, tightly packed
+pub fn write_png(path: &Path, width: u32, height: u32, rgba: &[u8]) -> image::ImageResult<()> {
+    image::save_buffer_with_format(path, rgba, width, height, image::ColorType::Rgba8, image::ImageFormat::Png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpadded_frame_round_trips_through_png() {
+        // 3x2 frame, rows padded to 16 bytes
+        let mut padded = vec![0u8; 32];
+        for row in 0..2 {
+            for x in 0..12 {
+                padded[row * 16 + x] = (row * 12 + x) as u8;
+            }
+        }
+        let rgba = unpad_rows(&padded, 16, 12, 2);
+        assert_eq!(rgba, (0..24).collect::<Vec<u8>>());
+
+        let path = std::env::temp_dir().join(format!("physics_engine_png_test_{}.png", std::process::id()));
+        write_png(&path, 3, 2, &rgba).unwrap();
+        let decoded = image::open(&path).unwrap().to_rgba8();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(decoded.dimensions(), (3, 2));
+        assert_eq!(decoded.into_raw(), rgba);
+    }
+}
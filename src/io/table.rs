@@ -0,0 +1,121 @@
+use bevy::math::Vec3;
+use crate::io::{ImportError, ImportedParticle};
+
+// Which column holds which quantity (0-based)
+#[derive(Clone, Copy, Debug)]
+pub struct TableColumns {
+    pub x: usize,
+    pub y: usize,
+    pub z: usize,
+    pub vx: usize,
+    pub vy: usize,
+    pub vz: usize,
+    pub mass: Option<usize>, // Missing masses default to 1
+}
+
+impl Default for TableColumns {
+    fn default() -> Self {
+        Self { x: 0, y: 1, z: 2, vx: 3, vy: 4, vz: 5, mass: Some(6) }
+    }
+}
+
+impl TableColumns {
+    // Maps named columns from a header line ("x y z vx vy vz mass").
+    // Returns None unless at least x, y and z are named.
+    fn from_header(names: &[&str]) -> Option<Self> {
+        let find = |candidates: &[&str]| {
+            names
+                .iter()
+                .position(|name| candidates.iter().any(|c| name.eq_ignore_ascii_case(c)))
+        };
+        Some(Self {
+            x: find(&["x", "pos_x"])?,
+            y: find(&["y", "pos_y"])?,
+            z: find(&["z", "pos_z"])?,
+            vx: find(&["vx", "vel_x"]).unwrap_or(usize::MAX),
+            vy: find(&["vy", "vel_y"]).unwrap_or(usize::MAX),
+            vz: find(&["vz", "vel_z"]).unwrap_or(usize::MAX),
+            mass: find(&["mass", "m"]),
+        })
+    }
+}
+
+fn split_fields(line: &str) -> Vec<&str> {
+    line.split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+        .filter(|field| !field.is_empty())
+        .collect()
+}
+
+// CSV or whitespace-separated ASCII. Lines starting with '#' or '%' are comments,
+// except a commented header naming the columns. A header row also overrides `columns`.
+pub fn parse_table(text: &str, columns: &TableColumns) -> Result<Vec<ImportedParticle>, ImportError> {
+    let mut columns = *columns;
+    let mut particles = Vec::new();
+    let mut seen_data = false;
+
+    for (line_number, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        // 1. COMMENTS and HEADERS
+        let comment = trimmed.starts_with('#') || trimmed.starts_with('%');
+        let fields = split_fields(trimmed.trim_start_matches(['#', '%']));
+        let numeric = fields.iter().all(|field| field.parse::<f32>().is_ok());
+        if comment || !numeric {
+            if !seen_data {
+                if let Some(named) = TableColumns::from_header(&fields) {
+                    columns = named;
+                }
+            } else if !comment {
+                return Err(ImportError::Format(format!("line {}: non-numeric row", line_number + 1)));
+            }
+            continue;
+        }
+        seen_data = true;
+
+        // 2. DATA
+        let value = |column: usize| fields.get(column).and_then(|field| field.parse::<f32>().ok());
+        let required = |column: usize| {
+            value(column).ok_or_else(|| ImportError::Format(format!("line {}: missing column {}", line_number + 1, column + 1)))
+        };
+        particles.push(ImportedParticle {
+            position: Vec3::new(required(columns.x)?, required(columns.y)?, required(columns.z)?),
+            velocity: Vec3::new(
+                value(columns.vx).unwrap_or(0.0),
+                value(columns.vy).unwrap_or(0.0),
+                value(columns.vz).unwrap_or(0.0),
+            ),
+            mass: columns.mass.and_then(value).unwrap_or(1.0),
+            kind: 0,
+        });
+    }
+    Ok(particles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_headed_csv_and_plain_ascii() {
+        let csv = "# exported\nmass,vx,vy,vz,x,y,z\n2,1,0,0,10,20,30\n3,0,1,0,-1,-2,-3\n";
+        let particles = parse_table(csv, &TableColumns::default()).unwrap();
+        assert_eq!(particles.len(), 2);
+        assert_eq!(particles[0].position, Vec3::new(10.0, 20.0, 30.0));
+        assert_eq!(particles[0].velocity, Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(particles[1].mass, 3.0);
+
+        let ascii = "% x y z\n1 2 3\n\n4\t5\t6\n";
+        let particles = parse_table(ascii, &TableColumns::default()).unwrap();
+        assert_eq!(particles[1].position, Vec3::new(4.0, 5.0, 6.0));
+        assert_eq!((particles[1].velocity, particles[1].mass), (Vec3::ZERO, 1.0));
+    }
+
+    #[test]
+    fn reports_bad_rows() {
+        assert!(parse_table("1 2 3\n4 5\n", &TableColumns::default()).is_err());
+        assert!(parse_table("1 2 3\nfoo bar baz\n", &TableColumns::default()).is_err());
+    }
+}
@@ -1,24 +1,117 @@
 use bevy::prelude::*;
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::render::settings::WgpuSettings;
+use bevy::render::RenderPlugin;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::ExitCondition;
+use bevy::winit::WinitPlugin;
+use std::time::Duration;
 
+mod io;
 mod physics;
 mod simulation;
+mod viz;
 
+use simulation::black_hole::BlackHolePlugin;
 use simulation::camera::{FlyCamera, FlyCameraPlugin};
+use simulation::camera_path::{CameraPathConfig, CameraPathPlugin};
+use simulation::capture::{CaptureConfig, CapturePlugin};
+use simulation::color_map::{ColorMapConfig, ColorMapPlugin};
+use simulation::current_field::{CurrentFieldPlugin, PicConfig};
+use simulation::debug_viz::{EtherVizPlugin, FlowVizConfig};
 use simulation::emitter::EmitterPlugin;
+use simulation::escape::{EscapeConfig, EscapePlugin};
+use simulation::ether::EtherPlugin;
+use simulation::ether_volume::{EtherVolumeConfig, EtherVolumePlugin};
+use simulation::export::{ExportConfig, ExportPlugin};
 use simulation::galaxy::GalaxyPlugin;
 use simulation::gpu_galaxy::GpuGalaxyPlugin;
+use simulation::hud::{HudConfig, HudPlugin};
+use simulation::import::{ImportConfig, ImportPlugin};
+use simulation::inspector::InspectorPlugin;
+use simulation::jet::JetPlugin;
+use simulation::magnetic_field::MagneticFieldPlugin;
+use simulation::particle_render::ParticleRenderPlugin;
+use simulation::paths::{PathConfig, PathPlugin};
 use simulation::plasma::PlasmaPlugin;
+use simulation::plots::{PlotConfig, PlotsPlugin};
 use simulation::render_settings::RenderSettingsPlugin;
+use simulation::scenario::Scenario;
+use simulation::software_render::{SoftwareRenderConfig, SoftwareRenderPlugin};
+use simulation::spatial_index::SpatialIndexPlugin;
+use simulation::sph::SphPlugin;
+use simulation::trails::{TrailConfig, TrailPlugin};
 
 fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins)
-        .add_plugins(RenderSettingsPlugin)
+    // `--headless`: no window and no GPU; pair with `--software-render` or `--export`
+    let headless = std::env::args().any(|arg| arg == "--headless");
+
+    let mut app = App::new();
+    app.insert_resource(Scenario::from_args())
+        .insert_resource(ImportConfig::from_args())
+        .insert_resource(ExportConfig::from_args())
+        .insert_resource(CameraPathConfig::from_args())
+        .insert_resource(PicConfig::from_args())
+        .insert_resource(CaptureConfig::from_args())
+        .insert_resource(SoftwareRenderConfig::from_args())
+        .insert_resource(TrailConfig::from_args())
+        .insert_resource(PathConfig::from_args())
+        .insert_resource(ColorMapConfig::from_args())
+        .insert_resource(EtherVolumeConfig::from_args())
+        .insert_resource(FlowVizConfig::from_args())
+        .insert_resource(EscapeConfig::from_args())
+        .insert_resource(HudConfig::from_args())
+        .insert_resource(PlotConfig::from_args());
+
+    if headless {
+        app.add_plugins(
+            DefaultPlugins
+                .set(RenderPlugin {
+                    render_creation: WgpuSettings { backends: None, ..default() }.into(),
+                    ..default()
+                })
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                })
+                .disable::<WinitPlugin>(),
+        )
+        .add_plugins(ScheduleRunnerPlugin::run_loop(Duration::ZERO))
+        // Nothing paces the loop, so each update is one 60 Hz simulation tick
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(1.0 / 60.0)));
+    } else {
+        app.add_plugins(DefaultPlugins)
+            .add_plugins(CapturePlugin);
+    }
+
+    app.add_plugins(RenderSettingsPlugin)
         .add_plugins(FlyCameraPlugin)
+        .add_plugins(CameraPathPlugin)
         .add_plugins(GalaxyPlugin)
+        .add_plugins(BlackHolePlugin)
         .add_plugins(GpuGalaxyPlugin)
+        .add_plugins(MagneticFieldPlugin)
+        .add_plugins(CurrentFieldPlugin)
+        .add_plugins(EtherPlugin)
         .add_plugins(PlasmaPlugin)
+        .add_plugins(ParticleRenderPlugin)
+        .add_plugins(TrailPlugin)
+        .add_plugins(PathPlugin)
+        .add_plugins(ColorMapPlugin)
+        .add_plugins(EtherVolumePlugin)
+        .add_plugins(EtherVizPlugin)
+        .add_plugins(JetPlugin)
+        .add_plugins(EscapePlugin)
+        .add_plugins(SpatialIndexPlugin)
+        .add_plugins(SphPlugin)
         .add_plugins(EmitterPlugin)
+        .add_plugins(ImportPlugin)
+        .add_plugins(ExportPlugin)
+        .add_plugins(SoftwareRenderPlugin)
+        .add_plugins(InspectorPlugin)
+        .add_plugins(HudPlugin)
+        .add_plugins(PlotsPlugin)
         .add_systems(Update, close_on_esc)
         .add_systems(Startup, setup_camera)
         .run();
@@ -0,0 +1,70 @@
+use bevy::math::Vec3;
+
+// Whether a particle is held by its galaxy, on its way out, or riding a jet
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Binding {
+    Bound,
+    Escaping,
+    JetBorne,
+}
+
+// One particle relative to its nearest core, under the force model that moves it
+#[derive(Clone, Copy, Debug)]
+pub struct Orbit {
+    pub offset: Vec3,            // From the core
+    pub velocity: Vec3,          // Relative to the core
+    pub potential: f32,          // Φ at the particle
+    pub boundary_potential: f32, // Φ where the particle would cross the outer boundary
+    pub drag: f32,               // Linear drag coefficient (work drag·|v| per unit distance)
+}
+
+// Escaping: past `escape_radius`, or moving outward with enough energy to climb to the
+// boundary at `boundary_radius` and pay for the drag on the way.
+pub fn classify(orbit: &Orbit, jet_borne: bool, escape_radius: f32, boundary_radius: f32) -> Binding {
+    if jet_borne {
+        return Binding::JetBorne;
+    }
+    let r = orbit.offset.length();
+    if r > escape_radius {
+        return Binding::Escaping;
+    }
+    let outward = orbit.offset.dot(orbit.velocity) > 0.0;
+    let speed = orbit.velocity.length();
+    let energy = 0.5 * speed * speed + orbit.potential;
+    let barrier = orbit.boundary_potential + orbit.drag * speed * (boundary_radius - r).max(0.0);
+    if outward && energy >= barrier {
+        Binding::Escaping
+    } else {
+        Binding::Bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_outward_orbits_that_clear_the_barrier_escape() {
+        // Logarithmic suction potential Φ = ln r: circular speed 1, barrier ln 100 at r = 100
+        let orbit = |offset: Vec3, velocity: Vec3, drag: f32| Orbit {
+            offset,
+            velocity,
+            potential: offset.length().ln(),
+            boundary_potential: 100f32.ln(),
+            drag,
+        };
+        let x = Vec3::new(10.0, 0.0, 0.0);
+        let circular = orbit(x, Vec3::Z, 0.0);
+        assert_eq!(classify(&circular, false, 80.0, 100.0), Binding::Bound);
+        assert_eq!(classify(&circular, true, 80.0, 100.0), Binding::JetBorne);
+
+        // ½v² ≈ 3.1 clears ln(100/10) ≈ 2.3, but only when moving out and without drag
+        let fast = Vec3::new(2.5, 0.0, 0.0);
+        assert_eq!(classify(&orbit(x, fast, 0.0), false, 80.0, 100.0), Binding::Escaping);
+        assert_eq!(classify(&orbit(x, -fast, 0.0), false, 80.0, 100.0), Binding::Bound);
+        assert_eq!(classify(&orbit(x, fast, 0.1), false, 80.0, 100.0), Binding::Bound);
+
+        // Past the escape radius anything counts as escaping
+        assert_eq!(classify(&orbit(x * 9.0, -fast, 0.0), false, 80.0, 100.0), Binding::Escaping);
+    }
+}
@@ -0,0 +1,201 @@
+use bevy::math::{UVec3, Vec3};
+use crate::physics::math::FluxQuaternion;
+
+// Grid of the Fluid Ether: w is the pressure, (x, y, z) the flow.
+// Each step follows assets/shaders/ether_compute.wgsl (diffuse w, add the curl of the flow,
+// drain the sinks, Golden Drag), plus the pressure-gradient push that lets the sinks draw
+// the flow in, damping, and a boundary held at the ambient pressure.
+pub struct EtherGrid {
+    pub dims: UVec3,
+    pub cell_size: f32,
+    pub origin: Vec3, // World position of node (0, 0, 0)
+    pub cells: Vec<FluxQuaternion>,
+    scratch: Vec<FluxQuaternion>,
+}
+
+pub const AMBIENT_PRESSURE: f32 = 1.0;
+
+// A drain in the ether (a black hole)
+#[derive(Clone, Copy, Debug)]
+pub struct EtherSink {
+    pub position: Vec3,
+    pub radius: f32,
+    pub rate: f32, // Pressure removed per second inside the radius
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EtherStep {
+    pub dt: f32,
+    pub pressure_push: f32, // Flow acceleration per unit pressure gradient
+    pub damping: f32,       // Fraction of the flow lost per second; above 1 / cell_size the curl term cannot run away
+}
+
+impl EtherGrid {
+    pub fn new(dims: UVec3, cell_size: f32, center: Vec3) -> Self {
+        let count = (dims.x * dims.y * dims.z) as usize;
+        let ambient = FluxQuaternion::new(AMBIENT_PRESSURE, 0.0, 0.0, 0.0);
+        let mut grid = Self {
+            dims,
+            cell_size,
+            origin: Vec3::ZERO,
+            cells: vec![ambient; count],
+            scratch: vec![ambient; count],
+        };
+        grid.recenter(center);
+        grid
+    }
+
+    // Move the grid so that `center` sits in the middle of the domain.
+    pub fn recenter(&mut self, center: Vec3) {
+        self.origin = center - self.extent() * 0.5;
+    }
+
+    pub fn extent(&self) -> Vec3 {
+        (self.dims - UVec3::ONE).as_vec3() * self.cell_size
+    }
+
+    pub fn index(&self, x: u32, y: u32, z: u32) -> usize {
+        (x + self.dims.x * (y + self.dims.y * z)) as usize
+    }
+
+    // Neighbour reads clamp to the edge, as textureLoad does in the shader
+    fn at(&self, x: i32, y: i32, z: i32) -> FluxQuaternion {
+        let max = self.dims.as_ivec3() - 1;
+        self.cells[self.index(x.clamp(0, max.x) as u32, y.clamp(0, max.y) as u32, z.clamp(0, max.z) as u32)]
+    }
+
+    fn flow(q: FluxQuaternion) -> Vec3 {
+        Vec3::new(q.x, q.y, q.z)
+    }
+
+    // Central-difference curl of the flow at a node
+    pub fn curl(&self, x: u32, y: u32, z: u32) -> Vec3 {
+        let (x, y, z) = (x as i32, y as i32, z as i32);
+        let inv_2h = 0.5 / self.cell_size;
+        let d_dx = (Self::flow(self.at(x + 1, y, z)) - Self::flow(self.at(x - 1, y, z))) * inv_2h;
+        let d_dy = (Self::flow(self.at(x, y + 1, z)) - Self::flow(self.at(x, y - 1, z))) * inv_2h;
+        let d_dz = (Self::flow(self.at(x, y, z + 1)) - Self::flow(self.at(x, y, z - 1))) * inv_2h;
+        Vec3::new(d_dy.z - d_dz.y, d_dz.x - d_dx.z, d_dx.y - d_dy.x)
+    }
+
+    // |∇×flow| at every node, in cell order
+    pub fn vorticity(&self) -> Vec<f32> {
+        let mut result = Vec::with_capacity(self.cells.len());
+        for z in 0..self.dims.z {
+            for y in 0..self.dims.y {
+                for x in 0..self.dims.x {
+                    result.push(self.curl(x, y, z).length());
+                }
+            }
+        }
+        result
+    }
+
+    pub fn step(&mut self, step: EtherStep, sinks: &[EtherSink]) {
+        let (nx, ny, nz) = (self.dims.x, self.dims.y, self.dims.z);
+        let inv_2h = 0.5 / self.cell_size;
+        let mut next = std::mem::take(&mut self.scratch);
+
+        for z in 0..nz {
+            for y in 0..ny {
+                for x in 0..nx {
+                    let (ix, iy, iz) = (x as i32, y as i32, z as i32);
+                    let current = self.at(ix, iy, iz);
+                    let i = self.index(x, y, z);
+                    let boundary = x == 0 || y == 0 || z == 0 || x == nx - 1 || y == ny - 1 || z == nz - 1;
+
+                    // 1. DIFFUSION: pressure relaxes to the mean of its 3×3×3 block
+                    let mut w_sum = 0.0;
+                    for dz in -1..=1 {
+                        for dy in -1..=1 {
+                            for dx in -1..=1 {
+                                w_sum += self.at(ix + dx, iy + dy, iz + dz).w;
+                            }
+                        }
+                    }
+                    let mut w = if boundary { AMBIENT_PRESSURE } else { w_sum / 27.0 };
+
+                    // 2. ADVECTION by the curl, and the push down the pressure gradient
+                    let gradient = Vec3::new(
+                        self.at(ix + 1, iy, iz).w - self.at(ix - 1, iy, iz).w,
+                        self.at(ix, iy + 1, iz).w - self.at(ix, iy - 1, iz).w,
+                        self.at(ix, iy, iz + 1).w - self.at(ix, iy, iz - 1).w,
+                    ) * inv_2h;
+                    let mut flow = Self::flow(current) + (self.curl(x, y, z) - gradient * step.pressure_push) * step.dt;
+                    flow *= (1.0 - step.damping * step.dt).max(0.0);
+
+                    // 3. SINKS drain the pressure
+                    let position = self.origin + UVec3::new(x, y, z).as_vec3() * self.cell_size;
+                    for sink in sinks {
+                        if position.distance_squared(sink.position) < sink.radius * sink.radius {
+                            w -= sink.rate * step.dt;
+                        }
+                    }
+
+                    // 4. GOLDEN DRAG
+                    next[i] = FluxQuaternion::new(w.max(0.0), flow.x, flow.y, flow.z).apply_golden_drag();
+                }
+            }
+        }
+        self.scratch = std::mem::replace(&mut self.cells, next);
+    }
+
+    // Trilinear interpolation of (pressure, flow) at a world position (None outside the grid)
+    pub fn sample(&self, pos: Vec3) -> Option<FluxQuaternion> {
+        let local = (pos - self.origin) / self.cell_size;
+        let base = local.floor();
+        let max = (self.dims - UVec3::splat(2)).as_vec3();
+        if base.x < 0.0 || base.y < 0.0 || base.z < 0.0 || base.cmpgt(max).any() {
+            return None;
+        }
+        let frac = local - base;
+        let (bx, by, bz) = (base.x as u32, base.y as u32, base.z as u32);
+
+        let mut result = FluxQuaternion::new(0.0, 0.0, 0.0, 0.0);
+        for corner in 0..8u32 {
+            let (dx, dy, dz) = (corner & 1, (corner >> 1) & 1, (corner >> 2) & 1);
+            let wx = if dx == 1 { frac.x } else { 1.0 - frac.x };
+            let wy = if dy == 1 { frac.y } else { 1.0 - frac.y };
+            let wz = if dz == 1 { frac.z } else { 1.0 - frac.z };
+            let q = self.cells[self.index(bx + dx, by + dy, bz + dz)];
+            let weight = wx * wy * wz;
+            result = FluxQuaternion::new(
+                result.w + q.w * weight,
+                result.x + q.x * weight,
+                result.y + q.y * weight,
+                result.z + q.z * weight,
+            );
+        }
+        Some(result)
+    }
+
+    pub fn flow_at(&self, pos: Vec3) -> Option<Vec3> {
+        self.sample(pos).map(Self::flow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_sink_digs_a_pressure_well_and_draws_the_flow_in() {
+        let mut grid = EtherGrid::new(UVec3::new(21, 21, 21), 1.0, Vec3::ZERO);
+        let sink = EtherSink { position: Vec3::ZERO, radius: 1.5, rate: 2.0 };
+        let step = EtherStep { dt: 0.05, pressure_push: 4.0, damping: 1.5 };
+        for _ in 0..200 {
+            grid.step(step, &[sink]);
+        }
+
+        let node = |x: u32, y: u32, z: u32| grid.cells[grid.index(x, y, z)];
+        let (centre, edge) = (node(10, 10, 10), node(18, 10, 10));
+        assert!(centre.w < edge.w && edge.w <= AMBIENT_PRESSURE);
+        assert!(grid.cells.iter().all(|q| q.w.is_finite() && q.x.is_finite() && q.y.is_finite() && q.z.is_finite()));
+
+        // Along +Z the Golden Drag (a rotation about Z) leaves the inflow pointing home
+        assert!(node(10, 10, 15).z < 0.0);
+        assert_eq!(grid.flow_at(Vec3::new(0.0, 0.0, 5.0)).map(|flow| flow.z), Some(node(10, 10, 15).z));
+        assert!(grid.sample(Vec3::splat(50.0)).is_none());
+        assert_eq!(grid.vorticity().len(), grid.cells.len());
+    }
+}
@@ -0,0 +1,369 @@
+use bevy::math::Vec3;
+use rand::Rng;
+use std::f32::consts::{PI, TAU};
+
+// Gravitational constant in simulation units
+pub const GRAVITY: f32 = 1.0;
+
+// Σ(R) = M / (2π Rd²) · exp(-R/Rd), vertical sech²(z/z0) profile
+#[derive(Clone, Copy, Debug)]
+pub struct ExponentialDisk {
+    pub mass: f32,
+    pub scale_length: f32,
+    pub scale_height: f32,
+    pub truncation: f32, // No particles beyond this radius
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpheroidProfile {
+    Hernquist, // ρ ∝ 1 / (r (r + a)³)
+    Plummer,   // ρ ∝ (1 + r²/a²)^(-5/2)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Bulge {
+    pub profile: SpheroidProfile,
+    pub mass: f32,
+    pub scale_radius: f32,
+}
+
+// ρ ∝ 1 / (x (1 + x)²) with x = r / rs, truncated at r = c·rs
+#[derive(Clone, Copy, Debug)]
+pub struct NfwHalo {
+    pub mass: f32, // Mass inside the truncation radius
+    pub scale_radius: f32,
+    pub concentration: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GalaxyComponent {
+    Disk,
+    Bulge,
+    Halo,
+}
+
+// How a population samples the model: the fraction of its particles drawn from each
+// component, and the fraction of the disk particles placed on the spiral arms.
+#[derive(Clone, Copy, Debug)]
+pub struct ComponentMix {
+    pub disk: f32,
+    pub bulge: f32,
+    pub halo: f32,
+    pub arm_fraction: f32,
+}
+
+// One sampled particle, in the galaxy's local frame (disk in XZ, rotating about +Y)
+#[derive(Clone, Copy, Debug)]
+pub struct Body {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub arm: u32,
+}
+
+// Disk + optional bulge + optional dark halo.
+// Every component contributes to the rotation curve, whichever ones are sampled.
+#[derive(Clone, Copy, Debug)]
+pub struct GalaxyModel {
+    pub disk: ExponentialDisk,
+    pub bulge: Option<Bulge>,
+    pub halo: Option<NfwHalo>,
+    pub toomre_q: f32,     // Q > 1 keeps the disk stable against local collapse
+    pub arms: u32,
+    pub pitch_angle: f32,  // Angle between the arm and the circle (rad)
+    pub arm_width: f32,    // Angular spread of the arms (rad, 1σ)
+}
+
+impl Default for GalaxyModel {
+    fn default() -> Self {
+        Self {
+            disk: ExponentialDisk {
+                mass: 6000.0,
+                scale_length: 15.0,
+                scale_height: 1.0,
+                truncation: 100.0,
+            },
+            bulge: Some(Bulge {
+                profile: SpheroidProfile::Hernquist,
+                mass: 1500.0,
+                scale_radius: 3.0,
+            }),
+            halo: Some(NfwHalo {
+                mass: 15000.0,
+                scale_radius: 20.0,
+                concentration: 5.0,
+            }),
+            toomre_q: 1.5,
+            arms: 2,
+            // tan(pitch) = 1/φ: the arms follow theta = ln(r) * φ, like the Z-Pinch target
+            pitch_angle: (1.0f32 / 1.618034).atan(),
+            arm_width: 0.15,
+        }
+    }
+}
+
+impl ExponentialDisk {
+    pub fn surface_density(&self, r: f32) -> f32 {
+        let rd = self.scale_length;
+        self.mass / (TAU * rd * rd) * (-r / rd).exp()
+    }
+
+    // Spherical approximation of the disk mass inside r
+    pub fn enclosed_mass(&self, r: f32) -> f32 {
+        let x = r.min(self.truncation) / self.scale_length;
+        self.mass * (1.0 - (1.0 + x) * (-x).exp())
+    }
+
+    // R·Σ(R) is a Gamma(2, Rd) distribution: the sum of two exponential deviates
+    fn sample_radius(&self, rng: &mut impl Rng) -> f32 {
+        loop {
+            let u: f32 = rng.gen_range(f32::EPSILON..1.0);
+            let v: f32 = rng.gen_range(f32::EPSILON..1.0);
+            let r = -self.scale_length * (u * v).ln();
+            if r <= self.truncation {
+                return r;
+            }
+        }
+    }
+
+    fn sample_height(&self, rng: &mut impl Rng) -> f32 {
+        let u: f32 = rng.gen_range(-0.999..0.999);
+        self.scale_height * u.atanh()
+    }
+}
+
+impl Bulge {
+    pub fn enclosed_mass(&self, r: f32) -> f32 {
+        let a = self.scale_radius;
+        match self.profile {
+            SpheroidProfile::Hernquist => self.mass * r * r / ((r + a) * (r + a)),
+            SpheroidProfile::Plummer => self.mass * r.powi(3) / (r * r + a * a).powf(1.5),
+        }
+    }
+
+    // Inverse of the cumulative mass profile (the outer 1% is cut off)
+    fn sample_radius(&self, rng: &mut impl Rng) -> f32 {
+        let u: f32 = rng.gen_range(0.0..0.99);
+        let a = self.scale_radius;
+        match self.profile {
+            SpheroidProfile::Hernquist => a * u.sqrt() / (1.0 - u.sqrt()),
+            SpheroidProfile::Plummer => a / (u.max(f32::EPSILON).powf(-2.0 / 3.0) - 1.0).sqrt(),
+        }
+    }
+}
+
+impl NfwHalo {
+    fn profile_mass(x: f32) -> f32 {
+        (1.0 + x).ln() - x / (1.0 + x)
+    }
+
+    pub fn truncation(&self) -> f32 {
+        self.scale_radius * self.concentration
+    }
+
+    pub fn enclosed_mass(&self, r: f32) -> f32 {
+        let x = r.min(self.truncation()) / self.scale_radius;
+        self.mass * Self::profile_mass(x) / Self::profile_mass(self.concentration)
+    }
+
+    // No closed-form inverse: bisect the cumulative profile
+    fn sample_radius(&self, rng: &mut impl Rng) -> f32 {
+        let target = rng.gen_range(0.0..1.0) * Self::profile_mass(self.concentration);
+        let (mut lo, mut hi) = (0.0, self.concentration);
+        for _ in 0..32 {
+            let mid = 0.5 * (lo + hi);
+            if Self::profile_mass(mid) < target {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        0.5 * (lo + hi) * self.scale_radius
+    }
+}
+
+impl ComponentMix {
+    fn choose(&self, u: f32, model: &GalaxyModel) -> GalaxyComponent {
+        let disk = self.disk.max(0.0);
+        let bulge = if model.bulge.is_some() { self.bulge.max(0.0) } else { 0.0 };
+        let halo = if model.halo.is_some() { self.halo.max(0.0) } else { 0.0 };
+        let total = disk + bulge + halo;
+        if total <= 0.0 {
+            return GalaxyComponent::Disk;
+        }
+        let pick = u * total;
+        if pick < disk {
+            GalaxyComponent::Disk
+        } else if pick < disk + bulge {
+            GalaxyComponent::Bulge
+        } else {
+            GalaxyComponent::Halo
+        }
+    }
+}
+
+fn gaussian(rng: &mut impl Rng) -> f32 {
+    // Box–Muller
+    let u: f32 = rng.gen_range(f32::EPSILON..1.0);
+    let v: f32 = rng.gen_range(0.0..1.0);
+    (-2.0 * u.ln()).sqrt() * (TAU * v).cos()
+}
+
+fn random_direction(rng: &mut impl Rng) -> Vec3 {
+    let z: f32 = rng.gen_range(-1.0..1.0);
+    let azimuth = rng.gen_range(0.0..TAU);
+    let ring = (1.0 - z * z).sqrt();
+    Vec3::new(ring * azimuth.cos(), z, ring * azimuth.sin())
+}
+
+impl GalaxyModel {
+    pub fn enclosed_mass(&self, r: f32) -> f32 {
+        self.disk.enclosed_mass(r)
+            + self.bulge.map_or(0.0, |bulge| bulge.enclosed_mass(r))
+            + self.halo.map_or(0.0, |halo| halo.enclosed_mass(r))
+    }
+
+    pub fn circular_speed(&self, r: f32) -> f32 {
+        let r = r.max(0.1);
+        (GRAVITY * self.enclosed_mass(r) / r).sqrt()
+    }
+
+    // κ² = R dΩ²/dR + 4Ω²
+    pub fn epicyclic_frequency(&self, r: f32) -> f32 {
+        let r = r.max(0.1);
+        let omega_sq = |r: f32| (self.circular_speed(r) / r).powi(2);
+        let h = 0.01 * r;
+        let d_omega_sq = (omega_sq(r + h) - omega_sq(r - h)) / (2.0 * h);
+        (r * d_omega_sq + 4.0 * omega_sq(r)).max(0.0).sqrt()
+    }
+
+    // Stellar Toomre criterion: Q = σ_R κ / (3.36 G Σ)
+    pub fn radial_dispersion(&self, r: f32) -> f32 {
+        let kappa = self.epicyclic_frequency(r).max(1e-3);
+        let sigma = self.toomre_q * 3.36 * GRAVITY * self.disk.surface_density(r) / kappa;
+        sigma.min(self.circular_speed(r))
+    }
+
+    // Angle of arm `arm` at cylindrical radius r (logarithmic spiral)
+    pub fn arm_angle(&self, arm: u32, r: f32) -> f32 {
+        let arms = self.arms.max(1);
+        TAU * arm as f32 / arms as f32 + r.max(0.1).ln() / self.pitch_angle.tan()
+    }
+
+    pub fn sample(&self, index: usize, mix: &ComponentMix, rng: &mut impl Rng) -> Body {
+        match mix.choose(rng.gen_range(0.0..1.0), self) {
+            GalaxyComponent::Disk => self.sample_disk(index, mix.arm_fraction, rng),
+            GalaxyComponent::Bulge => {
+                let r = self.bulge.map_or(0.0, |bulge| bulge.sample_radius(rng));
+                self.sample_spheroid(r, rng)
+            }
+            GalaxyComponent::Halo => {
+                let r = self.halo.map_or(0.0, |halo| halo.sample_radius(rng));
+                self.sample_spheroid(r, rng)
+            }
+        }
+    }
+
+    fn sample_disk(&self, index: usize, arm_fraction: f32, rng: &mut impl Rng) -> Body {
+        // 1. POSITION (on an arm, or anywhere on the ring)
+        let r = self.disk.sample_radius(rng);
+        let arm = (index % self.arms.max(1) as usize) as u32;
+        let phi = if rng.gen_range(0.0..1.0) < arm_fraction {
+            self.arm_angle(arm, r) + gaussian(rng) * self.arm_width
+        } else {
+            rng.gen_range(0.0..TAU)
+        };
+        let z = self.disk.sample_height(rng);
+        let radial = Vec3::new(phi.cos(), 0.0, phi.sin());
+        let tangent = Vec3::new(-phi.sin(), 0.0, phi.cos());
+
+        // 2. DISPERSIONS (Toomre-stable radial, epicyclic azimuthal, isothermal-sheet vertical)
+        let v_circ = self.circular_speed(r);
+        let omega = v_circ / r.max(0.1);
+        let kappa = self.epicyclic_frequency(r);
+        let sigma_r = self.radial_dispersion(r);
+        let sigma_phi = sigma_r * kappa / (2.0 * omega).max(1e-3);
+        let sigma_z = (PI * GRAVITY * self.disk.surface_density(r) * self.disk.scale_height).sqrt();
+
+        // 3. ASYMMETRIC DRIFT: hot disks rotate slower than the circular speed
+        let drift = sigma_r * sigma_r
+            * (1.0 - kappa * kappa / (4.0 * omega * omega).max(1e-6) - 2.0 * r / self.disk.scale_length);
+        let v_phi = (v_circ * v_circ + drift).max(0.0).sqrt();
+
+        let velocity = radial * gaussian(rng) * sigma_r
+            + tangent * (v_phi + gaussian(rng) * sigma_phi)
+            + Vec3::Y * gaussian(rng) * sigma_z;
+
+        Body {
+            position: radial * r + Vec3::Y * z,
+            velocity,
+            arm,
+        }
+    }
+
+    // Isotropic, non-rotating spheroid with the virial estimate σ = v_c / √3
+    fn sample_spheroid(&self, r: f32, rng: &mut impl Rng) -> Body {
+        let sigma = self.circular_speed(r) / 3.0f32.sqrt();
+        let velocity = Vec3::new(gaussian(rng), gaussian(rng), gaussian(rng)) * sigma;
+        Body {
+            position: random_direction(rng) * r,
+            velocity,
+            arm: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    // Fraction of sampled bodies inside r should match M(<r) / M_total
+    fn check_cumulative(model: &GalaxyModel, mix: &ComponentMix, mass: impl Fn(f32) -> f32, total: f32) {
+        let mut rng = StdRng::seed_from_u64(7);
+        let radii: Vec<f32> = (0..20_000).map(|i| model.sample(i, mix, &mut rng).position.length()).collect();
+        for r in [2.0, 5.0, 15.0, 40.0] {
+            let inside = radii.iter().filter(|&&radius| radius < r).count() as f32 / radii.len() as f32;
+            let expected = mass(r) / total;
+            assert!((inside - expected).abs() < 0.03, "r={r}: sampled {inside}, expected {expected}");
+        }
+    }
+
+    #[test]
+    fn spheroids_follow_their_mass_profiles() {
+        let halo_only = ComponentMix { disk: 0.0, bulge: 0.0, halo: 1.0, arm_fraction: 0.0 };
+        let model = GalaxyModel::default();
+        let halo = model.halo.unwrap();
+        check_cumulative(&model, &halo_only, |r| halo.enclosed_mass(r), halo.mass);
+
+        let bulge_only = ComponentMix { disk: 0.0, bulge: 1.0, halo: 0.0, arm_fraction: 0.0 };
+        for profile in [SpheroidProfile::Hernquist, SpheroidProfile::Plummer] {
+            let bulge = Bulge { profile, mass: 1500.0, scale_radius: 3.0 };
+            let model = GalaxyModel { bulge: Some(bulge), ..GalaxyModel::default() };
+            // The sampler drops the outer 1% of the mass
+            check_cumulative(&model, &bulge_only, |r| bulge.enclosed_mass(r).min(0.99 * bulge.mass), 0.99 * bulge.mass);
+        }
+    }
+
+    #[test]
+    fn disk_is_toomre_stable_and_rotating() {
+        let model = GalaxyModel::default();
+        for r in [5.0, 15.0, 30.0, 60.0] {
+            let kappa = model.epicyclic_frequency(r);
+            let q = model.radial_dispersion(r) * kappa / (3.36 * GRAVITY * model.disk.surface_density(r));
+            assert!(q >= model.toomre_q - 1e-3, "r={r}: Q={q}");
+        }
+
+        let disk_only = ComponentMix { disk: 1.0, bulge: 0.0, halo: 0.0, arm_fraction: 1.0 };
+        let mut rng = StdRng::seed_from_u64(11);
+        let bodies: Vec<Body> = (0..5000).map(|i| model.sample(i, &disk_only, &mut rng)).collect();
+        // Same sense as the other spawners: along (-z, 0, x)
+        let mean_rotation = bodies
+            .iter()
+            .map(|body| Vec3::new(-body.position.z, 0.0, body.position.x).normalize().dot(body.velocity))
+            .sum::<f32>()
+            / bodies.len() as f32;
+        assert!(mean_rotation > 10.0, "mean rotation {mean_rotation}");
+        assert!(bodies.iter().all(|body| body.position.length() <= model.disk.truncation + 10.0));
+    }
+}
@@ -1 +1,9 @@
-pub mod math;
\ No newline at end of file
+pub mod electromagnetism;
+pub mod escape;
+pub mod ether;
+pub mod initial_conditions;
+pub mod math;
+pub mod pic;
+pub mod spatial_hash;
+pub mod sph_kernel;
+pub mod streamlines;
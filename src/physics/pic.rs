@@ -0,0 +1,231 @@
+use bevy::math::{UVec3, Vec3};
+use crate::physics::electromagnetism::ETHER_PERMEABILITY;
+
+// Particle-In-Cell current grid.
+// Particles deposit their current element (q·v) onto the nodes of a uniform grid,
+// the vector potential is solved from ∇²A = -μ₀J, and B = ∇×A.
+// The μ₀I/2πr pinch is not prescribed anywhere: it emerges from the solve.
+pub struct CurrentGrid {
+    pub dims: UVec3,
+    pub cell_size: f32,
+    pub origin: Vec3, // World position of node (0, 0, 0)
+    pub current: Vec<Vec3>,
+    pub potential: Vec<Vec3>,
+    pub field: Vec<Vec3>,
+}
+
+impl CurrentGrid {
+    pub fn new(dims: UVec3, cell_size: f32, center: Vec3) -> Self {
+        let count = (dims.x * dims.y * dims.z) as usize;
+        let mut grid = Self {
+            dims,
+            cell_size,
+            origin: Vec3::ZERO,
+            current: vec![Vec3::ZERO; count],
+            potential: vec![Vec3::ZERO; count],
+            field: vec![Vec3::ZERO; count],
+        };
+        grid.recenter(center);
+        grid
+    }
+
+    // Move the grid so that `center` sits in the middle of the domain.
+    pub fn recenter(&mut self, center: Vec3) {
+        let half_extent = (self.dims - UVec3::ONE).as_vec3() * self.cell_size * 0.5;
+        self.origin = center - half_extent;
+    }
+
+    fn index(&self, x: u32, y: u32, z: u32) -> usize {
+        (x + self.dims.x * (y + self.dims.y * z)) as usize
+    }
+
+    pub fn clear_current(&mut self) {
+        self.current.fill(Vec3::ZERO);
+    }
+
+    // Cloud-In-Cell deposit of one particle's current element onto its 8 surrounding nodes.
+    // Particles outside the grid do not deposit.
+    pub fn deposit(&mut self, pos: Vec3, charge: f32, velocity: Vec3) {
+        let local = (pos - self.origin) / self.cell_size;
+        let base = local.floor();
+        let max = (self.dims - UVec3::splat(2)).as_vec3();
+        if base.x < 0.0 || base.y < 0.0 || base.z < 0.0 || base.cmpgt(max).any() {
+            return;
+        }
+        let frac = local - base;
+        let (bx, by, bz) = (base.x as u32, base.y as u32, base.z as u32);
+
+        let cell_volume = self.cell_size * self.cell_size * self.cell_size;
+        let density = velocity * (charge / cell_volume);
+
+        for corner in 0..8u32 {
+            let (dx, dy, dz) = (corner & 1, (corner >> 1) & 1, (corner >> 2) & 1);
+            let wx = if dx == 1 { frac.x } else { 1.0 - frac.x };
+            let wy = if dy == 1 { frac.y } else { 1.0 - frac.y };
+            let wz = if dz == 1 { frac.z } else { 1.0 - frac.z };
+            let index = self.index(bx + dx, by + dy, bz + dz);
+            self.current[index] += density * (wx * wy * wz);
+        }
+    }
+
+    // Solve ∇²A = -μ₀J with red-black Successive Over-Relaxation.
+    // A = 0 on the domain boundary. The previous solution is the warm start,
+    // so a handful of iterations per tick is enough once the run has settled.
+    pub fn solve_potential(&mut self, iterations: usize) {
+        const OVER_RELAXATION: f32 = 1.85;
+        let h_sq = self.cell_size * self.cell_size;
+        let (nx, ny, nz) = (self.dims.x, self.dims.y, self.dims.z);
+        let stride_y = nx as usize;
+        let stride_z = (nx * ny) as usize;
+
+        for _ in 0..iterations {
+            for color in 0..2 {
+                for z in 1..nz - 1 {
+                    for y in 1..ny - 1 {
+                        let start = 1 + (1 + y + z + color) % 2;
+                        for x in (start..nx - 1).step_by(2) {
+                            let i = self.index(x, y, z);
+                            let neighbors = self.potential[i - 1]
+                                + self.potential[i + 1]
+                                + self.potential[i - stride_y]
+                                + self.potential[i + stride_y]
+                                + self.potential[i - stride_z]
+                                + self.potential[i + stride_z];
+                            let target = (neighbors + self.current[i] * (ETHER_PERMEABILITY * h_sq)) / 6.0;
+                            let previous = self.potential[i];
+                            self.potential[i] = previous + (target - previous) * OVER_RELAXATION;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // B = ∇×A with central differences on interior nodes.
+    pub fn compute_field(&mut self) {
+        let inv_2h = 0.5 / self.cell_size;
+        let (nx, ny, nz) = (self.dims.x, self.dims.y, self.dims.z);
+        let stride_y = nx as usize;
+        let stride_z = (nx * ny) as usize;
+
+        self.field.fill(Vec3::ZERO);
+        for z in 1..nz - 1 {
+            for y in 1..ny - 1 {
+                for x in 1..nx - 1 {
+                    let i = self.index(x, y, z);
+                    let d_dx = (self.potential[i + 1] - self.potential[i - 1]) * inv_2h;
+                    let d_dy = (self.potential[i + stride_y] - self.potential[i - stride_y]) * inv_2h;
+                    let d_dz = (self.potential[i + stride_z] - self.potential[i - stride_z]) * inv_2h;
+                    self.field[i] = Vec3::new(
+                        d_dy.z - d_dz.y,
+                        d_dz.x - d_dx.z,
+                        d_dx.y - d_dy.x,
+                    );
+                }
+            }
+        }
+    }
+
+    // Trilinear interpolation of B at a world position (zero outside the grid).
+    pub fn sample_field(&self, pos: Vec3) -> Vec3 {
+        let local = (pos - self.origin) / self.cell_size;
+        let base = local.floor();
+        let max = (self.dims - UVec3::splat(2)).as_vec3();
+        if base.x < 0.0 || base.y < 0.0 || base.z < 0.0 || base.cmpgt(max).any() {
+            return Vec3::ZERO;
+        }
+        let frac = local - base;
+        let (bx, by, bz) = (base.x as u32, base.y as u32, base.z as u32);
+
+        let mut result = Vec3::ZERO;
+        for corner in 0..8u32 {
+            let (dx, dy, dz) = (corner & 1, (corner >> 1) & 1, (corner >> 2) & 1);
+            let wx = if dx == 1 { frac.x } else { 1.0 - frac.x };
+            let wy = if dy == 1 { frac.y } else { 1.0 - frac.y };
+            let wz = if dz == 1 { frac.z } else { 1.0 - frac.z };
+            result += self.field[self.index(bx + dx, by + dy, bz + dz)] * (wx * wy * wz);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Net current element stored on the grid: Σ J · h³
+    fn total_current(grid: &CurrentGrid) -> Vec3 {
+        let cell_volume = grid.cell_size * grid.cell_size * grid.cell_size;
+        grid.current.iter().copied().sum::<Vec3>() * cell_volume
+    }
+
+    // A straight current along Z through the middle of the grid, built from
+    // particles spaced evenly along the wire.
+    fn line_current_grid(current: f32) -> CurrentGrid {
+        let mut grid = CurrentGrid::new(UVec3::new(41, 41, 41), 1.0, Vec3::ZERO);
+        let particles_per_unit = 4;
+        let speed = 10.0;
+        let charge = current / (particles_per_unit as f32 * speed);
+        for i in -20 * particles_per_unit..=20 * particles_per_unit {
+            let z = i as f32 / particles_per_unit as f32;
+            grid.deposit(Vec3::new(0.0, 0.0, z), charge, Vec3::Z * speed);
+        }
+        grid.solve_potential(400);
+        grid.compute_field();
+        grid
+    }
+
+    #[test]
+    fn deposit_conserves_current() {
+        let mut grid = CurrentGrid::new(UVec3::new(16, 16, 16), 2.0, Vec3::ZERO);
+        let particles = [
+            (Vec3::new(1.3, -2.7, 0.4), 1.0, Vec3::new(3.0, 0.0, -1.0)),
+            (Vec3::new(-5.1, 4.9, 7.2), -0.5, Vec3::new(0.0, 2.0, 8.0)),
+            (Vec3::new(0.0, 0.0, 0.0), 2.0, Vec3::new(-1.0, 1.0, 1.0)),
+        ];
+        let mut expected = Vec3::ZERO;
+        for (pos, charge, velocity) in particles {
+            grid.deposit(pos, charge, velocity);
+            expected += velocity * charge;
+        }
+        assert!((total_current(&grid) - expected).length() < 1e-4);
+    }
+
+    #[test]
+    fn particles_outside_grid_do_not_deposit() {
+        let mut grid = CurrentGrid::new(UVec3::new(8, 8, 8), 1.0, Vec3::ZERO);
+        grid.deposit(Vec3::new(100.0, 0.0, 0.0), 1.0, Vec3::X);
+        grid.deposit(Vec3::new(0.0, -4.0, 0.0), 1.0, Vec3::X);
+        assert_eq!(total_current(&grid), Vec3::ZERO);
+    }
+
+    #[test]
+    fn line_current_field_follows_ampere() {
+        let current = 50.0;
+        let grid = line_current_grid(current);
+
+        for r in [3.0, 4.0, 6.0] {
+            let b = grid.sample_field(Vec3::new(r, 0.0, 0.0));
+            let expected = ETHER_PERMEABILITY * current / (2.0 * std::f32::consts::PI * r);
+
+            // Right-hand rule: current along +Z, field along +Y on the +X side
+            assert!(b.y > 0.0);
+            assert!(b.x.abs() < 0.05 * b.y && b.z.abs() < 0.05 * b.y);
+            let ratio = b.y / expected;
+            assert!((0.8..1.2).contains(&ratio), "r = {r}: B = {}, expected {expected}", b.y);
+        }
+    }
+
+    #[test]
+    fn parallel_currents_are_pinched() {
+        let grid = line_current_grid(50.0);
+
+        // A charge moving with the current on either side is pulled back toward the wire
+        for pos in [Vec3::new(4.0, 0.0, 0.0), Vec3::new(0.0, -5.0, 0.0)] {
+            let velocity = Vec3::Z * 10.0;
+            let force = velocity.cross(grid.sample_field(pos));
+            assert!(force.dot(-pos) > 0.0);
+            assert!(force.dot(Vec3::Z).abs() < 1e-3);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::math::{IVec3, Vec3};
+use bevy::utils::HashMap;
+
+// Uniform hash grid: buckets item indices by the cell their position falls in.
+// Radius queries only visit the cells overlapping the search sphere.
+pub struct SpatialHash {
+    pub cell_size: f32,
+    cells: HashMap<IVec3, Vec<usize>>,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::default(),
+        }
+    }
+
+    pub fn cell_of(&self, pos: Vec3) -> IVec3 {
+        (pos / self.cell_size).floor().as_ivec3()
+    }
+
+    // Keeps the bucket allocations around so rebuilding every tick stays cheap.
+    pub fn clear(&mut self) {
+        for bucket in self.cells.values_mut() {
+            bucket.clear();
+        }
+    }
+
+    pub fn insert(&mut self, index: usize, pos: Vec3) {
+        let cell = self.cell_of(pos);
+        self.cells.entry(cell).or_default().push(index);
+    }
+
+    // Calls `visit` with every index stored in a cell overlapping the sphere.
+    // Candidates may lie outside `radius`; callers do the exact distance test.
+    pub fn for_each_candidate(&self, pos: Vec3, radius: f32, mut visit: impl FnMut(usize)) {
+        let min = self.cell_of(pos - Vec3::splat(radius));
+        let max = self.cell_of(pos + Vec3::splat(radius));
+        for z in min.z..=max.z {
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    if let Some(bucket) = self.cells.get(&IVec3::new(x, y, z)) {
+                        for &index in bucket {
+                            visit(index);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use bevy::math::Vec3;
+use std::f32::consts::PI;
+
+// Smoothing kernels for SPH. Both have compact support of radius 2h.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SphKernel {
+    CubicSpline, // Monaghan M4 B-spline
+    WendlandC2,  // No pairing instability, smoother density field
+}
+
+impl SphKernel {
+    pub fn name(self) -> &'static str {
+        match self {
+            SphKernel::CubicSpline => "cubic spline",
+            SphKernel::WendlandC2 => "Wendland C2",
+        }
+    }
+
+    pub fn support_radius(&self, h: f32) -> f32 {
+        2.0 * h
+    }
+
+    // W(r, h)
+    pub fn value(&self, r: f32, h: f32) -> f32 {
+        let q = r / h;
+        match self {
+            SphKernel::CubicSpline => {
+                let sigma = 1.0 / (PI * h * h * h);
+                if q < 1.0 {
+                    sigma * (1.0 - 1.5 * q * q + 0.75 * q * q * q)
+                } else if q < 2.0 {
+                    sigma * 0.25 * (2.0 - q).powi(3)
+                } else {
+                    0.0
+                }
+            }
+            SphKernel::WendlandC2 => {
+                let sigma = 21.0 / (16.0 * PI * h * h * h);
+                if q < 2.0 {
+                    sigma * (1.0 - 0.5 * q).powi(4) * (2.0 * q + 1.0)
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    // ∇W for the separation vector `offset` = r_i - r_j
+    pub fn gradient(&self, offset: Vec3, h: f32) -> Vec3 {
+        let r = offset.length();
+        if r < 1e-6 {
+            return Vec3::ZERO;
+        }
+        let q = r / h;
+        let d_w_dr = match self {
+            SphKernel::CubicSpline => {
+                let sigma = 1.0 / (PI * h * h * h);
+                if q < 1.0 {
+                    sigma / h * (-3.0 * q + 2.25 * q * q)
+                } else if q < 2.0 {
+                    sigma / h * (-0.75 * (2.0 - q).powi(2))
+                } else {
+                    0.0
+                }
+            }
+            SphKernel::WendlandC2 => {
+                let sigma = 21.0 / (16.0 * PI * h * h * h);
+                if q < 2.0 {
+                    sigma / h * (-5.0 * q * (1.0 - 0.5 * q).powi(3))
+                } else {
+                    0.0
+                }
+            }
+        };
+        offset * (d_w_dr / r)
+    }
+}
+
+// Monaghan artificial viscosity Π_ij.
+// Only acts on approaching pairs, turning the kinetic energy of a collision into
+// heat so that shocks stay one or two smoothing lengths thick.
+pub fn artificial_viscosity(
+    offset: Vec3,
+    relative_velocity: Vec3,
+    h: f32,
+    sound_speed: f32,
+    mean_density: f32,
+    alpha: f32,
+    beta: f32,
+) -> f32 {
+    let approach = relative_velocity.dot(offset);
+    if approach >= 0.0 {
+        return 0.0;
+    }
+    let mu = h * approach / (offset.length_squared() + 0.01 * h * h);
+    (-alpha * sound_speed * mu + beta * mu * mu) / mean_density
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KERNELS: [SphKernel; 2] = [SphKernel::CubicSpline, SphKernel::WendlandC2];
+
+    #[test]
+    fn kernels_are_normalised_and_vanish_at_the_support_radius() {
+        let h = 1.5;
+        for kernel in KERNELS {
+            // ∫ W dV = ∫ 4πr² W(r) dr over the support (midpoint rule)
+            let support = kernel.support_radius(h);
+            let steps = 2000;
+            let dr = support / steps as f32;
+            let integral: f32 = (0..steps)
+                .map(|i| {
+                    let r = (i as f32 + 0.5) * dr;
+                    4.0 * PI * r * r * kernel.value(r, h) * dr
+                })
+                .sum();
+            assert!((integral - 1.0).abs() < 1e-3, "{kernel:?}: ∫W = {integral}");
+
+            assert!(kernel.value(support * 0.999, h) < 1e-6, "{kernel:?}");
+            assert_eq!(kernel.value(support, h), 0.0);
+            assert_eq!(kernel.gradient(Vec3::X * support * 1.01, h), Vec3::ZERO);
+        }
+    }
+
+    #[test]
+    fn gradient_matches_the_kernel_slope() {
+        let h = 2.0;
+        let direction = Vec3::new(1.0, -2.0, 2.0).normalize();
+        for kernel in KERNELS {
+            for r in [0.5, 1.5, 2.5, 3.5] {
+                let eps = 1e-3;
+                let slope = (kernel.value(r + eps, h) - kernel.value(r - eps, h)) / (2.0 * eps);
+                let gradient = kernel.gradient(direction * r, h);
+                assert!((gradient - direction * slope).length() < 1e-4, "{kernel:?} at r = {r}");
+            }
+        }
+    }
+
+    #[test]
+    fn viscosity_only_damps_approaching_pairs() {
+        let offset = Vec3::new(2.0, 0.0, 0.0);
+        let (h, c_s, rho) = (1.0, 5.0, 1.0);
+        assert_eq!(artificial_viscosity(offset, Vec3::X, h, c_s, rho, 1.0, 2.0), 0.0);
+        assert_eq!(artificial_viscosity(offset, Vec3::Y, h, c_s, rho, 1.0, 2.0), 0.0);
+
+        let slow = artificial_viscosity(offset, -Vec3::X, h, c_s, rho, 1.0, 2.0);
+        let fast = artificial_viscosity(offset, -Vec3::X * 4.0, h, c_s, rho, 1.0, 2.0);
+        assert!(slow > 0.0 && fast > slow);
+        // Denser gas feels proportionally less
+        assert!((artificial_viscosity(offset, -Vec3::X, h, c_s, 2.0 * rho, 1.0, 2.0) - 0.5 * slow).abs() < 1e-6);
+    }
+}
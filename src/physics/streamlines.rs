@@ -0,0 +1,114 @@
+use bevy::math::{Vec2, Vec3};
+
+// Flow visualization over any vector field given as a closure (None outside its domain).
+// Streamlines are integrated at unit speed, so `step` is a length rather than a time.
+
+fn direction(field: &impl Fn(Vec3) -> Option<Vec3>, pos: Vec3) -> Option<Vec3> {
+    field(pos).and_then(|v| v.try_normalize())
+}
+
+// One classical Runge–Kutta step along the field's direction
+pub fn rk4_step(field: &impl Fn(Vec3) -> Option<Vec3>, pos: Vec3, step: f32) -> Option<Vec3> {
+    let k1 = direction(field, pos)?;
+    let k2 = direction(field, pos + k1 * (step * 0.5))?;
+    let k3 = direction(field, pos + k2 * (step * 0.5))?;
+    let k4 = direction(field, pos + k3 * step)?;
+    Some(pos + (k1 + 2.0 * k2 + 2.0 * k3 + k4) * (step / 6.0))
+}
+
+// The streamline through `seed`, upstream end first. Each half stops after `max_steps`,
+// at the edge of the domain or where the flow stagnates.
+pub fn trace_streamline(field: &impl Fn(Vec3) -> Option<Vec3>, seed: Vec3, step: f32, max_steps: usize) -> Vec<Vec3> {
+    let half = |step: f32| {
+        let mut points = Vec::new();
+        let mut pos = seed;
+        for _ in 0..max_steps {
+            let Some(next) = rk4_step(field, pos, step) else { break };
+            points.push(next);
+            pos = next;
+        }
+        points
+    };
+    let mut line = half(-step);
+    line.reverse();
+    line.push(seed);
+    line.extend(half(step));
+    line
+}
+
+// Deterministic white noise in 0..1, one value per pixel
+pub fn white_noise(width: usize, height: usize, seed: u32) -> Vec<f32> {
+    (0..width * height)
+        .map(|i| {
+            let mut h = (i as u32).wrapping_mul(0x9e37_79b9) ^ seed.wrapping_mul(0x85eb_ca6b);
+            h ^= h >> 16;
+            h = h.wrapping_mul(0x7feb_352d);
+            h ^= h >> 15;
+            h = h.wrapping_mul(0x846c_a68b);
+            h ^= h >> 16;
+            (h >> 8) as f32 / (1 << 24) as f32
+        })
+        .collect()
+}
+
+// Line integral convolution: every pixel averages the noise along `half_length` pixels of
+// the streamline on either side. `field` is given in pixel units at a pixel-space position.
+pub fn lic(width: usize, height: usize, noise: &[f32], field: impl Fn(Vec2) -> Option<Vec2>, half_length: usize) -> Vec<f32> {
+    let bounds = Vec2::new(width as f32, height as f32);
+    let texel = |p: Vec2| noise[p.y as usize * width + p.x as usize];
+    let direction = |p: Vec2| field(p).and_then(|v| v.try_normalize());
+
+    let mut result = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let start = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+            let (mut sum, mut count) = (texel(start), 1.0);
+            for sign in [1.0, -1.0] {
+                let mut p = start;
+                for _ in 0..half_length {
+                    // Midpoint step of one pixel
+                    let Some(v1) = direction(p) else { break };
+                    let Some(v2) = direction(p + v1 * (0.5 * sign)) else { break };
+                    p += v2 * sign;
+                    if p.cmplt(Vec2::ZERO).any() || p.cmpge(bounds).any() {
+                        break;
+                    }
+                    sum += texel(p);
+                    count += 1.0;
+                }
+            }
+            result.push(sum / count);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streamlines_follow_circles_and_lic_smears_along_the_flow() {
+        // Rigid rotation about Y: every streamline is a circle
+        let swirl = |p: Vec3| Some(Vec3::new(-p.z, 0.0, p.x));
+        let line = trace_streamline(&swirl, Vec3::new(10.0, 0.0, 0.0), 0.5, 100);
+        assert_eq!(line.len(), 201);
+        assert!(line.iter().all(|p| (p.length() - 10.0).abs() < 1e-3));
+
+        // Stagnant flow stops the trace at the seed
+        let still = |_: Vec3| Some(Vec3::ZERO);
+        assert_eq!(trace_streamline(&still, Vec3::ONE, 0.5, 100), vec![Vec3::ONE]);
+
+        // Flow along +X: rows are smoothed, columns stay independent
+        let (width, height) = (64, 64);
+        let noise = white_noise(width, height, 7);
+        let smeared = lic(width, height, &noise, |_| Some(Vec2::X), 10);
+        let variance = |values: &[f32]| {
+            let mean = values.iter().sum::<f32>() / values.len() as f32;
+            values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / values.len() as f32
+        };
+        assert!(variance(&smeared) < variance(&noise) * 0.2);
+        let along_row = (smeared[32 * width + 30] - smeared[32 * width + 31]).abs();
+        assert!(along_row < 0.1);
+    }
+}
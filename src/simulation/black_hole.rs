@@ -0,0 +1,246 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use crate::simulation::galaxy::{update_stars, BlackHole};
+use crate::simulation::plasma::update_galaxy_physics;
+
+pub struct BlackHolePlugin;
+
+impl Plugin for BlackHolePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(BlackHoleConfig::default())
+            .insert_resource(AccretionLedger::default())
+            .add_systems(Update, update_black_hole_dynamics
+                .after(update_galaxy_physics)
+                .after(update_stars));
+    }
+}
+
+#[derive(Resource)]
+pub struct BlackHoleConfig {
+    pub seed_mass: f32,            // Mass of the core before any accretion
+    pub horizon_radius: f32,       // Event horizon at the seed mass (grows linearly with mass)
+    pub suction_strength: f32,     // Suction at the seed mass (grows linearly with mass)
+    pub max_jet_speed: f32,        // Jet launch speed at a* = 1
+    pub spin_coupling: f32,        // How strongly accreted angular momentum spins up the core
+    pub recoil_coupling: f32,      // Fraction of the jet momentum returned to the core
+    pub reference_accretion_rate: f32, // Particles/s fed to the core that power the jet at a*²
+    pub min_jet_power: f32,        // Floor so a starved core still launches a weak jet
+    pub accretion_smoothing: f32,  // Time constant (s) of the running accretion rate
+}
+
+impl Default for BlackHoleConfig {
+    fn default() -> Self {
+        Self {
+            seed_mass: 1000.0,
+            horizon_radius: 1.0,
+            suction_strength: 5.0,
+            max_jet_speed: 115.0, // a* = 0.7 launches at the classic 80 units/s
+            spin_coupling: 0.001,
+            recoil_coupling: 1.0,
+            reference_accretion_rate: 100.0,
+            min_jet_power: 0.05,
+            accretion_smoothing: 2.0,
+        }
+    }
+}
+
+impl BlackHoleConfig {
+    pub fn mass(&self, black_hole: &BlackHole) -> f32 {
+        self.seed_mass + black_hole.stored_mass as f32
+    }
+
+    // Schwarzschild-like: the horizon grows linearly with mass
+    pub fn event_horizon(&self, black_hole: &BlackHole) -> f32 {
+        self.horizon_radius * self.mass(black_hole) / self.seed_mass
+    }
+
+    pub fn suction(&self, black_hole: &BlackHole) -> f32 {
+        self.suction_strength * self.mass(black_hole) / self.seed_mass
+    }
+
+    // Jet power scales as a*² (Blandford–Znajek) times the accretion rate feeding the core.
+    // Launch speed goes as the square root of the power.
+    pub fn jet_power(&self, black_hole: &BlackHole) -> f32 {
+        let feeding = black_hole.accretion_rate / self.reference_accretion_rate;
+        (black_hole.spin * black_hole.spin * feeding).clamp(self.min_jet_power, 1.0)
+    }
+
+    pub fn jet_speed(&self, black_hole: &BlackHole) -> f32 {
+        self.max_jet_speed * self.jet_power(black_hole).sqrt()
+    }
+
+    pub fn core_state(&self, entity: Entity, transform: &Transform, black_hole: &BlackHole) -> CoreState {
+        CoreState {
+            entity,
+            position: transform.translation,
+            velocity: black_hole.velocity,
+            spin_axis: *transform.up(),
+            mass: self.mass(black_hole),
+            event_horizon: self.event_horizon(black_hole),
+            suction: self.suction(black_hole),
+            jet_speed: self.jet_speed(black_hole),
+        }
+    }
+}
+
+// Read-only view of one black hole, gathered once per tick
+// so the particle loops can feel every center at once.
+#[derive(Clone, Copy)]
+pub struct CoreState {
+    pub entity: Entity,
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub spin_axis: Vec3,
+    pub mass: f32,
+    pub event_horizon: f32,
+    pub suction: f32,
+    pub jet_speed: f32,
+}
+
+impl CoreState {
+    pub fn disk_frame(&self) -> Quat {
+        disk_frame(self.spin_axis)
+    }
+}
+
+// Rotation taking a galaxy's local frame (disk in XZ, spin along +Y) to the world
+pub fn disk_frame(spin_axis: Vec3) -> Quat {
+    Quat::from_rotation_arc(Vec3::Y, spin_axis)
+}
+
+pub fn gather_cores<'a>(
+    config: &BlackHoleConfig,
+    query: impl Iterator<Item = (Entity, &'a Transform, &'a BlackHole)>,
+) -> Vec<CoreState> {
+    query
+        .map(|(entity, transform, black_hole)| config.core_state(entity, transform, black_hole))
+        .collect()
+}
+
+pub fn nearest_core(cores: &[CoreState], pos: Vec3) -> Option<&CoreState> {
+    cores
+        .iter()
+        .min_by(|a, b| a.position.distance_squared(pos).total_cmp(&b.position.distance_squared(pos)))
+}
+
+// The galaxy a particle belongs to. If its black hole has merged away,
+// the particle follows whichever core is now closest.
+pub fn owner_core(cores: &[CoreState], owner: Entity, pos: Vec3) -> Option<&CoreState> {
+    cores
+        .iter()
+        .find(|core| core.entity == owner)
+        .or_else(|| nearest_core(cores, pos))
+}
+
+// Potential of the stars' suction (acceleration suction / max(r, 1)), zero at r = 1
+pub fn suction_potential(cores: &[CoreState], pos: Vec3) -> f32 {
+    cores
+        .iter()
+        .map(|core| {
+            let r = core.position.distance(pos);
+            core.suction * if r > 1.0 { r.ln() } else { r - 1.0 }
+        })
+        .sum()
+}
+
+// Mass-weighted center of all cores (where the PIC grid sits)
+pub fn barycenter(cores: &[CoreState]) -> Vec3 {
+    let total_mass: f32 = cores.iter().map(|core| core.mass).sum();
+    if total_mass <= 0.0 {
+        return Vec3::ZERO;
+    }
+    cores.iter().map(|core| core.position * core.mass).sum::<Vec3>() / total_mass
+}
+
+#[derive(Default)]
+pub struct LedgerEntry {
+    pub captured: usize,
+    pub launched: usize,
+    pub captured_angular_momentum: f32, // Along the spin axis
+    pub jet_momentum: Vec3,             // Total momentum launched into the jets
+}
+
+// What the particle systems handed to each core during this tick.
+// Drained by update_black_hole_dynamics.
+#[derive(Resource, Default)]
+pub struct AccretionLedger {
+    pub entries: HashMap<Entity, LedgerEntry>,
+}
+
+impl AccretionLedger {
+    pub fn capture(&mut self, core: &CoreState, offset: Vec3, velocity: Vec3) {
+        let entry = self.entries.entry(core.entity).or_default();
+        entry.captured += 1;
+        entry.captured_angular_momentum += offset.cross(velocity).dot(core.spin_axis);
+    }
+
+    pub fn launch(&mut self, core: &CoreState, velocity: Vec3) {
+        let entry = self.entries.entry(core.entity).or_default();
+        entry.launched += 1;
+        entry.jet_momentum += velocity;
+    }
+}
+
+fn update_black_hole_dynamics(
+    mut commands: Commands,
+    config: Res<BlackHoleConfig>,
+    mut ledger: ResMut<AccretionLedger>,
+    mut query: Query<(Entity, &mut BlackHole, &mut Transform)>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_seconds();
+    let cores = gather_cores(&config, query.iter().map(|(entity, black_hole, transform)| (entity, transform, black_hole)));
+
+    for (entity, mut black_hole, mut transform) in query.iter_mut() {
+        let entry = ledger.entries.remove(&entity).unwrap_or_default();
+
+        // 1. ACCRETION: captured particles add to the mass
+        black_hole.stored_mass += entry.captured;
+        let mass = config.mass(&black_hole);
+
+        // 2. SPIN: accreted angular momentum winds up (or down) the dynamo
+        let spin = black_hole.spin + config.spin_coupling * entry.captured_angular_momentum / mass;
+        black_hole.spin = spin.clamp(-0.998, 0.998);
+
+        // 3. RECOIL: unbalanced jets kick the core the other way
+        let kick = -entry.jet_momentum * config.recoil_coupling / mass;
+        black_hole.velocity += kick;
+
+        // 4. MUTUAL SUCTION: the other cores pull with the same 1/r law the stars feel
+        for other in cores.iter().filter(|core| core.entity != entity) {
+            let to_other = other.position - transform.translation;
+            let distance = to_other.length().max(1.0);
+            black_hole.velocity += to_other.normalize_or_zero() * (other.suction / distance) * dt;
+        }
+
+        // 5. FEEDING: running rate of material reaching the core (swallowed or launched)
+        if dt > 0.0 {
+            let fed = (entry.captured + entry.launched) as f32 / dt;
+            let blend = 1.0 - (-dt / config.accretion_smoothing).exp();
+            black_hole.accretion_rate += (fed - black_hole.accretion_rate) * blend;
+        }
+
+        // 6. MOTION
+        transform.translation += black_hole.velocity * dt;
+        transform.scale = Vec3::splat(config.event_horizon(&black_hole));
+    }
+    ledger.entries.clear();
+
+    // 7. MERGER: overlapping horizons coalesce; the lighter core is absorbed
+    for (i, a) in cores.iter().enumerate() {
+        for b in cores.iter().skip(i + 1) {
+            if a.position.distance(b.position) > a.event_horizon + b.event_horizon {
+                continue;
+            }
+            let (winner, loser) = if a.mass >= b.mass { (a, b) } else { (b, a) };
+            let Ok((_, mut black_hole, _)) = query.get_mut(winner.entity) else {
+                continue;
+            };
+            let total_mass = winner.mass + loser.mass;
+            black_hole.velocity = (winner.velocity * winner.mass + loser.velocity * loser.mass) / total_mass;
+            black_hole.stored_mass += loser.mass as usize;
+            commands.entity(loser.entity).despawn();
+            return; // One merger per tick keeps the bookkeeping simple
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use bevy::prelude::*;
-use bevy::input::mouse::MouseMotion;
+use bevy::input::mouse::{MouseMotion, MouseWheel};
+use bevy::render::camera::ScalingMode;
 use bevy::window::{CursorGrabMode, PrimaryWindow};
+use crate::simulation::galaxy::{BlackHole, Star};
+use crate::simulation::inspector::Inspection;
+use crate::simulation::plasma::{CloudCentroid, PlasmaParticle};
 
 #[derive(Component)]
 pub struct FlyCamera {
@@ -17,11 +21,70 @@ impl Default for FlyCamera {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrbitTarget {
+    BlackHole, // The heaviest core
+    Centroid,  // Centre of the plasma cloud
+}
+
+// C cycles Fly → Orbit black hole → Orbit centroid → Top-down;
+// F follows the inspected particle, or the one under the crosshair
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CameraMode {
+    #[default]
+    Fly,
+    Orbit(OrbitTarget),
+    Follow(Entity),
+    TopDown, // Orthographic, looking down -Y at the centroid
+}
+
+impl CameraMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            CameraMode::Fly => "fly",
+            CameraMode::Orbit(OrbitTarget::BlackHole) => "orbit black hole",
+            CameraMode::Orbit(OrbitTarget::Centroid) => "orbit centroid",
+            CameraMode::Follow(_) => "follow",
+            CameraMode::TopDown => "top-down",
+        }
+    }
+}
+
+// Spherical rig shared by the orbit, follow and top-down modes
+#[derive(Resource)]
+pub struct OrbitRig {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+    pub ortho_extent: f32, // World units across the top-down view
+    pub pan: Vec3,         // Top-down offset from the centroid
+}
+
+impl Default for OrbitRig {
+    fn default() -> Self {
+        Self {
+            yaw: 0.0,
+            pitch: 0.4,
+            distance: 60.0,
+            ortho_extent: 300.0,
+            pan: Vec3::ZERO,
+        }
+    }
+}
+
 pub struct FlyCameraPlugin;
 
 impl Plugin for FlyCameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (camera_movement, camera_look, cursor_grab));
+        app.init_resource::<CameraMode>()
+            .init_resource::<OrbitRig>()
+            .add_systems(Startup, grab_cursor_on_start)
+            .add_systems(Update, (
+                toggle_cursor_grab,
+                switch_camera_mode,
+                (camera_movement, camera_look).run_if(resource_equals(CameraMode::Fly)),
+                orbit_camera.after(switch_camera_mode),
+            ));
     }
 }
 
@@ -56,14 +119,25 @@ fn camera_movement(
     }
 }
 
+fn cursor_grabbed(window_query: &Query<&Window, With<PrimaryWindow>>) -> bool {
+    window_query
+        .get_single()
+        .is_ok_and(|window| window.cursor.grab_mode != CursorGrabMode::None)
+}
+
 fn camera_look(
     mut mouse_motion: EventReader<MouseMotion>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
     mut query: Query<(&FlyCamera, &mut Transform)>,
 ) {
     let mut delta = Vec2::ZERO;
     for event in mouse_motion.read() {
         delta += event.delta;
     }
+    // A free cursor belongs to the UI
+    if !cursor_grabbed(&window_query) {
+        return;
+    }
 
     for (fly_camera, mut transform) in query.iter_mut() {
         let (mut yaw, mut pitch, _roll) = transform.rotation.to_euler(EulerRot::YXZ);
@@ -77,8 +151,177 @@ fn camera_look(
     }
 }
 
-fn cursor_grab(mut window_query: Query<&mut Window, With<PrimaryWindow>>) {
-    let mut window = window_query.single_mut();
-    window.cursor.grab_mode = CursorGrabMode::Locked;
-    window.cursor.visible = false;
-}
\ No newline at end of file
+fn set_cursor_grab(window: &mut Window, grab: bool) {
+    window.cursor.grab_mode = if grab { CursorGrabMode::Locked } else { CursorGrabMode::None };
+    window.cursor.visible = !grab;
+}
+
+fn grab_cursor_on_start(mut window_query: Query<&mut Window, With<PrimaryWindow>>) {
+    // Headless runs have no window
+    let Ok(mut window) = window_query.get_single_mut() else { return };
+    set_cursor_grab(&mut window, true);
+}
+
+// G frees the cursor for the UI and takes it back
+fn toggle_cursor_grab(keys: Res<ButtonInput<KeyCode>>, mut window_query: Query<&mut Window, With<PrimaryWindow>>) {
+    if !keys.just_pressed(KeyCode::KeyG) {
+        return;
+    }
+    let Ok(mut window) = window_query.get_single_mut() else { return };
+    let grab = window.cursor.grab_mode == CursorGrabMode::None;
+    set_cursor_grab(&mut window, grab);
+}
+
+// Nearest CPU particle to the view ray through the screen centre (by angle)
+fn particle_under_crosshair<'a>(transform: &Transform, particles: impl Iterator<Item = (Entity, &'a Transform)>) -> Option<Entity> {
+    let forward = *transform.forward();
+    particles
+        .filter_map(|(entity, particle)| {
+            let offset = particle.translation - transform.translation;
+            let along = offset.dot(forward);
+            (along > 0.0).then(|| (entity, (offset - forward * along).length() / along))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
+type PickableParticles<'w, 's> = Query<'w, 's, (Entity, &'static Transform), (Or<(With<Star>, With<PlasmaParticle>)>, Without<FlyCamera>)>;
+
+fn switch_camera_mode(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut mode: ResMut<CameraMode>,
+    mut rig: ResMut<OrbitRig>,
+    mut cameras: Query<(&Transform, &mut Projection), With<FlyCamera>>,
+    particles: PickableParticles,
+    inspection: Res<Inspection>,
+) {
+    let Ok((transform, mut projection)) = cameras.get_single_mut() else { return };
+    let next = if keys.just_pressed(KeyCode::KeyC) {
+        match *mode {
+            CameraMode::Fly => CameraMode::Orbit(OrbitTarget::BlackHole),
+            CameraMode::Orbit(OrbitTarget::BlackHole) => CameraMode::Orbit(OrbitTarget::Centroid),
+            CameraMode::Orbit(OrbitTarget::Centroid) => CameraMode::TopDown,
+            CameraMode::Follow(_) | CameraMode::TopDown => CameraMode::Fly,
+        }
+    } else if keys.just_pressed(KeyCode::KeyF) {
+        let inspected = inspection.target.map(|picked| picked.entity);
+        match inspected.or_else(|| particle_under_crosshair(transform, particles.iter())) {
+            Some(entity) => CameraMode::Follow(entity),
+            None => return,
+        }
+    } else {
+        return;
+    };
+
+    // Keep the current view direction so the switch doesn't jump
+    let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+    rig.yaw = yaw;
+    rig.pitch = (-pitch).clamp(-1.5, 1.5);
+    rig.pan = Vec3::ZERO;
+    if let CameraMode::Follow(_) = next {
+        rig.distance = 15.0;
+    }
+
+    *projection = match next {
+        CameraMode::TopDown => Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical(rig.ortho_extent),
+            near: -5000.0,
+            far: 5000.0,
+            ..default()
+        }),
+        _ => Projection::Perspective(PerspectiveProjection::default()),
+    };
+    *mode = next;
+}
+
+#[allow(clippy::too_many_arguments)]
+fn orbit_camera(
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut mode: ResMut<CameraMode>,
+    mut rig: ResMut<OrbitRig>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut cameras: Query<(&FlyCamera, &mut Transform, &mut Projection)>,
+    black_holes: Query<(&Transform, &BlackHole), Without<FlyCamera>>,
+    targets: Query<&Transform, Without<FlyCamera>>,
+    centroid: Res<CloudCentroid>,
+) {
+    if *mode == CameraMode::Fly {
+        return;
+    }
+    let Ok((fly_camera, mut transform, mut projection)) = cameras.get_single_mut() else { return };
+
+    // 1. INPUT: mouse orbits while the cursor is grabbed, the wheel zooms
+    let mut delta = Vec2::ZERO;
+    for event in mouse_motion.read() {
+        delta += event.delta;
+    }
+    if !cursor_grabbed(&window_query) {
+        delta = Vec2::ZERO;
+    }
+    let zoom: f32 = mouse_wheel.read().map(|event| event.y.signum()).sum();
+    rig.yaw -= delta.x * fly_camera.sensitivity;
+    rig.pitch = (rig.pitch + delta.y * fly_camera.sensitivity).clamp(-1.5, 1.5);
+    rig.distance = (rig.distance * 0.9_f32.powf(zoom)).clamp(2.0, 2000.0);
+    rig.ortho_extent = (rig.ortho_extent * 0.9_f32.powf(zoom)).clamp(5.0, 5000.0);
+
+    // 2. TARGET of the current mode
+    let target = match *mode {
+        CameraMode::Orbit(OrbitTarget::BlackHole) | CameraMode::Fly => black_holes
+            .iter()
+            .max_by_key(|(_, black_hole)| black_hole.stored_mass)
+            .map_or(Vec3::ZERO, |(transform, _)| transform.translation),
+        CameraMode::Orbit(OrbitTarget::Centroid) | CameraMode::TopDown => centroid.position,
+        CameraMode::Follow(entity) => match targets.get(entity) {
+            Ok(target) => target.translation,
+            Err(_) => {
+                // The particle was swallowed or expired
+                *mode = CameraMode::Fly;
+                return;
+            }
+        },
+    };
+
+    // 3. PLACE the camera
+    if *mode == CameraMode::TopDown {
+        // WASD pans across the disk plane
+        let mut pan = Vec3::ZERO;
+        for (key, direction) in [(KeyCode::KeyW, -Vec3::Z), (KeyCode::KeyS, Vec3::Z), (KeyCode::KeyA, -Vec3::X), (KeyCode::KeyD, Vec3::X)] {
+            if keys.pressed(key) {
+                pan += direction;
+            }
+        }
+        let pan_speed = rig.ortho_extent * 0.5;
+        rig.pan += pan * pan_speed * time.delta_seconds();
+        if let Projection::Orthographic(orthographic) = &mut *projection {
+            orthographic.scaling_mode = ScalingMode::FixedVertical(rig.ortho_extent);
+        }
+        *transform = Transform::from_translation(target + rig.pan + Vec3::Y * 1000.0).looking_at(target + rig.pan, -Vec3::Z);
+    } else {
+        let offset = Quat::from_euler(EulerRot::YXZ, rig.yaw, -rig.pitch, 0.0) * Vec3::Z * rig.distance;
+        *transform = Transform::from_translation(target + offset).looking_at(target, Vec3::Y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crosshair_picks_the_particle_closest_in_angle_in_front() {
+        let camera = Transform::from_xyz(0.0, 0.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y);
+        let particles = [
+            (Entity::from_raw(0), Transform::from_xyz(0.0, 0.0, 20.0)), // Behind, exactly on the axis
+            (Entity::from_raw(1), Transform::from_xyz(1.0, 0.0, 0.0)),  // 10 away, 1 off the axis
+            (Entity::from_raw(2), Transform::from_xyz(0.0, 1.5, -20.0)), // 30 away, 1.5 off: a smaller angle
+            (Entity::from_raw(3), Transform::from_xyz(3.0, 3.0, 5.0)),
+        ];
+        let picked = particle_under_crosshair(&camera, particles.iter().map(|(entity, transform)| (*entity, transform)));
+        assert_eq!(picked, Some(Entity::from_raw(2)));
+
+        let behind = &particles[..1];
+        assert_eq!(particle_under_crosshair(&camera, behind.iter().map(|(entity, transform)| (*entity, transform))), None);
+    }
+}
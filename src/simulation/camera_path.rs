@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use std::path::PathBuf;
+use crate::io::camera_path::{read_camera_path, write_camera_path, CameraKeyframe, CameraPath, CameraPose, Easing};
+use crate::simulation::camera::FlyCamera;
+
+pub struct CameraPathPlugin;
+
+impl Plugin for CameraPathPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraPathConfig>()
+            .add_systems(Update, record_keyframes)
+            // After the manual fly controls, before transforms propagate
+            .add_systems(PostUpdate, play_camera_path.before(TransformSystem::TransformPropagate));
+    }
+}
+
+// Picked on the command line:
+// `cargo run -- --record-camera-path flyby.txt` (press Enter to add the current pose as a keyframe)
+// `cargo run -- --camera-path flyby.txt --camera-path-loop`
+#[derive(Resource)]
+pub struct CameraPathConfig {
+    pub playback: Option<CameraPath>,
+    pub looping: bool,
+    pub record_to: Option<PathBuf>,
+    pub record_easing: Easing,
+}
+
+impl Default for CameraPathConfig {
+    fn default() -> Self {
+        Self {
+            playback: None,
+            looping: false,
+            record_to: None,
+            record_easing: Easing::InOut,
+        }
+    }
+}
+
+impl CameraPathConfig {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        let mut config = Self::default();
+        if let Some(path) = value("--camera-path") {
+            match read_camera_path(path.as_ref()) {
+                Ok(camera_path) if !camera_path.keyframes.is_empty() => config.playback = Some(camera_path),
+                Ok(_) => eprintln!("Camera path {path} has no keyframes"),
+                Err(error) => eprintln!("Camera path {path} could not be read: {error}"),
+            }
+        }
+        config.looping = args.iter().any(|arg| arg == "--camera-path-loop");
+        config.record_to = value("--record-camera-path").map(PathBuf::from);
+        config
+    }
+}
+
+fn perspective_fov(projection: &Projection) -> f32 {
+    match projection {
+        Projection::Perspective(perspective) => perspective.fov,
+        _ => PerspectiveProjection::default().fov,
+    }
+}
+
+fn record_keyframes(
+    config: Res<CameraPathConfig>,
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    cameras: Query<(&Transform, &Projection), With<FlyCamera>>,
+    mut recording: Local<(Option<f32>, CameraPath)>,
+) {
+    let Some(file) = config.record_to.as_ref() else { return };
+    if !keys.just_pressed(KeyCode::Enter) {
+        return;
+    }
+    let Some((transform, projection)) = cameras.iter().next() else { return };
+    let (start, camera_path) = &mut *recording;
+
+    // Keyframe times count simulation seconds from the first key.
+    // The look-at point sits as far ahead as the camera is from the origin.
+    let time_offset = time.elapsed_seconds() - *start.get_or_insert(time.elapsed_seconds());
+    let focus = transform.translation.length().max(1.0);
+    camera_path.push(CameraKeyframe {
+        time: time_offset,
+        pose: CameraPose {
+            position: transform.translation,
+            look_at: transform.translation + *transform.forward() * focus,
+            fov: perspective_fov(projection),
+        },
+        easing: config.record_easing,
+    });
+
+    // Rewritten on every key so nothing is lost if the run is closed
+    if let Err(error) = write_camera_path(file, camera_path) {
+        eprintln!("Camera path {} could not be written: {error}", file.display());
+    }
+}
+
+// Driven by simulation time, so playback is identical between runs and captures
+pub fn play_camera_path(
+    config: Res<CameraPathConfig>,
+    time: Res<Time>,
+    mut cameras: Query<(&mut Transform, &mut Projection), With<FlyCamera>>,
+) {
+    let Some(camera_path) = config.playback.as_ref() else { return };
+    let mut t = time.elapsed_seconds();
+    if config.looping && camera_path.duration() > 0.0 {
+        t %= camera_path.duration();
+    }
+    let Some(pose) = camera_path.sample(t) else { return };
+
+    for (mut transform, mut projection) in cameras.iter_mut() {
+        *transform = Transform::from_translation(pose.position).looking_at(pose.look_at, Vec3::Y);
+        if let Projection::Perspective(perspective) = &mut *projection {
+            perspective.fov = pose.fov;
+        }
+    }
+}
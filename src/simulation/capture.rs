@@ -0,0 +1,325 @@
+use bevy::prelude::*;
+use bevy::core_pipeline::bloom::BloomSettings;
+use bevy::render::camera::RenderTarget;
+use bevy::render::extract_resource::{ExtractResource, ExtractResourcePlugin};
+use bevy::render::render_asset::{RenderAssetUsages, RenderAssets};
+use bevy::render::render_resource::{
+    Buffer, BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d, ImageCopyBuffer,
+    ImageDataLayout, Maintain, MapMode, TextureDimension, TextureFormat, TextureUsages,
+};
+use bevy::render::renderer::{render_system, RenderDevice, RenderQueue};
+use bevy::render::texture::GpuImage;
+use bevy::render::{Render, RenderApp, RenderSet};
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::{PresentMode, PrimaryWindow};
+use std::f32::consts::TAU;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::io::png::{unpad_rows, write_png};
+use crate::simulation::camera::FlyCamera;
+use crate::simulation::camera_path::play_camera_path;
+
+pub struct CapturePlugin;
+
+impl Plugin for CapturePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CaptureConfig>();
+        let config = app.world().resource::<CaptureConfig>();
+        if !config.enabled {
+            return;
+        }
+
+        // Every frame advances the simulation by exactly one tick, however long it took to render
+        let tick = Duration::from_secs_f32(1.0 / config.fps);
+        let inbox = CaptureInbox::default();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(tick))
+            .insert_resource(inbox.clone())
+            .add_plugins(ExtractResourcePlugin::<CaptureTarget>::default())
+            .add_systems(PostStartup, setup_capture) // After the fly camera exists
+            .add_systems(PostUpdate, drive_capture_camera
+                .after(play_camera_path)
+                .before(TransformSystem::TransformPropagate))
+            .add_systems(Last, (save_captured_frames, advance_capture).chain());
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(inbox)
+                .add_systems(Render, copy_capture_frame.after(render_system).in_set(RenderSet::Render));
+        }
+    }
+}
+
+// Where the capture camera is during the run
+#[derive(Clone, Debug)]
+pub enum CapturePath {
+    FlyCamera, // Follow whatever the fly camera does
+    Orbit {
+        period: f32, // Simulation seconds per revolution
+        radius: f32,
+        height: f32,
+    },
+}
+
+impl CapturePath {
+    // Camera transform at simulation time `t`; None follows the fly camera
+    pub fn transform_at(&self, t: f32) -> Option<Transform> {
+        match self {
+            CapturePath::FlyCamera => None,
+            CapturePath::Orbit { period, radius, height } => {
+                let angle = TAU * t / period.max(f32::EPSILON);
+                let position = Vec3::new(radius * angle.sin(), *height, radius * angle.cos());
+                Some(Transform::from_translation(position).looking_at(Vec3::ZERO, Vec3::Y))
+            }
+        }
+    }
+}
+
+// Picked on the command line:
+// `cargo run -- --capture frames --capture-size 3840x2160 --capture-fps 30 --capture-frames 900 --capture-orbit 60`
+#[derive(Resource)]
+pub struct CaptureConfig {
+    pub enabled: bool,
+    pub directory: PathBuf,
+    pub width: u32,
+    pub height: u32,
+    pub fps: f32,             // Simulation ticks per video second
+    pub frames: Option<u32>,  // Exit after this many frames
+    pub warmup: u32,          // Frames skipped while pipelines compile
+    pub offscreen: bool,      // Hide the window and render only the capture target
+    pub path: CapturePath,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: PathBuf::from("captures"),
+            width: 1920,
+            height: 1080,
+            fps: 60.0,
+            frames: None,
+            warmup: 5,
+            offscreen: false,
+            path: CapturePath::FlyCamera,
+        }
+    }
+}
+
+impl CaptureConfig {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        let mut config = Self::default();
+        if let Some(directory) = value("--capture") {
+            config.enabled = true;
+            config.directory = PathBuf::from(directory);
+        }
+        let size = value("--capture-size").and_then(|size| size.split_once('x'));
+        if let Some((Ok(width), Ok(height))) = size.map(|(w, h)| (w.parse::<u32>(), h.parse::<u32>())) {
+            config.width = width.max(1);
+            config.height = height.max(1);
+        }
+        if let Some(fps) = value("--capture-fps").and_then(|v| v.parse::<f32>().ok()) {
+            config.fps = fps.max(1.0);
+        }
+        config.frames = value("--capture-frames").and_then(|v| v.parse().ok());
+        if let Some(warmup) = value("--capture-warmup").and_then(|v| v.parse().ok()) {
+            config.warmup = warmup;
+        }
+        config.offscreen = args.iter().any(|arg| arg == "--capture-offscreen");
+        if let Some(period) = value("--capture-orbit").and_then(|v| v.parse().ok()) {
+            config.path = CapturePath::Orbit { period, radius: 200.0, height: 80.0 };
+        }
+        config
+    }
+}
+
+// Marks the camera that renders into the capture image
+#[derive(Component)]
+pub struct CaptureCamera;
+
+// Shared with the render world: which image to copy and which frame it holds
+#[derive(Resource, Clone, ExtractResource)]
+struct CaptureTarget {
+    image: Handle<Image>,
+    frame: u32,
+    recording: bool,
+}
+
+struct CapturedFrame {
+    index: u32,
+    width: u32,
+    height: u32,
+    rgba: Vec<u8>,
+}
+
+// Frames read back by the render world, waiting to be written
+#[derive(Resource, Clone, Default)]
+struct CaptureInbox(Arc<Mutex<Vec<CapturedFrame>>>);
+
+fn setup_capture(
+    mut commands: Commands,
+    config: Res<CaptureConfig>,
+    mut images: ResMut<Assets<Image>>,
+    mut fly_cameras: Query<(&mut Camera, &Transform), With<FlyCamera>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if let Err(error) = std::fs::create_dir_all(&config.directory) {
+        eprintln!("Capture cannot create {}: {error}", config.directory.display());
+    }
+
+    // 1. OFFSCREEN TARGET at the requested resolution
+    let size = Extent3d { width: config.width, height: config.height, depth_or_array_layers: 1 };
+    let mut image = Image::new_fill(
+        size,
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_SRC | TextureUsages::RENDER_ATTACHMENT;
+    let image = images.add(image);
+
+    // 2. CAPTURE CAMERA, starting where the fly camera is
+    let start = fly_cameras.iter().next().map_or(Transform::default(), |(_, transform)| *transform);
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                target: RenderTarget::Image(image.clone()),
+                hdr: true,
+                order: -1,
+                ..default()
+            },
+            transform: start,
+            ..default()
+        },
+        BloomSettings::default(),
+        CaptureCamera,
+    ));
+    commands.insert_resource(CaptureTarget { image, frame: 0, recording: false });
+
+    // 3. OFFSCREEN MODE: nothing is drawn to (or paced by) the window
+    if let Ok(mut window) = windows.get_single_mut() {
+        window.present_mode = PresentMode::AutoNoVsync;
+        if config.offscreen {
+            window.visible = false;
+            for (mut camera, _) in fly_cameras.iter_mut() {
+                camera.is_active = false;
+            }
+        }
+    }
+}
+
+type CaptureLens<'a> = (&'a mut Transform, &'a mut Projection);
+type FlyCameraOnly = (With<FlyCamera>, Without<CaptureCamera>);
+
+// Follows the fly camera (and any camera path it is playing), lens included, unless orbiting
+fn drive_capture_camera(
+    config: Res<CaptureConfig>,
+    time: Res<Time>,
+    fly_cameras: Query<(&Transform, &Projection), FlyCameraOnly>,
+    mut capture_cameras: Query<CaptureLens, With<CaptureCamera>>,
+) {
+    let Some((fly_transform, fly_projection)) = fly_cameras.iter().next() else { return };
+    let transform = config.path.transform_at(time.elapsed_seconds()).unwrap_or(*fly_transform);
+    for (mut camera_transform, mut projection) in capture_cameras.iter_mut() {
+        *camera_transform = transform;
+        *projection = fly_projection.clone();
+    }
+}
+
+// Numbers the frame that is about to be extracted and rendered
+fn advance_capture(config: Res<CaptureConfig>, target: Option<ResMut<CaptureTarget>>, mut tick: Local<u32>) {
+    let Some(mut target) = target else { return };
+    *tick += 1;
+    target.recording = *tick > config.warmup;
+    target.frame = tick.saturating_sub(config.warmup + 1);
+}
+
+fn save_captured_frames(
+    config: Res<CaptureConfig>,
+    inbox: Res<CaptureInbox>,
+    mut app_exit_events: EventWriter<AppExit>,
+    mut saved: Local<u32>,
+) {
+    let frames: Vec<CapturedFrame> = std::mem::take(&mut *inbox.0.lock().unwrap());
+    for frame in frames {
+        let path = config.directory.join(format!("frame_{:06}.png", frame.index));
+        match write_png(&path, frame.width, frame.height, &frame.rgba) {
+            Ok(()) => *saved += 1,
+            Err(error) => eprintln!("Capture of {} failed: {error}", path.display()),
+        }
+    }
+
+    if config.frames.is_some_and(|frames| *saved >= frames) {
+        println!("Captured {} frames to {}", *saved, config.directory.display());
+        app_exit_events.send(AppExit::Success);
+    }
+}
+
+// Render world: copies the finished capture image into a mappable buffer and hands it back
+fn copy_capture_frame(
+    target: Option<Res<CaptureTarget>>,
+    gpu_images: Res<RenderAssets<GpuImage>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    inbox: Res<CaptureInbox>,
+    mut staging: Local<Option<(Buffer, UVec2)>>,
+) {
+    let Some(target) = target.filter(|target| target.recording) else { return };
+    let Some(gpu_image) = gpu_images.get(&target.image) else { return };
+    let size = gpu_image.size;
+    let row_bytes = size.x as usize * 4;
+    let padded_row_bytes = RenderDevice::align_copy_bytes_per_row(row_bytes);
+
+    // 1. STAGING BUFFER sized for the padded rows
+    if staging.as_ref().is_none_or(|(_, staged)| *staged != size) {
+        let buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("Capture Staging Buffer"),
+            size: (padded_row_bytes * size.y as usize) as u64,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        *staging = Some((buffer, size));
+    }
+    let Some((buffer, _)) = staging.as_ref() else { return };
+
+    // 2. COPY after the frame's own render commands
+    let mut command_encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("Capture Copy Encoder"),
+    });
+    command_encoder.copy_texture_to_buffer(
+        gpu_image.texture.as_image_copy(),
+        ImageCopyBuffer {
+            buffer,
+            layout: ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row_bytes as u32),
+                rows_per_image: None,
+            },
+        },
+        Extent3d { width: size.x, height: size.y, depth_or_array_layers: 1 },
+    );
+    render_queue.submit([command_encoder.finish()]);
+
+    // 3. MAP and wait: capture trades speed for never dropping a frame
+    let slice = buffer.slice(..);
+    render_device.map_buffer(&slice, MapMode::Read, |_| {});
+    render_device.poll(Maintain::Wait);
+    let rgba = unpad_rows(&slice.get_mapped_range(), padded_row_bytes, row_bytes, size.y as usize);
+    buffer.unmap();
+
+    inbox.0.lock().unwrap().push(CapturedFrame {
+        index: target.frame,
+        width: size.x,
+        height: size.y,
+        rgba,
+    });
+}
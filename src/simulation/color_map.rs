@@ -0,0 +1,411 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use std::f32::consts::{PI, TAU};
+use crate::viz::colormap::{normalize, quantile_range, Colormap};
+use crate::simulation::black_hole::{gather_cores, nearest_core, suction_potential, BlackHoleConfig, CoreState};
+use crate::simulation::galaxy::{BlackHole, InitialConditions, Star};
+use crate::simulation::gpu_galaxy::PhiResource;
+use crate::simulation::hud::timed;
+use crate::simulation::inspector::Birth;
+use crate::simulation::particle_render::gather_particle_instances;
+use crate::simulation::plasma::{PlasmaParticle, PATTERN_SPEED};
+use crate::simulation::spatial_index::{Population, SpatialIndex};
+
+pub const STAR_COLOR: LinearRgba = LinearRgba::rgb(1.0, 0.85, 0.6); // Warm white
+const LEGEND_WIDTH: u32 = 200;
+const RANGE_SAMPLES: usize = 8192; // Particles the automatic range is estimated from
+
+pub struct ColorMapPlugin;
+
+impl Plugin for ColorMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ColorMapConfig>()
+            .init_resource::<ColorMapRange>()
+            .add_systems(Startup, setup_legend)
+            .add_systems(Update, (stamp_particle_colors, cycle_color_map))
+            .add_systems(PostUpdate, (timed("color map", map_particle_colors).before(gather_particle_instances), update_legend).chain());
+    }
+}
+
+// What the particle colors show. GPU particles follow the same mapping for every field
+// they can compute (age and density fall back to their native colors).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorField {
+    Native, // Each population's own colors
+    Speed,
+    Radius, // From the nearest black hole
+    Arm,
+    Age,
+    Density, // Neighbours within one spatial-index cell
+    Energy,  // Specific kinetic energy relative to the nearest core plus the suction potential
+    SpiralDeviation, // 0 on an arm's ideal log spiral, 1 halfway between arms
+    Temperature,     // Kinetic: the non-circular part of the velocity
+}
+
+impl ColorField {
+    pub const ALL: [ColorField; 9] = [
+        ColorField::Native,
+        ColorField::Speed,
+        ColorField::Radius,
+        ColorField::Arm,
+        ColorField::Age,
+        ColorField::Density,
+        ColorField::Energy,
+        ColorField::SpiralDeviation,
+        ColorField::Temperature,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorField::Native => "native",
+            ColorField::Speed => "speed",
+            ColorField::Radius => "radius",
+            ColorField::Arm => "arm",
+            ColorField::Age => "age",
+            ColorField::Density => "density",
+            ColorField::Energy => "energy",
+            ColorField::SpiralDeviation => "spiral-deviation",
+            ColorField::Temperature => "temperature",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|field| field.name() == name)
+    }
+
+    // Field index in galaxy_sim.wgsl
+    pub fn gpu_id(self) -> f32 {
+        Self::ALL.iter().position(|field| *field == self).unwrap_or(0) as f32
+    }
+}
+
+// M cycles the field, N the colormap; [ and ] narrow and widen a fixed range, R returns to automatic.
+// Picked on the command line: `cargo run -- --color-by speed --colormap magma --color-range 0,40`
+#[derive(Resource)]
+pub struct ColorMapConfig {
+    pub field: ColorField,
+    pub colormap: Colormap,
+    pub range: Option<(f32, f32)>, // None follows the 2nd to 98th percentile of the CPU particles
+}
+
+impl Default for ColorMapConfig {
+    fn default() -> Self {
+        Self {
+            field: ColorField::Native,
+            colormap: Colormap::Viridis,
+            range: None,
+        }
+    }
+}
+
+impl ColorMapConfig {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        let mut config = Self::default();
+        if let Some(field) = value("--color-by") {
+            match ColorField::parse(field) {
+                Some(field) => config.field = field,
+                None => eprintln!("Unknown --color-by {field}"),
+            }
+        }
+        if let Some(colormap) = value("--colormap") {
+            match Colormap::parse(colormap) {
+                Some(colormap) => config.colormap = colormap,
+                None => eprintln!("Unknown --colormap {colormap}"),
+            }
+        }
+        let range = value("--color-range").and_then(|range| range.split_once(','));
+        if let Some((Ok(min), Ok(max))) = range.map(|(min, max)| (min.parse::<f32>(), max.parse::<f32>())) {
+            config.range = Some((min, max));
+        }
+        config
+    }
+}
+
+// The range in use this frame, shared by the CPU colors, the GPU uniforms and the legend
+#[derive(Resource)]
+pub struct ColorMapRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl Default for ColorMapRange {
+    fn default() -> Self {
+        Self { min: 0.0, max: 1.0 }
+    }
+}
+
+// Display color of a star or plasma particle, read by every renderer
+#[derive(Component, Clone, Copy)]
+pub struct ParticleColor(pub LinearRgba);
+
+type Newborn = (Or<(Added<Star>, Added<PlasmaParticle>)>, Without<ParticleColor>);
+
+fn stamp_particle_colors(mut commands: Commands, newborn: Query<Entity, Newborn>) {
+    for entity in newborn.iter() {
+        // The particle may be swallowed before the command applies
+        commands.entity(entity).try_insert(ParticleColor(STAR_COLOR));
+    }
+}
+
+fn cycle_color_map(keys: Res<ButtonInput<KeyCode>>, mut config: ResMut<ColorMapConfig>, range: Res<ColorMapRange>) {
+    if keys.just_pressed(KeyCode::KeyM) {
+        let next = ColorField::ALL.iter().position(|field| *field == config.field).map_or(0, |i| i + 1);
+        config.field = ColorField::ALL[next % ColorField::ALL.len()];
+        config.range = None;
+    }
+    if keys.just_pressed(KeyCode::KeyN) {
+        config.colormap = match config.colormap {
+            Colormap::Viridis => Colormap::Magma,
+            Colormap::Magma => Colormap::Viridis,
+        };
+    }
+    // Scale the span about its centre, fixing the range
+    let scale = match (keys.just_pressed(KeyCode::BracketLeft), keys.just_pressed(KeyCode::BracketRight)) {
+        (true, false) => 0.8,
+        (false, true) => 1.25,
+        _ => 1.0,
+    };
+    if scale != 1.0 {
+        let (centre, half) = ((range.min + range.max) * 0.5, (range.max - range.min) * 0.5 * scale);
+        config.range = Some((centre - half, centre + half));
+    }
+    if keys.just_pressed(KeyCode::KeyR) {
+        config.range = None;
+    }
+}
+
+// Disk-frame quantities of a particle about its nearest core
+struct DiskSample {
+    radius: f32,
+    phase: f32,          // Angle behind the log spiral through the particle, pattern rotation removed
+    relative_velocity: Vec3,
+    tangent: Vec3,       // Direction of circular flow
+}
+
+fn disk_sample(core: Option<&CoreState>, pos: Vec3, velocity: Vec3, phi: f32, pattern_angle: f32) -> DiskSample {
+    let (origin, core_velocity, frame) = core.map_or((Vec3::ZERO, Vec3::ZERO, Quat::IDENTITY), |core| {
+        (core.position, core.velocity, core.disk_frame())
+    });
+    let local = frame.inverse() * (pos - origin);
+    let radius = Vec2::new(local.x, local.z).length().max(1e-3);
+    DiskSample {
+        radius: pos.distance(origin),
+        phase: local.z.atan2(local.x) - radius.ln() * phi - pattern_angle,
+        relative_velocity: velocity - core_velocity,
+        tangent: frame * Vec3::new(-local.z, 0.0, local.x).normalize_or_zero(),
+    }
+}
+
+// Index of the arm at `phase` (arms sit at multiples of TAU / arms) and the distance to it, 0..1
+fn arm_position(phase: f32, arms: u32) -> (f32, f32) {
+    let arms = arms.max(1) as f32;
+    let nearest = (phase * arms / TAU).round();
+    (nearest.rem_euclid(arms), (phase * arms - nearest * TAU).abs() / PI)
+}
+
+// 0 on an arm's ideal log spiral about `core`, 1 halfway between arms
+pub fn spiral_deviation(core: Option<&CoreState>, pos: Vec3, phi: f32, pattern_angle: f32, arms: u32) -> f32 {
+    arm_position(disk_sample(core, pos, Vec3::ZERO, phi, pattern_angle).phase, arms).1
+}
+
+// 1 when every arm particle (position, arm count) sits on its ideal spiral, 0 when they are
+// spread evenly between the arms. None without particles.
+pub fn arm_coherence(cores: &[CoreState], particles: impl Iterator<Item = (Vec3, u32)>, phi: f32, pattern_angle: f32) -> Option<f32> {
+    let (sum, count) = particles.fold((0.0, 0usize), |(sum, count), (pos, arms)| {
+        (sum + spiral_deviation(nearest_core(cores, pos), pos, phi, pattern_angle, arms), count + 1)
+    });
+    (count > 0).then(|| (1.0 - 2.0 * sum / count as f32).clamp(0.0, 1.0))
+}
+
+type StarColors<'a> = (&'a Star, &'a Transform, Option<&'a Birth>, &'a mut ParticleColor);
+type PlasmaColors<'a> = (&'a PlasmaParticle, &'a Transform, Option<&'a Birth>, &'a mut ParticleColor);
+
+#[allow(clippy::too_many_arguments)]
+fn map_particle_colors(
+    time: Res<Time>,
+    config: Res<ColorMapConfig>,
+    mut range: ResMut<ColorMapRange>,
+    phi: Res<PhiResource>,
+    initial_conditions: Res<InitialConditions>,
+    black_hole_config: Res<BlackHoleConfig>,
+    black_holes: Query<(Entity, &Transform, &BlackHole)>,
+    index: Res<SpatialIndex>,
+    mut stars: Query<StarColors, Without<PlasmaParticle>>,
+    mut plasma: Query<PlasmaColors, Without<Star>>,
+    mut previous_field: Local<Option<ColorField>>,
+) {
+    let field = config.field;
+    if field == ColorField::Native {
+        for (_, _, _, mut color) in stars.iter_mut() {
+            color.0 = STAR_COLOR;
+        }
+        for (particle, _, _, mut color) in plasma.iter_mut() {
+            color.0 = particle.color.to_linear();
+        }
+        *previous_field = Some(field);
+        return;
+    }
+
+    // 1. VALUES of the field, stars first
+    let now = time.elapsed_seconds();
+    let cores = gather_cores(&black_hole_config, black_holes.iter());
+    let pattern_angle = now * PATTERN_SPEED;
+    let neighbours = [Population::Star, Population::Plasma, Population::Gas];
+    let value = |pos: Vec3, velocity: Vec3, arm: Option<u32>, arms: u32, birth: Option<&Birth>| {
+        let core = nearest_core(&cores, pos);
+        let disk = disk_sample(core, pos, velocity, phi.phi_value, pattern_angle);
+        match field {
+            ColorField::Native | ColorField::Speed => velocity.length(),
+            ColorField::Radius => disk.radius,
+            ColorField::Arm => arm.map_or_else(|| arm_position(disk.phase, arms).0, |arm| arm as f32),
+            ColorField::Age => birth.map_or(f32::NAN, |birth| now - birth.time),
+            ColorField::Density => {
+                let mut count = 0;
+                index.for_each_in_radius(&neighbours, pos, index.cell_size(), |_, _, _| count += 1);
+                count as f32
+            }
+            ColorField::Energy => 0.5 * disk.relative_velocity.length_squared() + suction_potential(&cores, pos),
+            ColorField::SpiralDeviation => arm_position(disk.phase, arms).1,
+            ColorField::Temperature => {
+                let circular = disk.tangent * disk.relative_velocity.dot(disk.tangent);
+                (disk.relative_velocity - circular).length()
+            }
+        }
+    };
+    let star_arms = initial_conditions.model.arms;
+    let mut values: Vec<f32> = stars
+        .iter()
+        .map(|(star, transform, birth, _)| value(transform.translation, star.velocity, None, star_arms, birth))
+        .collect();
+    values.extend(plasma.iter().map(|(particle, transform, birth, _)| {
+        value(transform.translation, particle.velocity, Some(particle.arm), particle.arms, birth)
+    }));
+
+    // 2. RANGE: fixed, or the smoothed percentile range (snapping when the field changes)
+    if let Some((min, max)) = config.range {
+        (range.min, range.max) = (min, max);
+    } else {
+        let stride = values.len().div_ceil(RANGE_SAMPLES).max(1);
+        let sample: Vec<f32> = values.iter().step_by(stride).copied().collect();
+        if let Some((min, max)) = quantile_range(&sample, 0.02, 0.98) {
+            let blend = if *previous_field == Some(field) { 0.1 } else { 1.0 };
+            range.min += (min - range.min) * blend;
+            range.max += (max - range.max) * blend;
+        }
+    }
+    *previous_field = Some(field);
+
+    // 3. COLORS in the same order
+    let span = (range.min, range.max);
+    let mut colors = values.into_iter().map(|value| {
+        let [r, g, b] = config.colormap.sample(normalize(value, span));
+        LinearRgba::rgb(r, g, b)
+    });
+    for (_, _, _, mut color) in stars.iter_mut() {
+        color.0 = colors.next().unwrap_or(STAR_COLOR);
+    }
+    for (_, _, _, mut color) in plasma.iter_mut() {
+        color.0 = colors.next().unwrap_or(STAR_COLOR);
+    }
+}
+
+#[derive(Component)]
+struct LegendPanel;
+
+#[derive(Component)]
+enum LegendText {
+    Title,
+    Min,
+    Max,
+}
+
+#[derive(Resource)]
+struct LegendBar(Handle<Image>);
+
+fn gradient(colormap: Colormap) -> Vec<u8> {
+    (0..LEGEND_WIDTH)
+        .flat_map(|x| {
+            let [r, g, b] = colormap.sample(x as f32 / (LEGEND_WIDTH - 1) as f32);
+            Color::from(LinearRgba::rgb(r, g, b)).to_srgba().to_u8_array()
+        })
+        .collect()
+}
+
+fn setup_legend(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let bar = images.add(Image::new(
+        Extent3d { width: LEGEND_WIDTH, height: 1, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        gradient(Colormap::Viridis),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    ));
+    let text = |kind: LegendText| (TextBundle::from_section("", TextStyle { font_size: 14.0, ..default() }), kind);
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(10.0),
+                    left: Val::Px(10.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(8.0)),
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            LegendPanel,
+        ))
+        .with_children(|panel| {
+            panel.spawn(text(LegendText::Title));
+            panel
+                .spawn(NodeBundle {
+                    style: Style { column_gap: Val::Px(6.0), align_items: AlignItems::Center, ..default() },
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn(text(LegendText::Min));
+                    row.spawn(ImageBundle {
+                        image: UiImage::new(bar.clone()),
+                        style: Style { width: Val::Px(LEGEND_WIDTH as f32), height: Val::Px(12.0), ..default() },
+                        ..default()
+                    });
+                    row.spawn(text(LegendText::Max));
+                });
+        });
+    commands.insert_resource(LegendBar(bar));
+}
+
+fn update_legend(
+    config: Res<ColorMapConfig>,
+    range: Res<ColorMapRange>,
+    bar: Option<Res<LegendBar>>,
+    mut images: ResMut<Assets<Image>>,
+    mut panels: Query<&mut Visibility, With<LegendPanel>>,
+    mut texts: Query<(&mut Text, &LegendText)>,
+) {
+    for mut visibility in panels.iter_mut() {
+        *visibility = if config.field == ColorField::Native { Visibility::Hidden } else { Visibility::Inherited };
+    }
+    if let Some(image) = bar.filter(|_| config.is_changed()).and_then(|bar| images.get_mut(&bar.0)) {
+        image.data = gradient(config.colormap);
+    }
+    let fixed = if config.range.is_some() { "fixed" } else { "auto" };
+    for (mut text, kind) in texts.iter_mut() {
+        text.sections[0].value = match kind {
+            LegendText::Title => format!("{} ({}, {} range)", config.field.name(), config.colormap.name(), fixed),
+            LegendText::Min => format!("{:.3}", range.min),
+            LegendText::Max => format!("{:.3}", range.max),
+        };
+    }
+}
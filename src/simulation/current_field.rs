@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+use crate::physics::pic::CurrentGrid;
+use crate::simulation::black_hole::{barycenter, gather_cores, BlackHoleConfig};
+use crate::simulation::galaxy::BlackHole;
+use crate::simulation::gpu_galaxy::PhiResource;
+use crate::simulation::hud::timed;
+use crate::simulation::plasma::{resonance, update_galaxy_physics, PlasmaParticle};
+
+pub struct CurrentFieldPlugin;
+
+impl Plugin for CurrentFieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PicConfig>();
+        let config = app.world().resource::<PicConfig>();
+        let grid = CurrentGrid::new(config.dims, config.cell_size, Vec3::ZERO);
+        app.insert_resource(SelfField { grid })
+            .add_systems(Update, timed("pic", deposit_and_solve).before(update_galaxy_physics));
+    }
+}
+
+#[derive(Resource)]
+pub struct PicConfig {
+    pub enabled: bool,
+    pub dims: UVec3,
+    pub cell_size: f32,
+    pub solver_iterations: usize,
+    // Fraction of a charge carrier each macro-particle deposits.
+    // Keeps the self-field of 50,000 particles on the same scale as the Dynamo.
+    pub particle_weight: f32,
+    // Also pull plasma toward its ideal spiral. Always on when the grid is disabled,
+    // since the deposited currents are then the only thing left holding the arms.
+    pub analytic_pinch: bool,
+}
+
+impl Default for PicConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dims: UVec3::new(48, 16, 48),
+            cell_size: 5.0,
+            solver_iterations: 8,
+            particle_weight: 0.01,
+            analytic_pinch: false,
+        }
+    }
+}
+
+impl PicConfig {
+    // `cargo run -- --analytic-pinch` keeps the old ideal-spiral pull, `--no-pic` falls back to it
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let flag = |name: &str| args.iter().any(|arg| arg == name);
+
+        Self {
+            enabled: !flag("--no-pic"),
+            analytic_pinch: flag("--analytic-pinch"),
+            ..Self::default()
+        }
+    }
+
+    // Whether update_galaxy_physics applies the pull toward the ideal spiral
+    pub fn analytic_pinch(&self) -> bool {
+        self.analytic_pinch || !self.enabled
+    }
+}
+
+// Magnetic field generated by the plasma's own currents (Z-Pinch).
+#[derive(Resource)]
+pub struct SelfField {
+    pub grid: CurrentGrid,
+}
+
+impl SelfField {
+    pub fn field_at(&self, pos: Vec3) -> Vec3 {
+        self.grid.sample_field(pos)
+    }
+}
+
+fn deposit_and_solve(
+    config: Res<PicConfig>,
+    phi: Res<PhiResource>,
+    mut self_field: ResMut<SelfField>,
+    particles: Query<(&PlasmaParticle, &Transform)>,
+    black_hole_query: Query<(Entity, &Transform, &BlackHole)>,
+    black_hole_config: Res<BlackHoleConfig>,
+) {
+    let grid = &mut self_field.grid;
+
+    // Rebuild the grid if the config changed shape
+    if grid.dims != config.dims || grid.cell_size != config.cell_size {
+        *grid = CurrentGrid::new(config.dims, config.cell_size, Vec3::ZERO);
+    }
+
+    grid.clear_current();
+    if !config.enabled {
+        grid.field.fill(Vec3::ZERO);
+        return;
+    }
+
+    // 1. FOLLOW THE CORES (mass-weighted center when several galaxies share the grid)
+    grid.recenter(barycenter(&gather_cores(&black_hole_config, black_hole_query.iter())));
+
+    // 2. DEPOSIT (J = Σ q·v). The Ether only carries the currents coherently at resonance,
+    // so a detuned galaxy loses its self-pinch as well.
+    let weight = config.particle_weight * resonance(phi.phi_value);
+    for (particle, transform) in particles.iter() {
+        grid.deposit(
+            transform.translation,
+            particle.charge * weight,
+            particle.velocity,
+        );
+    }
+
+    // 3. SOLVE (∇²A = -μ₀J, B = ∇×A)
+    grid.solve_potential(config.solver_iterations);
+    grid.compute_field();
+}
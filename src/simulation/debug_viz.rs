@@ -1,20 +1,339 @@
 use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use crate::viz::colormap::{normalize, quantile_range};
+use crate::physics::streamlines::{lic, trace_streamline, white_noise};
+use crate::simulation::color_map::ColorMapConfig;
+use crate::simulation::ether::Ether;
+use crate::simulation::ether_volume::EtherVolumeConfig;
 use crate::simulation::galaxy::BlackHole;
+use crate::simulation::hud::timed;
+
+const REFRESH_INTERVAL: f32 = 0.25; // Seconds between re-traces of the streamlines and the LIC texture
+const LIC_TEXELS: f32 = 192.0;      // Along the longer side of the slice
+const LIC_HALF_LENGTH: usize = 16;  // Pixels convolved on either side of each pixel
+const MAX_STREAMLINE_STEPS: usize = 120; // Per direction
 
 pub struct EtherVizPlugin;
 
 impl Plugin for EtherVizPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, draw_ether_flow);
+        app.init_resource::<FlowVizConfig>()
+            .init_resource::<FlowLines>()
+            .add_systems(Startup, setup_lic_plane)
+            .add_systems(Update, (control_flow_viz, draw_ether_flow, draw_streamlines))
+            .add_systems(PostUpdate, timed("flow viz", trace_flow));
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlowSource {
+    Analytic, // Suction toward every black hole, turned by the Golden Drag
+    Grid,     // The simulated ether
+}
+
+impl FlowSource {
+    pub fn name(self) -> &'static str {
+        match self {
+            FlowSource::Analytic => "analytic",
+            FlowSource::Grid => "grid",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "analytic" => Some(FlowSource::Analytic),
+            "grid" => Some(FlowSource::Grid),
+            _ => None,
+        }
     }
 }
 
-fn draw_ether_flow(mut gizmos: Gizmos, black_hole_query: Query<&Transform, With<BlackHole>>) {
-    let black_hole_pos = black_hole_query.single().translation;
+// O toggles the arrows, T the streamlines, L the LIC texture on the ether slice (B and , . move it),
+// U switches the field between the analytic suction and the simulated grid.
+// Picked on the command line: `cargo run -- --streamlines --lic --flow-source grid --flow-seeds 8,3,8`
+#[derive(Resource)]
+pub struct FlowVizConfig {
+    pub source: FlowSource,
+    pub arrows: bool,
+    pub streamlines: bool,
+    pub lic: bool,
+    pub seeds: UVec3, // Streamline seed lattice across the ether grid
+}
+
+impl Default for FlowVizConfig {
+    fn default() -> Self {
+        Self {
+            source: FlowSource::Analytic,
+            arrows: false,
+            streamlines: false,
+            lic: false,
+            seeds: UVec3::new(6, 3, 6),
+        }
+    }
+}
+
+impl FlowVizConfig {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let flag = |name: &str| args.iter().any(|arg| arg == name);
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        let mut config = Self {
+            arrows: flag("--flow-arrows"),
+            streamlines: flag("--streamlines"),
+            lic: flag("--lic"),
+            ..Self::default()
+        };
+        if let Some(source) = value("--flow-source") {
+            match FlowSource::parse(source) {
+                Some(source) => config.source = source,
+                None => eprintln!("Unknown --flow-source {source}"),
+            }
+        }
+        let seeds: Option<Vec<u32>> = value("--flow-seeds").map(|seeds| seeds.split(',').filter_map(|n| n.parse().ok()).collect());
+        if let Some(&[x, y, z]) = seeds.as_deref() {
+            config.seeds = UVec3::new(x, y, z).max(UVec3::ONE);
+        }
+        config
+    }
+}
+
+// Suction: vector to each black hole with magnitude 1.0 / distance,
+// then the Golden Drag turns it about the up-axis (Y) by φ^{-4}
+pub fn suction_flow(black_holes: &[Vec3], pos: Vec3) -> Vec3 {
     const PHI_INV_4: f32 = 0.1464466094067262; // φ^{-4}
     let cos_mu = PHI_INV_4.cos();
     let sin_mu = PHI_INV_4.sin();
 
+    let mut suction = Vec3::ZERO;
+    for black_hole_pos in black_holes.iter() {
+        let to_center = *black_hole_pos - pos;
+        let distance = to_center.length().max(0.1);
+        suction += to_center.normalize_or_zero() * (1.0 / distance);
+    }
+
+    let rotated_x = suction.x * cos_mu + suction.z * sin_mu;
+    let rotated_z = -suction.x * sin_mu + suction.z * cos_mu;
+    Vec3::new(rotated_x, suction.y, rotated_z)
+}
+
+// The selected field, bounded by the ether grid so both sources cover the same box
+fn flow_field<'a>(source: FlowSource, ether: &'a Ether, black_holes: &'a [Vec3]) -> impl Fn(Vec3) -> Option<Vec3> + 'a {
+    let grid = &ether.grid;
+    move |pos| match source {
+        FlowSource::Analytic => {
+            let local = pos - grid.origin;
+            (local.cmpge(Vec3::ZERO).all() && local.cmple(grid.extent()).all()).then(|| suction_flow(black_holes, pos))
+        }
+        FlowSource::Grid => grid.flow_at(pos),
+    }
+}
+
+// Traced polylines with the flow speed at each point, refreshed every REFRESH_INTERVAL
+#[derive(Resource, Default)]
+struct FlowLines {
+    lines: Vec<Vec<(Vec3, f32)>>,
+    speed_range: (f32, f32),
+}
+
+#[derive(Component)]
+struct LicPlane;
+
+#[derive(Resource)]
+struct LicTexture {
+    image: Handle<Image>,
+}
+
+fn lic_image(width: u32, height: u32) -> Image {
+    Image::new(
+        Extent3d { width, height, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        vec![0; (width * height * 4) as usize],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+fn setup_lic_plane(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let image = images.add(lic_image(1, 1));
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(0.5))),
+            material: materials.add(StandardMaterial {
+                base_color_texture: Some(image.clone()),
+                unlit: true,
+                alpha_mode: AlphaMode::Blend,
+                double_sided: true,
+                cull_mode: None,
+                ..default()
+            }),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        LicPlane,
+    ));
+    commands.insert_resource(LicTexture { image });
+}
+
+fn control_flow_viz(keys: Res<ButtonInput<KeyCode>>, mut config: ResMut<FlowVizConfig>) {
+    if keys.just_pressed(KeyCode::KeyO) {
+        config.arrows = !config.arrows;
+    }
+    if keys.just_pressed(KeyCode::KeyT) {
+        config.streamlines = !config.streamlines;
+    }
+    if keys.just_pressed(KeyCode::KeyL) {
+        config.lic = !config.lic;
+    }
+    if keys.just_pressed(KeyCode::KeyU) {
+        config.source = match config.source {
+            FlowSource::Analytic => FlowSource::Grid,
+            FlowSource::Grid => FlowSource::Analytic,
+        };
+    }
+}
+
+type LicPlanes<'w, 's> = Query<'w, 's, (&'static mut Transform, &'static mut Visibility, &'static Handle<StandardMaterial>), (With<LicPlane>, Without<BlackHole>)>;
+
+#[allow(clippy::too_many_arguments)]
+fn trace_flow(
+    time: Res<Time>,
+    config: Res<FlowVizConfig>,
+    slice: Res<EtherVolumeConfig>,
+    color_map: Res<ColorMapConfig>,
+    ether: Res<Ether>,
+    black_hole_query: Query<&Transform, With<BlackHole>>,
+    texture: Option<Res<LicTexture>>,
+    mut lines: ResMut<FlowLines>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut planes: LicPlanes,
+    mut since_refresh: Local<f32>,
+) {
+    // 1. FOLLOW THE SLICE every frame; re-trace only on the interval or a change of settings
+    let plane = slice.slice_transform(&ether.grid);
+    for (mut transform, mut visibility, _) in planes.iter_mut() {
+        *transform = plane;
+        *visibility = if config.lic { Visibility::Inherited } else { Visibility::Hidden };
+    }
+    *since_refresh += time.delta_seconds();
+    let settings_changed = config.is_changed() || slice.is_changed() || color_map.is_changed();
+    if *since_refresh < REFRESH_INTERVAL && !settings_changed {
+        return;
+    }
+    *since_refresh = 0.0;
+
+    let black_holes: Vec<Vec3> = black_hole_query.iter().map(|t| t.translation).collect();
+    let field = flow_field(config.source, &ether, &black_holes);
+    let grid = &ether.grid;
+
+    // 2. STREAMLINES from a lattice of seeds across the grid
+    lines.lines.clear();
+    if config.streamlines {
+        let step = grid.cell_size * 0.5;
+        for z in 0..config.seeds.z {
+            for y in 0..config.seeds.y {
+                for x in 0..config.seeds.x {
+                    let fraction = (UVec3::new(x, y, z).as_vec3() + 0.5) / config.seeds.as_vec3();
+                    let seed = grid.origin + fraction * grid.extent();
+                    let line = trace_streamline(&field, seed, step, MAX_STREAMLINE_STEPS);
+                    if line.len() > 1 {
+                        lines.lines.push(line.into_iter().map(|p| (p, field(p).map_or(0.0, |v| v.length()))).collect());
+                    }
+                }
+            }
+        }
+        let speeds: Vec<f32> = lines.lines.iter().flatten().map(|&(_, speed)| speed).collect();
+        lines.speed_range = quantile_range(&speeds, 0.02, 0.98).unwrap_or((0.0, 1.0));
+    }
+
+    // 3. LIC over the slice: the flow projected into the plane, in pixels
+    let Some(texture) = texture.filter(|_| config.lic) else {
+        return;
+    };
+    let longest = plane.scale.x.max(plane.scale.z).max(1e-3);
+    let width = ((LIC_TEXELS * plane.scale.x / longest).round() as usize).max(8);
+    let height = ((LIC_TEXELS * plane.scale.z / longest).round() as usize).max(8);
+    let (axis_u, axis_v) = (plane.rotation * Vec3::X, plane.rotation * Vec3::Z);
+    let pixels = Vec2::new(width as f32 / plane.scale.x, height as f32 / plane.scale.z); // Per world unit
+
+    // The field once per pixel; the convolution reads the nearest one
+    let mut flow = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let uv = Vec2::new((x as f32 + 0.5) / width as f32, (y as f32 + 0.5) / height as f32);
+            let world = plane.transform_point(Vec3::new(uv.x - 0.5, 0.0, uv.y - 0.5));
+            flow.push(field(world).unwrap_or(Vec3::ZERO));
+        }
+    }
+    let in_plane = |p: Vec2| {
+        let v = flow[(p.y as usize).min(height - 1) * width + (p.x as usize).min(width - 1)];
+        Some(Vec2::new(v.dot(axis_u), v.dot(axis_v)) * pixels)
+    };
+    let noise = white_noise(width, height, 1);
+    let intensity = lic(width, height, &noise, in_plane, LIC_HALF_LENGTH);
+
+    // 4. COLOR: streak contrast stretched to 0..1, tinted by the speed through the colormap
+    let contrast = quantile_range(&intensity, 0.02, 0.98).unwrap_or((0.0, 1.0));
+    let speeds: Vec<f32> = flow.iter().map(|v| v.length()).collect();
+    let speed_range = quantile_range(&speeds, 0.02, 0.98).unwrap_or((0.0, 1.0));
+    let Some(image) = images.get_mut(&texture.image) else {
+        return;
+    };
+    if image.width() as usize != width || image.height() as usize != height {
+        *image = lic_image(width as u32, height as u32);
+    }
+    image.data = intensity
+        .iter()
+        .zip(&speeds)
+        .flat_map(|(&streak, &speed)| {
+            let [r, g, b] = color_map.colormap.sample(normalize(speed, speed_range));
+            let shade = 0.25 + 0.75 * normalize(streak, contrast).clamp(0.0, 1.0);
+            let [r, g, b, _] = Srgba::from(LinearRgba::rgb(r * shade, g * shade, b * shade)).to_u8_array();
+            [r, g, b, 230]
+        })
+        .collect();
+
+    // Touching the material rebinds the re-uploaded texture
+    for (_, _, handle) in planes.iter() {
+        materials.get_mut(handle);
+    }
+}
+
+fn draw_streamlines(mut gizmos: Gizmos, config: Res<FlowVizConfig>, color_map: Res<ColorMapConfig>, lines: Res<FlowLines>) {
+    if !config.streamlines {
+        return;
+    }
+    for line in lines.lines.iter() {
+        gizmos.linestrip_gradient(line.iter().map(|&(position, speed)| {
+            let [r, g, b] = color_map.colormap.sample(normalize(speed, lines.speed_range));
+            (position, LinearRgba::rgb(r, g, b))
+        }));
+    }
+}
+
+fn draw_ether_flow(
+    mut gizmos: Gizmos,
+    config: Res<FlowVizConfig>,
+    ether: Res<Ether>,
+    black_hole_query: Query<&Transform, With<BlackHole>>,
+) {
+    if !config.arrows {
+        return;
+    }
+    let black_hole_positions: Vec<Vec3> = black_hole_query.iter().map(|t| t.translation).collect();
+    let field = flow_field(config.source, &ether, &black_hole_positions);
+
     let x_min = -50.0;
     let x_max = 50.0;
     let y_min = -20.0;
@@ -33,16 +352,10 @@ fn draw_ether_flow(mut gizmos: Gizmos, black_hole_query: Query<&Transform, With<
             let mut z = z_min;
             while z <= z_max {
                 let pos = Vec3::new(x, y, z);
-                let to_center = black_hole_pos - pos;
-                let distance = to_center.length().max(0.1);
-
-                // Suction: vector to black hole with magnitude 1.0 / distance
-                let suction = to_center.normalize() * (1.0 / distance);
-
-                // Drag: rotate around up-axis (Y)
-                let rotated_x = suction.x * cos_mu + suction.z * sin_mu;
-                let rotated_z = -suction.x * sin_mu + suction.z * cos_mu;
-                let flow = Vec3::new(rotated_x, suction.y, rotated_z);
+                let Some(flow) = field(pos).filter(|flow| *flow != Vec3::ZERO) else {
+                    z += step;
+                    continue;
+                };
 
                 // Color based on magnitude
                 let magnitude = flow.length();
@@ -62,7 +375,7 @@ fn draw_ether_flow(mut gizmos: Gizmos, black_hole_query: Query<&Transform, With<
                 gizmos.line(pos, shaft_end, shaft_color);
 
                 // Head
-                let perp = Vec3::cross(direction, Vec3::Y).normalize() * head_width;
+                let perp = Vec3::cross(direction, Vec3::Y).normalize_or_zero() * head_width;
                 let head_left = shaft_end + perp;
                 let head_right = shaft_end - perp;
                 gizmos.line(tip, head_left, Color::srgb(1.0, 1.0, 0.0));
@@ -75,4 +388,4 @@ fn draw_ether_flow(mut gizmos: Gizmos, black_hole_query: Query<&Transform, With<
         }
         x += step;
     }
-}
\ No newline at end of file
+}
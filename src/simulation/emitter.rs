@@ -1,72 +1,438 @@
 use bevy::prelude::*;
+use bevy::ecs::system::EntityCommands;
+use bevy::render::render_resource::Buffer;
+use bevy::render::renderer::RenderQueue;
+use std::f32::consts::{PI, TAU};
 use crate::simulation::plasma::PlasmaParticle;
-use crate::simulation::galaxy::BlackHole;
+use crate::simulation::black_hole::{gather_cores, owner_core, BlackHoleConfig};
+use crate::simulation::galaxy::{BlackHole, GalaxyMember, InitialConditions, Star};
+use crate::simulation::gpu_galaxy::{GpuGalaxyResources, Particle, NUM_PARTICLES};
+use crate::simulation::import::ImportConfig;
+use crate::simulation::scenario::Scenario;
+use crate::simulation::sph::{SphConfig, SphGas};
+use crate::physics::initial_conditions::{ComponentMix, GalaxyModel};
 
 pub struct EmitterPlugin;
 
 impl Plugin for EmitterPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, big_bang_burst);
+        app.add_systems(Startup, spawn_scenario_emitters)
+            .add_systems(Update, (
+                attach_genesis_emitters,
+                run_emitters.after(attach_genesis_emitters),
+                expire_emitted,
+            ));
     }
 }
 
-fn big_bang_burst(
+// Where new particles appear, in the emitter's local frame (disk in XZ, axis along +Y)
+#[derive(Clone, Copy, Debug)]
+pub enum EmitterShape {
+    Point,
+    Sphere { radius: f32 },
+    Disk { inner_radius: f32, outer_radius: f32, thickness: f32 },
+    // Logarithmic spiral: theta = ln(r) * pitch + arm offset
+    LogSpiral { arms: u32, pitch: f32, inner_radius: f32, outer_radius: f32, fuzz: f32, thickness: f32 },
+    // Cone along ±Y (both lobes if bipolar)
+    JetCone { half_angle: f32, length: f32, bipolar: bool },
+    // Equilibrium galaxy (see physics::initial_conditions); supplies its own velocities
+    Galaxy { model: GalaxyModel, mix: ComponentMix },
+}
+
+// One sampled emission point, in the emitter's local frame
+pub struct ShapeSample {
+    pub position: Vec3,
+    pub arm: u32,
+    pub velocity: Vec3, // Only the Galaxy shape knows a natural velocity
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum EmissionMode {
+    Burst { count: usize }, // Fires once, then the emitter disables itself
+    Rate { per_second: f32 },
+}
+
+// Base velocity of each particle, in the emitter's local frame
+#[derive(Clone, Copy, Debug)]
+pub enum VelocityProfile {
+    Orbital { speed: f32 },   // Tangential around the local Y axis
+    Radial { speed: f32 },    // Straight out from the emitter
+    Isotropic { speed: f32 }, // Random direction
+    Directed { velocity: Vec3 },
+    Equilibrium, // Whatever the shape supplies (Galaxy: rotation + dispersion)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum EmitterColor {
+    Constant(Color),
+    // `inner` inside `split` (cylindrical radius), `outer` beyond
+    ByRadius { inner: Color, outer: Color, split: f32 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmitterTarget {
+    Plasma,
+    Stars,
+    Gpu, // Overwrites slots of the GPU particle buffer (ring order)
+}
+
+// A source of particles. Emits in its own Transform's frame, so moving or
+// rotating the entity animates the emission. Emitters placed on a BlackHole
+// inherit its velocity and make their particles members of its galaxy.
+#[derive(Component, Clone, Debug)]
+pub struct Emitter {
+    pub enabled: bool,
+    pub shape: EmitterShape,
+    pub mode: EmissionMode,
+    pub velocity: VelocityProfile,
+    pub dispersion: f32,       // Random velocity added per axis (±dispersion)
+    pub color: EmitterColor,
+    pub lifetime: Option<f32>, // Seconds before emitted particles despawn (None = forever)
+    pub target: EmitterTarget,
+    pub emitted: usize,        // Running total
+    pub accumulator: f32,      // Fractional particles carried over between ticks (rate mode)
+}
+
+impl Default for Emitter {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            shape: EmitterShape::Point,
+            mode: EmissionMode::Burst { count: 1000 },
+            velocity: VelocityProfile::Isotropic { speed: 10.0 },
+            dispersion: 0.0,
+            color: EmitterColor::Constant(Color::WHITE),
+            lifetime: None,
+            target: EmitterTarget::Plasma,
+            emitted: 0,
+            accumulator: 0.0,
+        }
+    }
+}
+
+// Despawns emitted entities once their lifetime runs out
+#[derive(Component)]
+pub struct Lifetime {
+    pub timer: Timer,
+}
+
+impl EmitterShape {
+    // Local position and spiral arm of the `index`-th particle
+    pub fn sample(&self, index: usize) -> ShapeSample {
+        let (position, arm) = match *self {
+            EmitterShape::Point => (Vec3::ZERO, 0),
+            EmitterShape::Sphere { radius } => {
+                (random_direction() * radius * rand::random::<f32>().cbrt(), 0)
+            }
+            EmitterShape::Disk { inner_radius, outer_radius, thickness } => {
+                // Uniform in area
+                let r = (inner_radius * inner_radius
+                    + rand::random::<f32>() * (outer_radius * outer_radius - inner_radius * inner_radius))
+                    .sqrt();
+                let theta = rand::random::<f32>() * TAU;
+                let y = (rand::random::<f32>() - 0.5) * thickness;
+                (Vec3::new(r * theta.cos(), y, r * theta.sin()), 0)
+            }
+            EmitterShape::LogSpiral { arms, pitch, inner_radius, outer_radius, fuzz, thickness } => {
+                let arms = arms.max(1);
+                let arm = (index % arms as usize) as u32;
+                let r = inner_radius + rand::random::<f32>() * (outer_radius - inner_radius);
+                let theta = r.ln() * pitch
+                    + TAU * arm as f32 / arms as f32
+                    + (rand::random::<f32>() - 0.5) * fuzz;
+                let y = (rand::random::<f32>() - 0.5) * thickness;
+                (Vec3::new(r * theta.cos(), y, r * theta.sin()), arm)
+            }
+            EmitterShape::JetCone { half_angle, length, bipolar } => {
+                let theta = half_angle * rand::random::<f32>().sqrt();
+                let azimuth = rand::random::<f32>() * TAU;
+                let lobe = if bipolar && rand::random::<bool>() { -1.0 } else { 1.0 };
+                let direction = Vec3::new(theta.sin() * azimuth.cos(), lobe * theta.cos(), theta.sin() * azimuth.sin());
+                (direction * length * rand::random::<f32>(), 0)
+            }
+            EmitterShape::Galaxy { model, mix } => {
+                let body = model.sample(index, &mix, &mut rand::thread_rng());
+                return ShapeSample { position: body.position, arm: body.arm, velocity: body.velocity };
+            }
+        };
+        ShapeSample { position, arm, velocity: Vec3::ZERO }
+    }
+
+    fn arms(&self) -> u32 {
+        match *self {
+            EmitterShape::LogSpiral { arms, .. } => arms.max(1),
+            EmitterShape::Galaxy { model, .. } => model.arms.max(1),
+            _ => 1,
+        }
+    }
+}
+
+impl VelocityProfile {
+    pub fn sample(&self, shape_sample: &ShapeSample) -> Vec3 {
+        let local_pos = shape_sample.position;
+        match *self {
+            VelocityProfile::Orbital { speed } => {
+                Vec3::new(-local_pos.z, 0.0, local_pos.x).normalize_or_zero() * speed
+            }
+            VelocityProfile::Radial { speed } => {
+                let direction = local_pos.try_normalize().unwrap_or_else(random_direction);
+                direction * speed
+            }
+            VelocityProfile::Isotropic { speed } => random_direction() * speed,
+            VelocityProfile::Directed { velocity } => velocity,
+            VelocityProfile::Equilibrium => shape_sample.velocity,
+        }
+    }
+}
+
+impl EmitterColor {
+    pub fn sample(&self, local_pos: Vec3) -> Color {
+        match *self {
+            EmitterColor::Constant(color) => color,
+            EmitterColor::ByRadius { inner, outer, split } => {
+                if Vec2::new(local_pos.x, local_pos.z).length() < split { inner } else { outer }
+            }
+        }
+    }
+}
+
+fn random_direction() -> Vec3 {
+    let z = rand::random::<f32>() * 2.0 - 1.0;
+    let azimuth = rand::random::<f32>() * TAU;
+    let ring = (1.0 - z * z).sqrt();
+    Vec3::new(ring * azimuth.cos(), z, ring * azimuth.sin())
+}
+
+fn spawn_scenario_emitters(mut commands: Commands, scenario: Res<Scenario>) {
+    for (transform, emitter) in scenario.emitters() {
+        commands.spawn((emitter, transform));
+    }
+}
+
+// GENESIS: the old big bang, now a one-shot galaxy emitter riding on every black hole
+fn attach_genesis_emitters(
     mut commands: Commands,
-    black_hole_query: Query<&Transform, With<BlackHole>>,
+    black_hole_query: Query<Entity, With<BlackHole>>,
+    initial_conditions: Res<InitialConditions>,
+    import_config: Res<ImportConfig>,
     mut done: Local<bool>,
 ) {
     if *done { return; }
     *done = true;
-    let black_hole_pos = black_hole_query.single().translation;
-
-    // GENESIS PARAMETERS
-    let phi = 1.618034;
-
-    for i in 0..50_000 {
-        // 1. RADIUS (Logarithmic-like distribution looks best, but linear is fine)
-        // Spread them from 2.0 to 100.0
-        let radius = 2.0 + rand::random::<f32>() * 98.0;
-
-        // 2. SPIRAL MATH (Genesis Mode)
-        // Theta = ln(r) * Phi
-        let base_theta = radius.ln() * phi;
-
-        // 3. SYMMETRY (2 Arms)
-        // Even = Arm A (0), Odd = Arm B (PI)
-        let arm_id = if i % 2 == 0 { 0 } else { 1 };
-        let arm_offset = if arm_id == 0 { 0.0 } else { std::f32::consts::PI };
-
-        // 4. FUZZ (Thickness)
-        let fuzz = (rand::random::<f32>() - 0.5) * 0.5;
-        let theta = base_theta + arm_offset + fuzz;
-
-        // 5. POSITION
-        let position = black_hole_pos + Vec3::new(
-            radius * theta.cos(),
-            (rand::random::<f32>() - 0.5) * 4.0, // Vertical spread
-            radius * theta.sin()
-        );
-
-        // 6. VELOCITY (Tangent stability)
-        let tangent = Vec3::new(-position.z, 0.0, position.x).normalize();
-        let velocity = tangent * 15.0;
-
-        // 7. COLOR
-        let color = if radius < 15.0 {
-            Color::srgb(1.0, 0.84, 0.0) // Gold Core
-        } else {
-            Color::srgb(0.0, 1.0, 1.0) // Cyan Arms
+
+    // Imported plasma stands in for the genesis burst
+    if import_config.replaces(EmitterTarget::Plasma) {
+        return;
+    }
+
+    let galaxy_count = black_hole_query.iter().count().max(1);
+    for black_hole_entity in black_hole_query.iter() {
+        commands.entity(black_hole_entity).insert(Emitter {
+            shape: EmitterShape::Galaxy {
+                model: initial_conditions.model,
+                mix: initial_conditions.plasma,
+            },
+            mode: EmissionMode::Burst { count: 50_000 / galaxy_count },
+            velocity: VelocityProfile::Equilibrium,
+            color: EmitterColor::ByRadius {
+                inner: Color::srgb(1.0, 0.84, 0.0), // Gold Core
+                outer: Color::srgb(0.0, 1.0, 1.0),  // Cyan Arms
+                split: 15.0,
+            },
+            ..default()
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_emitters(
+    mut commands: Commands,
+    mut emitters: Query<(Entity, &mut Emitter, &Transform, Option<&BlackHole>)>,
+    black_holes: Query<(Entity, &Transform, &BlackHole)>,
+    black_hole_config: Res<BlackHoleConfig>,
+    sph_config: Res<SphConfig>,
+    gpu_resources: Option<Res<GpuGalaxyResources>>,
+    render_queue: Option<Res<RenderQueue>>, // Absent in headless runs
+    time: Res<Time>,
+    mut gpu_cursor: Local<usize>,
+) {
+    let gas_stride = if sph_config.gas_fraction > 0.0 {
+        (1.0 / sph_config.gas_fraction).round().max(1.0) as usize
+    } else {
+        usize::MAX
+    };
+    let cores = gather_cores(&black_hole_config, black_holes.iter());
+
+    for (emitter_entity, mut emitter, transform, black_hole) in emitters.iter_mut() {
+        if !emitter.enabled {
+            continue;
+        }
+
+        // 1. HOW MANY this tick
+        let count = match emitter.mode {
+            EmissionMode::Burst { count } => {
+                emitter.enabled = false;
+                count
+            }
+            EmissionMode::Rate { per_second } => {
+                emitter.accumulator += per_second * time.delta_seconds();
+                let whole = emitter.accumulator.floor();
+                emitter.accumulator -= whole;
+                whole as usize
+            }
         };
+        if count == 0 {
+            continue;
+        }
+
+        let base_velocity = black_hole.map_or(Vec3::ZERO, |black_hole| black_hole.velocity);
+        let arms = emitter.shape.arms();
+        let mut gpu_batch = Vec::new();
+
+        for _ in 0..count {
+            let index = emitter.emitted;
+            emitter.emitted += 1;
+
+            // 2. SAMPLE in the emitter frame, then move into the world
+            let shape_sample = emitter.shape.sample(index);
+            let (local_pos, arm) = (shape_sample.position, shape_sample.arm);
+            let jitter = (Vec3::new(rand::random(), rand::random(), rand::random()) - 0.5) * 2.0 * emitter.dispersion;
+            let local_velocity = emitter.velocity.sample(&shape_sample) + jitter;
+            let position = transform.translation + transform.rotation * local_pos; // Ignore scale (horizon size)
+            let velocity = base_velocity + transform.rotation * local_velocity;
+            let color = emitter.color.sample(local_pos);
+
+            // The Z-Pinch pulls toward the spiral of the emitter's own core, or of the nearest
+            // one for a standalone emitter, so the radius is measured in that core's disk frame
+            let original_radius = owner_core(&cores, emitter_entity, position).map_or(
+                Vec2::new(local_pos.x, local_pos.z).length(),
+                |core| {
+                    let disk = core.disk_frame().inverse() * (position - core.position);
+                    Vec2::new(disk.x, disk.z).length()
+                },
+            );
 
-        commands.spawn((
-            PlasmaParticle {
+            // 3. SPAWN into the target population
+            let new_particle = NewParticle {
+                position,
                 velocity,
-                history: std::collections::VecDeque::new(),
                 color,
-                original_radius: radius,
-                arm: arm_id, // Save the ID so Physics knows where to pull!
-            },
-            Transform::from_translation(position),
-        ));
+                original_radius: original_radius.max(1.0),
+                arm,
+                arms,
+                gas: index % gas_stride == 0,
+            };
+            let Some(mut particle) = spawn_cpu_particle(&mut commands, emitter.target, &new_particle) else {
+                gpu_batch.push(new_particle.to_gpu());
+                continue;
+            };
+            if black_hole.is_some() {
+                particle.insert(GalaxyMember { black_hole: emitter_entity });
+            }
+            if let Some(lifetime) = emitter.lifetime {
+                particle.insert(Lifetime { timer: Timer::from_seconds(lifetime, TimerMode::Once) });
+            }
+        }
+
+        // 4. UPLOAD GPU particles
+        if let (Some(gpu_resources), Some(render_queue)) = (gpu_resources.as_ref(), render_queue.as_ref()) {
+            *gpu_cursor = write_gpu_particles(render_queue, &gpu_resources.particle_buffer, *gpu_cursor, &gpu_batch);
+        }
     }
-}
\ No newline at end of file
+}
+
+// A particle headed for one of the populations (world frame)
+pub struct NewParticle {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub color: Color,
+    pub original_radius: f32, // Plasma: radius of its Z-Pinch target
+    pub arm: u32,
+    pub arms: u32,
+    pub gas: bool,            // Plasma: also an SPH element
+}
+
+impl NewParticle {
+    pub fn to_gpu(&self) -> Particle {
+        Particle {
+            pos: self.position.extend(1.0),
+            vel: self.velocity.extend(0.0),
+            color: Vec4::from_array(self.color.to_linear().to_f32_array()),
+        }
+    }
+}
+
+// Spawns a Star or PlasmaParticle entity.
+// Returns None for the GPU target (batch those through write_gpu_particles).
+pub fn spawn_cpu_particle<'a>(
+    commands: &'a mut Commands,
+    target: EmitterTarget,
+    new_particle: &NewParticle,
+) -> Option<EntityCommands<'a>> {
+    match target {
+        EmitterTarget::Gpu => None,
+        EmitterTarget::Stars => Some(commands.spawn((
+            Star { velocity: new_particle.velocity },
+            Transform::from_translation(new_particle.position),
+        ))),
+        EmitterTarget::Plasma => {
+            let mut particle = commands.spawn((
+                PlasmaParticle {
+                    velocity: new_particle.velocity,
+                    color: new_particle.color,
+                    original_radius: new_particle.original_radius,
+                    arm: new_particle.arm, // Save the ID so Physics knows where to pull!
+                    arms: new_particle.arms,
+                    charge: 1.0, // Ions carry the current; electrons are a neutralising background
+                },
+                Transform::from_translation(new_particle.position),
+            ));
+            // GAS (an SPH element that can shock and compress)
+            if new_particle.gas {
+                particle.insert(SphGas::default());
+            }
+            Some(particle)
+        }
+    }
+}
+
+// Writes `particles` into the GPU buffer starting at slot `first`, wrapping around the end.
+// Returns the slot after the last one written.
+pub fn write_gpu_particles(render_queue: &RenderQueue, buffer: &Buffer, first: usize, particles: &[Particle]) -> usize {
+    let stride = std::mem::size_of::<Particle>() as u64;
+    let mut cursor = first % NUM_PARTICLES;
+    for chunk in particles.chunks(NUM_PARTICLES) {
+        let (head, tail) = chunk.split_at(chunk.len().min(NUM_PARTICLES - cursor));
+        render_queue.write_buffer(buffer, cursor as u64 * stride, bytemuck::cast_slice(head));
+        if !tail.is_empty() {
+            render_queue.write_buffer(buffer, 0, bytemuck::cast_slice(tail));
+        }
+        cursor = (cursor + chunk.len()) % NUM_PARTICLES;
+    }
+    cursor
+}
+
+fn expire_emitted(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Lifetime)>,
+    time: Res<Time>,
+) {
+    for (entity, mut lifetime) in query.iter_mut() {
+        lifetime.timer.tick(time.delta());
+        if lifetime.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// Bipolar jet cone for scenes that want an extra source (e.g. a second quasar)
+pub fn jet_cone_emitter(per_second: f32, speed: f32) -> Emitter {
+    Emitter {
+        shape: EmitterShape::JetCone { half_angle: PI / 24.0, length: 5.0, bipolar: true },
+        mode: EmissionMode::Rate { per_second },
+        velocity: VelocityProfile::Radial { speed },
+        color: EmitterColor::Constant(Color::srgb(0.0, 1.0, 1.0)),
+        lifetime: Some(10.0),
+        ..default()
+    }
+}
@@ -0,0 +1,303 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::physics::escape::{classify, Binding, Orbit};
+use crate::physics::initial_conditions::ComponentMix;
+use crate::simulation::black_hole::{gather_cores, nearest_core, owner_core, suction_potential, BlackHoleConfig, CoreState};
+use crate::simulation::current_field::PicConfig;
+use crate::simulation::galaxy::{update_stars, BlackHole, GalaxyMember, InitialConditions, Star};
+use crate::simulation::gpu_galaxy::PhiResource;
+use crate::simulation::hud::timed;
+use crate::simulation::jet::JetBorne;
+use crate::simulation::plasma::{pinch_target, resonance, update_galaxy_physics, PlasmaParticle, PATTERN_SPEED, PINCH_STIFFNESS, PLASMA_DRAG};
+
+pub struct EscapePlugin;
+
+impl Plugin for EscapePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EscapeConfig>()
+            .init_resource::<BindingCensus>()
+            .add_systems(Update, timed("escape", classify_particles).after(update_galaxy_physics).after(update_stars));
+    }
+}
+
+// What happens to a particle that crosses the outer boundary
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundaryMode {
+    Off,     // It flies on forever
+    Recycle, // It is resampled onto the disk of its nearest galaxy
+    Despawn,
+}
+
+impl BoundaryMode {
+    pub const ALL: [BoundaryMode; 3] = [BoundaryMode::Off, BoundaryMode::Recycle, BoundaryMode::Despawn];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BoundaryMode::Off => "off",
+            BoundaryMode::Recycle => "recycle",
+            BoundaryMode::Despawn => "despawn",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+}
+
+// Picked on the command line: `cargo run -- --escape-radius 120 --boundary recycle --boundary-radius 300`
+#[derive(Resource)]
+pub struct EscapeConfig {
+    pub escape_radius: f32,   // Distance from the nearest core beyond which a particle counts as escaping
+    pub boundary_radius: f32, // Distance at which escapees are recycled or despawned
+    pub boundary: BoundaryMode,
+    pub interval: f32,        // Seconds between classifications
+}
+
+impl Default for EscapeConfig {
+    fn default() -> Self {
+        Self {
+            escape_radius: 150.0,
+            boundary_radius: 400.0,
+            boundary: BoundaryMode::Off,
+            interval: 0.1,
+        }
+    }
+}
+
+impl EscapeConfig {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        let mut config = Self::default();
+        if let Some(radius) = value("--escape-radius").and_then(|v| v.parse::<f32>().ok()) {
+            config.escape_radius = radius.max(0.0);
+        }
+        if let Some(radius) = value("--boundary-radius").and_then(|v| v.parse::<f32>().ok()) {
+            config.boundary_radius = radius.max(0.0);
+        }
+        if let Some(mode) = value("--boundary") {
+            match BoundaryMode::parse(mode) {
+                Some(mode) => config.boundary = mode,
+                None => eprintln!("Unknown --boundary {mode}"),
+            }
+        }
+        config
+    }
+}
+
+// The latest classification of every star and plasma particle (not the GPU buffer)
+#[derive(Component)]
+pub struct Fate(pub Binding);
+
+// Counts of the latest classification, plus every particle that has crossed the boundary
+#[derive(Resource, Default)]
+pub struct BindingCensus {
+    pub bound: usize,
+    pub escaping: usize,
+    pub jet_borne: usize,
+    pub recycled: usize,
+    pub despawned: usize,
+}
+
+impl BindingCensus {
+    pub fn total(&self) -> usize {
+        self.bound + self.escaping + self.jet_borne
+    }
+
+    // Share of the classified particles, 0 when there are none
+    pub fn fraction(&self, count: usize) -> f32 {
+        count as f32 / self.total().max(1) as f32
+    }
+
+    fn count(&mut self, binding: Binding) {
+        match binding {
+            Binding::Bound => self.bound += 1,
+            Binding::Escaping => self.escaping += 1,
+            Binding::JetBorne => self.jet_borne += 1,
+        }
+    }
+}
+
+// A fresh particle from the galaxy model, placed on the disk of `core` (world frame)
+fn resample(initial_conditions: &InitialConditions, mix: &ComponentMix, core: &CoreState) -> (Vec3, Vec3, u32) {
+    let mut rng = rand::thread_rng();
+    let body = initial_conditions.model.sample(rng.gen_range(0..1024), mix, &mut rng);
+    let frame = core.disk_frame();
+    (core.position + frame * body.position, core.velocity + frame * body.velocity, body.arm)
+}
+
+type StarOrbits<'a> = (Entity, &'a mut Star, &'a mut Transform, Option<&'a Fate>);
+type PlasmaOrbits<'a> = (
+    Entity,
+    &'a mut PlasmaParticle,
+    &'a mut Transform,
+    Option<&'a GalaxyMember>,
+    Option<&'a Fate>,
+    Has<JetBorne>,
+);
+
+#[allow(clippy::too_many_arguments)]
+fn classify_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<EscapeConfig>,
+    phi: Res<PhiResource>,
+    pic_config: Res<PicConfig>,
+    initial_conditions: Res<InitialConditions>,
+    black_hole_config: Res<BlackHoleConfig>,
+    black_holes: Query<(Entity, &Transform, &BlackHole)>,
+    mut stars: Query<StarOrbits, (Without<PlasmaParticle>, Without<BlackHole>)>,
+    mut plasma: Query<PlasmaOrbits, (Without<Star>, Without<BlackHole>)>,
+    mut census: ResMut<BindingCensus>,
+    mut since_classified: Local<f32>,
+) {
+    *since_classified += time.delta_seconds();
+    if *since_classified < config.interval {
+        return;
+    }
+    *since_classified = 0.0;
+
+    let cores = gather_cores(&black_hole_config, black_holes.iter());
+    let crossed = |offset: Vec3| config.boundary != BoundaryMode::Off && offset.length() > config.boundary_radius;
+    let mark = |commands: &mut Commands, entity: Entity, fate: Option<&Fate>, binding: Binding| {
+        if fate.is_none_or(|fate| fate.0 != binding) {
+            commands.entity(entity).try_insert(Fate(binding));
+        }
+    };
+    let (mut recycled, mut despawned) = (0, 0);
+    let mut counts = BindingCensus::default();
+
+    // 1. STARS fall through the suction potential of every core
+    for (entity, mut star, mut transform, fate) in stars.iter_mut() {
+        let Some(core) = nearest_core(&cores, transform.translation) else { continue };
+        if crossed(transform.translation - core.position) {
+            if config.boundary == BoundaryMode::Despawn {
+                commands.entity(entity).despawn();
+                despawned += 1;
+                continue;
+            }
+            let (position, velocity, _) = resample(&initial_conditions, &initial_conditions.stars, core);
+            transform.translation = position;
+            star.velocity = velocity;
+            commands.entity(entity).try_insert(GalaxyMember { black_hole: core.entity });
+            recycled += 1;
+        }
+
+        let pos = transform.translation;
+        let offset = pos - core.position;
+        // The golden drag turns the velocity about world Z every frame, so only the Z component
+        // keeps its heading long enough to carry a star out. World Z lies in the default disk
+        // plane: it is just the one axis the drag leaves untouched.
+        let orbit = Orbit {
+            offset,
+            velocity: Vec3::Z * (star.velocity - core.velocity).z,
+            potential: suction_potential(&cores, pos),
+            boundary_potential: suction_potential(&cores, core.position + offset.normalize_or_zero() * config.boundary_radius),
+            drag: 0.0,
+        };
+        let binding = classify(&orbit, false, config.escape_radius, config.boundary_radius);
+        mark(&mut commands, entity, fate, binding);
+        counts.count(binding);
+    }
+
+    // 2. PLASMA sits in the Z-Pinch well around its ideal spiral, as deep as the resonance allows.
+    // Without the analytic pull only the drag holds it back. Jets are left to rain back on their own.
+    // The Lorentz force of the core and self-generated fields is left out: with no electric field
+    // it only turns the velocity, so it redirects an orbit but never changes its energy.
+    let stiffness = if pic_config.analytic_pinch() { PINCH_STIFFNESS * resonance(phi.phi_value) } else { 0.0 };
+    let galaxy_angle = time.elapsed_seconds() * PATTERN_SPEED;
+    for (entity, mut particle, mut transform, member, fate, jet_borne) in plasma.iter_mut() {
+        let Some(core) = nearest_core(&cores, transform.translation) else { continue };
+        if !jet_borne && crossed(transform.translation - core.position) {
+            if config.boundary == BoundaryMode::Despawn {
+                commands.entity(entity).despawn();
+                despawned += 1;
+                continue;
+            }
+            let (position, velocity, arm) = resample(&initial_conditions, &initial_conditions.plasma, core);
+            let local = core.disk_frame().inverse() * (position - core.position);
+            transform.translation = position;
+            particle.velocity = velocity;
+            particle.original_radius = Vec2::new(local.x, local.z).length().max(1.0);
+            particle.arm = arm;
+            particle.arms = initial_conditions.model.arms.max(1);
+            commands.entity(entity).try_insert(GalaxyMember { black_hole: core.entity });
+            recycled += 1;
+        }
+
+        let pos = transform.translation;
+        let home = member
+            .and_then(|member| owner_core(&cores, member.black_hole, pos))
+            .unwrap_or(core);
+        let target = pinch_target(&particle, home, phi.phi_value, galaxy_angle);
+        let climb = (config.boundary_radius - particle.original_radius).max(0.0);
+        let orbit = Orbit {
+            offset: pos - core.position,
+            velocity: particle.velocity - core.velocity,
+            potential: 0.5 * stiffness * target.distance_squared(pos),
+            boundary_potential: 0.5 * stiffness * climb * climb,
+            drag: PLASMA_DRAG,
+        };
+        let binding = classify(&orbit, jet_borne, config.escape_radius, config.boundary_radius);
+        mark(&mut commands, entity, fate, binding);
+        counts.count(binding);
+    }
+
+    counts.recycled = census.recycled + recycled;
+    counts.despawned = census.despawned + despawned;
+    *census = counts;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // One black hole at the origin, a star well inside the boundary and one beyond it
+    fn boundary_app(boundary: BoundaryMode) -> (App, Entity, Entity) {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<BindingCensus>()
+            .init_resource::<PicConfig>()
+            .init_resource::<InitialConditions>()
+            .init_resource::<BlackHoleConfig>()
+            .insert_resource(PhiResource { phi_value: 1.618034 })
+            .insert_resource(EscapeConfig { boundary, interval: 0.0, ..default() })
+            .add_systems(Update, classify_particles);
+        app.world_mut().spawn((
+            BlackHole { stored_mass: 0, velocity: Vec3::ZERO, spin: 0.0, accretion_rate: 0.0 },
+            Transform::IDENTITY,
+        ));
+        let inside = app.world_mut().spawn((Star { velocity: Vec3::ZERO }, Transform::from_xyz(20.0, 0.0, 0.0))).id();
+        let outside = app.world_mut().spawn((Star { velocity: Vec3::ZERO }, Transform::from_xyz(1000.0, 0.0, 0.0))).id();
+        app.update();
+        (app, inside, outside)
+    }
+
+    #[test]
+    fn boundary_recycles_or_despawns_only_the_particles_past_it() {
+        let (app, inside, outside) = boundary_app(BoundaryMode::Despawn);
+        assert!(app.world().get_entity(inside).is_some());
+        assert!(app.world().get_entity(outside).is_none());
+        let census = app.world().resource::<BindingCensus>();
+        assert_eq!((census.despawned, census.recycled, census.total()), (1, 0, 1));
+
+        let (app, inside, outside) = boundary_app(BoundaryMode::Recycle);
+        // Resampled onto the galaxy, whose bulge tail reaches at most ~600
+        let radius = app.world().get::<Transform>(outside).unwrap().translation.length();
+        assert!(radius < 1000.0);
+        assert!(app.world().get::<GalaxyMember>(outside).is_some());
+        assert!(app.world().get::<GalaxyMember>(inside).is_none());
+        let census = app.world().resource::<BindingCensus>();
+        assert_eq!((census.despawned, census.recycled, census.total()), (0, 1, 2));
+
+        // Off leaves it flying, counted as escaping
+        let (app, _, outside) = boundary_app(BoundaryMode::Off);
+        assert_eq!(app.world().get::<Transform>(outside).unwrap().translation.x, 1000.0);
+        assert_eq!(app.world().resource::<BindingCensus>().escaping, 1);
+    }
+}
@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use crate::physics::ether::{EtherGrid, EtherSink, EtherStep};
+use crate::simulation::black_hole::{barycenter, gather_cores, BlackHoleConfig};
+use crate::simulation::debug_viz::{FlowSource, FlowVizConfig};
+use crate::simulation::ether_volume::{EtherView, EtherVolumeConfig};
+use crate::simulation::galaxy::BlackHole;
+use crate::simulation::hud::timed;
+use crate::simulation::plasma::update_galaxy_physics;
+
+const MAX_STEP: f32 = 1.0 / 30.0; // Longer frames are split so the diffusion stays stable
+
+pub struct EtherPlugin;
+
+impl Plugin for EtherPlugin {
+    fn build(&self, app: &mut App) {
+        let config = EtherConfig::default();
+        app.insert_resource(Ether {
+            grid: EtherGrid::new(config.dims, config.cell_size, Vec3::ZERO),
+        })
+        .insert_resource(config)
+        .add_systems(Update, timed("ether", step_ether).after(update_galaxy_physics));
+    }
+}
+
+#[derive(Resource)]
+pub struct EtherConfig {
+    pub enabled: bool, // Still only steps while something draws the grid (see `step_ether`)
+    pub dims: UVec3,
+    pub cell_size: f32,
+    pub sink_rate: f32,     // Pressure drained per second per unit of suction inside a horizon
+    pub pressure_push: f32, // Flow gained per unit pressure gradient per second
+    pub damping: f32,       // Must exceed 1 / cell_size (see EtherStep)
+}
+
+impl Default for EtherConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dims: UVec3::new(40, 16, 40),
+            cell_size: 4.0,
+            sink_rate: 0.5,
+            pressure_push: 40.0,
+            damping: 0.5,
+        }
+    }
+}
+
+// The Fluid Ether the galaxies sit in, drained by the black holes.
+#[derive(Resource)]
+pub struct Ether {
+    pub grid: EtherGrid,
+}
+
+fn step_ether(
+    time: Res<Time>,
+    config: Res<EtherConfig>,
+    mut ether: ResMut<Ether>,
+    black_hole_query: Query<(Entity, &Transform, &BlackHole)>,
+    black_hole_config: Res<BlackHoleConfig>,
+    volume: Res<EtherVolumeConfig>,
+    flow: Res<FlowVizConfig>,
+) {
+    // Nothing else reads the grid, so it only runs while the volume, the slice or a grid-sourced flow view is shown
+    let drawn = volume.view != EtherView::Off
+        || (flow.source == FlowSource::Grid && (flow.arrows || flow.streamlines || flow.lic));
+    let dt = time.delta_seconds();
+    if !config.enabled || !drawn || dt <= 0.0 {
+        return;
+    }
+    let grid = &mut ether.grid;
+
+    // Rebuild the grid if the config changed shape
+    if grid.dims != config.dims || grid.cell_size != config.cell_size {
+        *grid = EtherGrid::new(config.dims, config.cell_size, Vec3::ZERO);
+    }
+
+    // 1. FOLLOW THE CORES, as the PIC grid does
+    let cores = gather_cores(&black_hole_config, black_hole_query.iter());
+    grid.recenter(barycenter(&cores));
+
+    // 2. SINKS: every horizon drains at least one cell
+    let sinks: Vec<EtherSink> = cores
+        .iter()
+        .map(|core| EtherSink {
+            position: core.position,
+            radius: core.event_horizon.max(config.cell_size),
+            rate: core.suction * config.sink_rate,
+        })
+        .collect();
+
+    // 3. STEP
+    let substeps = (dt / MAX_STEP).ceil().max(1.0);
+    let step = EtherStep {
+        dt: dt / substeps,
+        pressure_push: config.pressure_push,
+        damping: config.damping,
+    };
+    for _ in 0..substeps as usize {
+        grid.step(step, &sinks);
+    }
+}
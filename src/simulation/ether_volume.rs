@@ -0,0 +1,414 @@
+use bevy::pbr::{Material, MaterialPipeline, MaterialPipelineKey, MaterialPlugin, NotShadowCaster};
+use bevy::prelude::*;
+use bevy::render::mesh::MeshVertexBufferLayoutRef;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{
+    AsBindGroup, Extent3d, RenderPipelineDescriptor, ShaderRef, ShaderType, SpecializedMeshPipelineError,
+    TextureDimension, TextureFormat,
+};
+use bevy::render::texture::ImageSampler;
+use bevy::render::view::NoFrustumCulling;
+use std::f32::consts::FRAC_PI_2;
+use crate::viz::colormap::{normalize, quantile_range, STOPS};
+use crate::physics::ether::EtherGrid;
+use crate::simulation::color_map::ColorMapConfig;
+use crate::simulation::debug_viz::FlowVizConfig;
+use crate::simulation::ether::Ether;
+
+const SWEEP_SPEED: f32 = 0.25; // Grid widths per second while , or . is held
+
+pub struct EtherVolumePlugin;
+
+impl Plugin for EtherVolumePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MaterialPlugin::<EtherVolumeMaterial>::default())
+            .init_resource::<EtherVolumeConfig>()
+            .add_systems(Startup, setup_ether_volume)
+            .add_systems(Update, control_ether_volume)
+            .add_systems(PostUpdate, update_ether_volume);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EtherView {
+    Off,
+    Volume,
+    Slice,
+    Both,
+}
+
+impl EtherView {
+    pub const ALL: [EtherView; 4] = [EtherView::Off, EtherView::Volume, EtherView::Slice, EtherView::Both];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            EtherView::Off => "off",
+            EtherView::Volume => "volume",
+            EtherView::Slice => "slice",
+            EtherView::Both => "both",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|view| view.name() == name)
+    }
+
+    fn volume(self) -> bool {
+        matches!(self, EtherView::Volume | EtherView::Both)
+    }
+
+    fn slice(self) -> bool {
+        matches!(self, EtherView::Slice | EtherView::Both)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EtherChannel {
+    Pressure,  // w; drawn densest where the sinks have drained it
+    Vorticity, // |∇×flow|; drawn densest where the flow swirls
+}
+
+impl EtherChannel {
+    pub fn name(self) -> &'static str {
+        match self {
+            EtherChannel::Pressure => "pressure",
+            EtherChannel::Vorticity => "vorticity",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "pressure" => Some(EtherChannel::Pressure),
+            "vorticity" => Some(EtherChannel::Vorticity),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SliceAxis {
+    X,
+    Y,
+    Z,
+}
+
+impl SliceAxis {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "x" => Some(SliceAxis::X),
+            "y" => Some(SliceAxis::Y),
+            "z" => Some(SliceAxis::Z),
+            _ => None,
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            SliceAxis::X => SliceAxis::Y,
+            SliceAxis::Y => SliceAxis::Z,
+            SliceAxis::Z => SliceAxis::X,
+        }
+    }
+
+    // Turns the unit XZ plane to face along the axis, and scales it over the box (`extent`)
+    fn plane(self, extent: Vec3) -> (Quat, Vec3) {
+        match self {
+            SliceAxis::X => (Quat::from_rotation_z(-FRAC_PI_2), Vec3::new(extent.y, 1.0, extent.z)),
+            SliceAxis::Y => (Quat::IDENTITY, Vec3::new(extent.x, 1.0, extent.z)),
+            SliceAxis::Z => (Quat::from_rotation_x(FRAC_PI_2), Vec3::new(extent.x, 1.0, extent.y)),
+        }
+    }
+
+    fn normal(self) -> Vec3 {
+        match self {
+            SliceAxis::X => Vec3::X,
+            SliceAxis::Y => Vec3::Y,
+            SliceAxis::Z => Vec3::Z,
+        }
+    }
+}
+
+// V cycles off / volume / slice / both, X the channel, B the slice axis; hold , and . to sweep
+// the slice, - and = to thin or thicken the volume. The colormap follows the particle colormap (N).
+// Picked on the command line: `cargo run -- --ether-view both --ether-channel vorticity --ether-range 0,0.2`
+#[derive(Resource)]
+pub struct EtherVolumeConfig {
+    pub view: EtherView,
+    pub channel: EtherChannel,
+    pub range: Option<(f32, f32)>, // None follows the 2nd to 98th percentile of the grid
+    pub opacity: f32,              // Extinction per cell at full value
+    pub steps: u32,                // Ray-march samples across the box
+    pub slice_axis: SliceAxis,
+    pub slice_position: f32,       // 0..1 across the grid
+}
+
+impl Default for EtherVolumeConfig {
+    fn default() -> Self {
+        Self {
+            view: EtherView::Off,
+            channel: EtherChannel::Pressure,
+            range: None,
+            opacity: 0.3,
+            steps: 96,
+            slice_axis: SliceAxis::Y,
+            slice_position: 0.5,
+        }
+    }
+}
+
+impl EtherVolumeConfig {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        let mut config = Self::default();
+        if let Some(view) = value("--ether-view") {
+            match EtherView::parse(view) {
+                Some(view) => config.view = view,
+                None => eprintln!("Unknown --ether-view {view}"),
+            }
+        }
+        if let Some(channel) = value("--ether-channel") {
+            match EtherChannel::parse(channel) {
+                Some(channel) => config.channel = channel,
+                None => eprintln!("Unknown --ether-channel {channel}"),
+            }
+        }
+        let range = value("--ether-range").and_then(|range| range.split_once(','));
+        if let Some((Ok(min), Ok(max))) = range.map(|(min, max)| (min.parse::<f32>(), max.parse::<f32>())) {
+            config.range = Some((min, max));
+        }
+        if let Some(opacity) = value("--ether-opacity").and_then(|v| v.parse::<f32>().ok()) {
+            config.opacity = opacity.max(0.0);
+        }
+        if let Some(steps) = value("--ether-steps").and_then(|v| v.parse::<u32>().ok()) {
+            config.steps = steps.clamp(8, 512);
+        }
+        if let Some(axis) = value("--ether-slice") {
+            match SliceAxis::parse(axis) {
+                Some(axis) => config.slice_axis = axis,
+                None => eprintln!("Unknown --ether-slice {axis}"),
+            }
+        }
+        if let Some(position) = value("--ether-slice-at").and_then(|v| v.parse::<f32>().ok()) {
+            config.slice_position = position.clamp(0.0, 1.0);
+        }
+        config
+    }
+
+    // Places the unit XZ plane across the grid at the slice; its UV (0..1) spans the box
+    pub fn slice_transform(&self, grid: &EtherGrid) -> Transform {
+        let extent = grid.extent();
+        let (rotation, scale) = self.slice_axis.plane(extent);
+        let normal = self.slice_axis.normal();
+        let translation = grid.origin + extent * 0.5 + normal * (self.slice_position - 0.5) * normal.dot(extent);
+        Transform { translation, rotation, scale }
+    }
+}
+
+// Matches EtherVolumeParams in ether_volume.wgsl
+#[derive(Clone, Copy, Debug, Default, ShaderType)]
+pub struct EtherVolumeParams {
+    pub colormap: [Vec4; STOPS],
+    pub box_min: Vec4, // Node (0, 0, 0); w unused
+    pub box_max: Vec4, // Last node; w unused
+    pub dims: Vec4,    // Grid nodes per axis; w unused
+    pub mode: u32,     // 0 ray-marches the box, 1 draws a slice
+    pub invert: u32,   // 1 makes low values dense (pressure wells)
+    pub steps: u32,
+    pub opacity: f32,
+}
+
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct EtherVolumeMaterial {
+    #[uniform(0)]
+    pub params: EtherVolumeParams,
+    #[texture(1, dimension = "3d")]
+    #[sampler(2)]
+    pub volume: Handle<Image>, // The selected channel, normalized to the range
+}
+
+impl Material for EtherVolumeMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/ether_volume.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Premultiplied
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // The slice is seen from both sides; the volume shades its back faces so the camera may fly inside
+        descriptor.primitive.cull_mode = None;
+        Ok(())
+    }
+}
+
+#[derive(Component)]
+struct EtherVolumeBox;
+
+#[derive(Component)]
+struct EtherSlice;
+
+#[derive(Resource)]
+struct EtherVolumeTexture(Handle<Image>);
+
+fn volume_image(dims: UVec3) -> Image {
+    let mut image = Image::new(
+        Extent3d { width: dims.x, height: dims.y, depth_or_array_layers: dims.z },
+        TextureDimension::D3,
+        vec![0; (dims.x * dims.y * dims.z) as usize],
+        TextureFormat::R8Unorm,
+        RenderAssetUsages::default(),
+    );
+    image.sampler = ImageSampler::linear();
+    image
+}
+
+fn setup_ether_volume(
+    mut commands: Commands,
+    ether: Res<Ether>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<EtherVolumeMaterial>>,
+) {
+    let volume = images.add(volume_image(ether.grid.dims));
+    let material = |mode: u32| EtherVolumeMaterial {
+        params: EtherVolumeParams { mode, ..default() },
+        volume: volume.clone(),
+    };
+
+    commands.spawn((
+        MaterialMeshBundle {
+            mesh: meshes.add(Cuboid::from_size(Vec3::ONE)),
+            material: materials.add(material(0)),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        EtherVolumeBox,
+        NotShadowCaster,
+        NoFrustumCulling,
+    ));
+    commands.spawn((
+        MaterialMeshBundle {
+            mesh: meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(0.5))),
+            material: materials.add(material(1)),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        EtherSlice,
+        NotShadowCaster,
+    ));
+    commands.insert_resource(EtherVolumeTexture(volume));
+}
+
+fn control_ether_volume(time: Res<Time>, keys: Res<ButtonInput<KeyCode>>, mut config: ResMut<EtherVolumeConfig>) {
+    if keys.just_pressed(KeyCode::KeyV) {
+        let next = EtherView::ALL.iter().position(|view| *view == config.view).map_or(0, |i| i + 1);
+        config.view = EtherView::ALL[next % EtherView::ALL.len()];
+    }
+    if keys.just_pressed(KeyCode::KeyX) {
+        config.channel = match config.channel {
+            EtherChannel::Pressure => EtherChannel::Vorticity,
+            EtherChannel::Vorticity => EtherChannel::Pressure,
+        };
+        config.range = None;
+    }
+    if keys.just_pressed(KeyCode::KeyB) {
+        config.slice_axis = config.slice_axis.next();
+    }
+    let sweep = match (keys.pressed(KeyCode::Comma), keys.pressed(KeyCode::Period)) {
+        (true, false) => -1.0,
+        (false, true) => 1.0,
+        _ => 0.0,
+    };
+    if sweep != 0.0 {
+        config.slice_position = (config.slice_position + sweep * SWEEP_SPEED * time.delta_seconds()).clamp(0.0, 1.0);
+    }
+    if keys.just_pressed(KeyCode::Minus) {
+        config.opacity *= 0.8;
+    }
+    if keys.just_pressed(KeyCode::Equal) {
+        config.opacity *= 1.25;
+    }
+}
+
+type BoxQuery<'w, 's> = Query<'w, 's, (&'static mut Transform, &'static mut Visibility, &'static Handle<EtherVolumeMaterial>), With<EtherVolumeBox>>;
+type SliceQuery<'w, 's> = Query<'w, 's, (&'static mut Transform, &'static mut Visibility, &'static Handle<EtherVolumeMaterial>), (With<EtherSlice>, Without<EtherVolumeBox>)>;
+
+#[allow(clippy::too_many_arguments)]
+fn update_ether_volume(
+    config: Res<EtherVolumeConfig>,
+    color_map: Res<ColorMapConfig>,
+    flow: Res<FlowVizConfig>,
+    ether: Res<Ether>,
+    texture: Option<Res<EtherVolumeTexture>>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<EtherVolumeMaterial>>,
+    mut boxes: BoxQuery,
+    mut slices: SliceQuery,
+) {
+    let grid = &ether.grid;
+    let extent = grid.extent();
+    let center = grid.origin + extent * 0.5;
+
+    // 1. PLACE the box over the grid and the slice across it
+    for (mut transform, mut visibility, _) in boxes.iter_mut() {
+        *transform = Transform::from_translation(center).with_scale(extent);
+        *visibility = if config.view.volume() { Visibility::Inherited } else { Visibility::Hidden };
+    }
+    for (mut transform, mut visibility, _) in slices.iter_mut() {
+        *transform = config.slice_transform(grid);
+        // The LIC texture takes the plane over while it is shown
+        *visibility = if config.view.slice() && !flow.lic { Visibility::Inherited } else { Visibility::Hidden };
+    }
+    let Some(texture) = texture.filter(|_| config.view != EtherView::Off) else {
+        return;
+    };
+
+    // 2. NORMALIZE the channel into the texture
+    let values = match config.channel {
+        EtherChannel::Pressure => grid.cells.iter().map(|cell| cell.w).collect(),
+        EtherChannel::Vorticity => grid.vorticity(),
+    };
+    let range = config.range.or_else(|| quantile_range(&values, 0.02, 0.98)).unwrap_or((0.0, 1.0));
+    if let Some(image) = images.get_mut(&texture.0) {
+        if image.texture_descriptor.size.depth_or_array_layers != grid.dims.z
+            || image.texture_descriptor.size.width != grid.dims.x
+            || image.texture_descriptor.size.height != grid.dims.y
+        {
+            *image = volume_image(grid.dims);
+        }
+        image.data = values
+            .iter()
+            .map(|&value| (normalize(value, range).clamp(0.0, 1.0) * 255.0).round() as u8)
+            .collect();
+    }
+
+    // 3. TRANSFER FUNCTION; touching the materials also rebinds the re-uploaded texture
+    let colormap = color_map.colormap.stops_linear().map(|[r, g, b]| Vec4::new(r, g, b, 1.0));
+    let handles = boxes.iter().map(|(_, _, handle)| handle).chain(slices.iter().map(|(_, _, handle)| handle));
+    for handle in handles {
+        if let Some(material) = materials.get_mut(handle) {
+            material.params = EtherVolumeParams {
+                colormap,
+                box_min: grid.origin.extend(0.0),
+                box_max: (grid.origin + extent).extend(0.0),
+                dims: grid.dims.as_vec3().extend(0.0),
+                invert: (config.channel == EtherChannel::Pressure) as u32,
+                steps: config.steps,
+                opacity: config.opacity,
+                ..material.params
+            };
+        }
+    }
+}
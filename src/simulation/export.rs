@@ -0,0 +1,196 @@
+use bevy::prelude::*;
+use std::path::PathBuf;
+use crate::io::export::{write_csv, write_npy, write_pvd, write_vtu, ExportFields, ExportRecord};
+use crate::simulation::galaxy::Star;
+use crate::simulation::gpu_galaxy::GpuReadback;
+use crate::simulation::jet::JetBorne;
+use crate::simulation::plasma::PlasmaParticle;
+use crate::simulation::sph::SphGas;
+
+pub struct ExportPlugin;
+
+impl Plugin for ExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ExportConfig>()
+            .add_systems(Last, export_snapshots); // After every physics system of the tick
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Npy, // NumPy structured array
+    Vtu, // VTK point cloud (+ a .pvd time series for ParaView)
+}
+
+// Population tag written with every particle
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum PopulationTag {
+    Star = 0,
+    Plasma = 1,
+    Gas = 2, // SPH plasma
+    Jet = 3, // Plasma riding a quasar jet
+    Gpu = 4, // GPU read-back sample
+}
+
+// Picked on the command line:
+// `cargo run -- --export csv,npy,vtu --export-every 0.5 --export-fields position,velocity`
+#[derive(Resource)]
+pub struct ExportConfig {
+    pub enabled: bool,
+    pub directory: PathBuf,
+    pub formats: Vec<ExportFormat>,
+    pub fields: ExportFields,
+    pub interval: f32, // Simulation seconds between snapshots
+    pub stars: bool,
+    pub plasma: bool,
+    pub gpu: bool,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: PathBuf::from("exports"),
+            formats: vec![ExportFormat::Csv],
+            fields: ExportFields::default(),
+            interval: 1.0,
+            stars: true,
+            plasma: true,
+            gpu: true,
+        }
+    }
+}
+
+impl ExportConfig {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+        let list = |flag: &str| value(flag).map(|v| v.split(',').map(str::to_string).collect::<Vec<_>>());
+
+        let mut config = Self::default();
+        if let Some(formats) = list("--export") {
+            config.enabled = true;
+            config.formats = formats
+                .iter()
+                .filter_map(|format| match format.as_str() {
+                    "csv" => Some(ExportFormat::Csv),
+                    "npy" => Some(ExportFormat::Npy),
+                    "vtu" | "vtk" => Some(ExportFormat::Vtu),
+                    _ => {
+                        eprintln!("Unknown export format {format}");
+                        None
+                    }
+                })
+                .collect();
+        }
+        if let Some(directory) = value("--export-dir") {
+            config.directory = PathBuf::from(directory);
+        }
+        if let Some(interval) = value("--export-every").and_then(|v| v.parse().ok()) {
+            config.interval = interval;
+        }
+        if let Some(fields) = list("--export-fields") {
+            let has = |name: &str| fields.iter().any(|field| field == name);
+            config.fields = ExportFields {
+                position: has("position"),
+                velocity: has("velocity"),
+                color: has("color"),
+                population: has("population"),
+            };
+        }
+        if let Some(populations) = list("--export-populations") {
+            let has = |name: &str| populations.iter().any(|population| population == name);
+            config.stars = has("stars");
+            config.plasma = has("plasma");
+            config.gpu = has("gpu");
+        }
+        config
+    }
+}
+
+#[derive(Default)]
+struct ExportState {
+    next_time: f32,
+    snapshot: u32,
+    vtu_series: Vec<(f32, String)>,
+    failed: bool,
+}
+
+type PlasmaRecord<'a> = (&'a PlasmaParticle, &'a Transform, Option<&'a SphGas>, Option<&'a JetBorne>);
+
+fn export_snapshots(
+    config: Res<ExportConfig>,
+    time: Res<Time>,
+    stars: Query<(&Star, &Transform)>,
+    plasma: Query<PlasmaRecord>,
+    readback: Option<Res<GpuReadback>>,
+    mut state: Local<ExportState>,
+) {
+    if !config.enabled || state.failed || time.elapsed_seconds() < state.next_time {
+        return;
+    }
+    state.next_time = time.elapsed_seconds() + config.interval;
+
+    // 1. GATHER every selected population
+    let mut records = Vec::new();
+    if config.stars {
+        records.extend(stars.iter().map(|(star, transform)| ExportRecord {
+            position: transform.translation,
+            velocity: star.velocity,
+            color: Vec4::ONE,
+            population: PopulationTag::Star as u32,
+        }));
+    }
+    if config.plasma {
+        records.extend(plasma.iter().map(|(particle, transform, gas, jet)| ExportRecord {
+            position: transform.translation,
+            velocity: particle.velocity,
+            color: Vec4::from_array(particle.color.to_linear().to_f32_array()),
+            population: match (jet, gas) {
+                (Some(_), _) => PopulationTag::Jet,
+                (None, Some(_)) => PopulationTag::Gas,
+                (None, None) => PopulationTag::Plasma,
+            } as u32,
+        }));
+    }
+    if let Some(readback) = readback.as_ref().filter(|_| config.gpu) {
+        records.extend(readback.particles.iter().map(|particle| ExportRecord {
+            position: particle.pos.truncate(),
+            velocity: particle.vel.truncate(),
+            color: particle.color,
+            population: PopulationTag::Gpu as u32,
+        }));
+    }
+
+    // 2. WRITE one file per format
+    if let Err(error) = std::fs::create_dir_all(&config.directory) {
+        eprintln!("Export disabled, cannot create {}: {error}", config.directory.display());
+        state.failed = true;
+        return;
+    }
+    let stem = format!("snapshot_{:05}", state.snapshot);
+    for format in config.formats.iter() {
+        let (extension, result) = match format {
+            ExportFormat::Csv => ("csv", write_csv(&config.directory.join(format!("{stem}.csv")), &records, &config.fields)),
+            ExportFormat::Npy => ("npy", write_npy(&config.directory.join(format!("{stem}.npy")), &records, &config.fields)),
+            ExportFormat::Vtu => {
+                let name = format!("{stem}.vtu");
+                let result = write_vtu(&config.directory.join(&name), &records, &config.fields).and_then(|_| {
+                    state.vtu_series.push((time.elapsed_seconds(), name));
+                    write_pvd(&config.directory.join("snapshots.pvd"), &state.vtu_series)
+                });
+                ("vtu", result)
+            }
+        };
+        if let Err(error) = result {
+            eprintln!("Export of {stem}.{extension} failed: {error}");
+        }
+    }
+    state.snapshot += 1;
+}
@@ -1,18 +1,23 @@
 use bevy::prelude::*;
-use bevy::time::{Timer, TimerMode};
-use crate::simulation::plasma::{update_galaxy_physics, PlasmaParticle};
+use crate::simulation::black_hole::{disk_frame, gather_cores, AccretionLedger, BlackHoleConfig};
+use crate::simulation::emitter::EmitterTarget;
+use crate::simulation::hud::timed;
+use crate::simulation::import::ImportConfig;
+use crate::simulation::inspector::ForceProbe;
+use crate::simulation::paths::PathTrail;
+use crate::simulation::scenario::Scenario;
+use crate::physics::initial_conditions::{ComponentMix, GalaxyModel};
 
 pub struct GalaxyPlugin;
 
 impl Plugin for GalaxyPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, (setup_galaxy, setup_black_hole))
+        app.init_resource::<Scenario>()
+            .init_resource::<InitialConditions>()
+            .add_systems(Startup, (setup_black_hole, setup_galaxy).chain())
             .add_systems(Update, (
                 rotate_black_hole,
-                spawn_trail,
-                update_stars,
-                update_trail,
-                update_galaxy_physics,
+                timed("stars", update_stars),
             ));
     }
 }
@@ -26,150 +31,161 @@ pub struct Star {
 pub struct BlackHole {
     pub stored_mass: usize,
     pub velocity: Vec3,
+    pub spin: f32, // Dimensionless spin parameter a* (-1..1)
+    pub accretion_rate: f32, // Particles/s swallowed or launched (drives the jet power)
 }
 
-
+// Which galaxy (black hole) a star or plasma particle was born into
 #[derive(Component)]
-pub struct TrailPoint {
-    pub timer: Timer,
+pub struct GalaxyMember {
+    pub black_hole: Entity,
 }
 
-fn setup_galaxy(mut commands: Commands) {
-    // Spawn stars in random disk for emergent spiral behavior
-    for _ in 0..10000 {
-        let theta = rand::random::<f32>() * std::f32::consts::TAU;
-        let r = rand::random::<f32>().sqrt() * 50.0;
-        let y = (rand::random::<f32>() - 0.5) * 4.0;
-        let pos = Vec3::new(
-            r * theta.cos(),
-            y,
-            r * theta.sin(),
-        );
-        let tangent = Vec3::new(-pos.z, 0.0, pos.x).normalize();
-        let speed = 15.0;
-        let velocity = tangent * speed + Vec3::new(0.0, (rand::random::<f32>() - 0.5) * 0.5, 0.0);
-        commands.spawn((
-            Star { velocity },
-            Transform::from_translation(pos),
-        ));
-    }
+// Galaxy profile shared by every spawner: stars, the genesis plasma and the GPU buffer.
+// Each population samples its own mix of the model's components.
+#[derive(Resource)]
+pub struct InitialConditions {
+    pub model: GalaxyModel,
+    pub stars: ComponentMix,
+    pub plasma: ComponentMix,
+    pub gpu: ComponentMix,
 }
 
-fn setup_black_hole(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-) {
-    let sphere_mesh = meshes.add(Sphere::new(1.0));
-    let material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.0, 0.0, 0.0), // Black
-        emissive: Color::srgb(1.0, 1.0, 1.0).into(), // White rim glow
-        ..default()
-    });
-
-    commands.spawn((
-        BlackHole {
-            stored_mass: 0,
-            velocity: Vec3::ZERO,
-        },
-        PbrBundle {
-            mesh: sphere_mesh,
-            material,
-            transform: Transform::from_translation(Vec3::ZERO),
-            ..default()
-        },
-    ));
+impl Default for InitialConditions {
+    fn default() -> Self {
+        Self {
+            model: GalaxyModel::default(),
+            // Old disk population with a bulge and a sprinkling of halo stars
+            stars: ComponentMix { disk: 0.8, bulge: 0.15, halo: 0.05, arm_fraction: 0.0 },
+            // Plasma traces the arms
+            plasma: ComponentMix { disk: 1.0, bulge: 0.0, halo: 0.0, arm_fraction: 1.0 },
+            gpu: ComponentMix { disk: 0.95, bulge: 0.05, halo: 0.0, arm_fraction: 1.0 },
+        }
+    }
 }
 
-const PHI: f32 = 1.618033988749;
-
-pub fn rotate_black_hole(
-    mut query: Query<&mut Transform, With<BlackHole>>,
-    time: Res<Time>,
+fn setup_galaxy(
+    mut commands: Commands,
+    black_hole_query: Query<(Entity, &Transform, &BlackHole)>,
+    initial_conditions: Res<InitialConditions>,
+    import_config: Res<ImportConfig>,
 ) {
-    let dt = time.delta_seconds();
-    for mut transform in query.iter_mut() {
-        transform.rotate_y(PHI * dt * 0.1);
+    // Imported stars stand in for the procedural disk
+    if import_config.replaces(EmitterTarget::Stars) {
+        return;
+    }
+    let galaxy_count = black_hole_query.iter().count().max(1);
+    let mut rng = rand::thread_rng();
+
+    for (black_hole_entity, black_hole_transform, black_hole) in black_hole_query.iter() {
+        // The disk lies in the black hole's equatorial plane
+        let frame = disk_frame(*black_hole_transform.up());
+
+        // Spawn stars from the galaxy model (Toomre-stable disk, bulge, halo)
+        for i in 0..10000 / galaxy_count {
+            let body = initial_conditions.model.sample(i, &initial_conditions.stars, &mut rng);
+            commands.spawn((
+                Star { velocity: black_hole.velocity + frame * body.velocity },
+                GalaxyMember { black_hole: black_hole_entity },
+                Transform::from_translation(black_hole_transform.translation + frame * body.position),
+            ));
+        }
     }
 }
 
-fn spawn_trail(
+fn setup_black_hole(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    black_hole_query: Query<&Transform, With<BlackHole>>,
-    time: Res<Time>,
-    mut last_spawn: Local<f32>,
+    scenario: Res<Scenario>,
+    black_hole_config: Res<BlackHoleConfig>,
 ) {
-    let sphere_mesh = meshes.add(Sphere::new(0.5));
+    let sphere_mesh = meshes.add(Sphere::new(1.0));
     let material = materials.add(StandardMaterial {
-        base_color: Color::srgba(0.0, 0.0, 0.0, 0.3), // Faint black
-        emissive: Color::srgba(1.0, 1.0, 1.0, 0.1).into(),
+        base_color: Color::srgb(0.0, 0.0, 0.0), // Black
+        emissive: Color::srgb(1.0, 1.0, 1.0).into(), // White rim glow
         ..default()
     });
 
-    let black_hole_pos = black_hole_query.single().translation;
-
-    // Spawn trail point every 0.1 seconds
-    if time.elapsed_seconds() - *last_spawn > 0.1 {
-        *last_spawn = time.elapsed_seconds();
+    for galaxy in scenario.galaxies() {
         commands.spawn((
-            TrailPoint {
-                timer: Timer::from_seconds(5.0, TimerMode::Once),
+            BlackHole {
+                stored_mass: 0,
+                velocity: galaxy.velocity,
+                spin: galaxy.spin,
+                // Start fed at the reference rate so the first jets fire at full strength
+                accretion_rate: black_hole_config.reference_accretion_rate,
             },
             PbrBundle {
-                mesh: sphere_mesh,
-                material,
-                transform: Transform::from_translation(black_hole_pos),
+                mesh: sphere_mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_translation(galaxy.center)
+                    .with_rotation(disk_frame(galaxy.spin_axis)),
                 ..default()
             },
+            PathTrail::new(LinearRgba::rgb(0.8, 0.8, 0.8)), // Faint white
         ));
     }
 }
 
-fn update_trail(
-    mut commands: Commands,
-    mut query: Query<(Entity, &mut TrailPoint)>,
+const PHI: f32 = 1.618033988749;
+
+pub fn rotate_black_hole(
+    mut query: Query<&mut Transform, With<BlackHole>>,
     time: Res<Time>,
 ) {
-    for (entity, mut trail) in query.iter_mut() {
-        trail.timer.tick(time.delta());
-        if trail.timer.finished() {
-            commands.entity(entity).despawn();
-        }
+    let dt = time.delta_seconds();
+    for mut transform in query.iter_mut() {
+        // Spin about the black hole's own axis (tilted cores keep their tilt)
+        transform.rotate_local_y(PHI * dt * 0.1);
     }
 }
 
-fn update_stars(
+pub fn update_stars(
+    mut commands: Commands,
     mut param_set: ParamSet<(
-        Query<(&mut Star, &mut Transform)>,
-        Query<&Transform, With<BlackHole>>,
+        Query<(Entity, &mut Star, &mut Transform, Option<&mut ForceProbe>)>,
+        Query<(Entity, &Transform, &BlackHole)>,
     )>,
     time: Res<Time>,
+    black_hole_config: Res<BlackHoleConfig>,
+    mut ledger: ResMut<AccretionLedger>,
 ) {
-    let black_hole_pos = param_set.p1().single().translation;
+    // Every core pulls on every star; heavier cores pull harder
+    let cores = gather_cores(&black_hole_config, param_set.p1().iter());
+
     // The Drag Constant (Phi^-4)
     const PHI_INV_4: f32 = 0.1464466094067262;
     let cos_mu = PHI_INV_4.cos();
     let sin_mu = PHI_INV_4.sin();
 
-    // The "Black Hole" Suction Strength (Gravity)
-    let suction_strength = 5.0;
-
-    for (mut star, mut transform) in param_set.p0().iter_mut() {
+    for (entity, mut star, mut transform, probe) in param_set.p0().iter_mut() {
         let dt = time.delta_seconds();
         let pos = transform.translation;
 
+        // ACCRETION: inside an event horizon the star is swallowed
+        if let Some(core) = cores
+            .iter()
+            .find(|core| pos.distance_squared(core.position) < core.event_horizon * core.event_horizon)
+        {
+            ledger.capture(core, pos - core.position, star.velocity - core.velocity);
+            commands.entity(entity).despawn();
+            continue;
+        }
+
         // 1. CALCULATE SUCTION (Gravity)
-        // Vector pointing to black hole
-        let to_center = black_hole_pos - pos;
-        let dist_sq = to_center.length_squared();
-
-        // Normalize and scale by inverse square (or just 1/r for fluid flow)
-        // In QQM, suction is high near the drain.
-        if dist_sq > 0.1 {
-            let suction_vector = to_center.normalize() * (suction_strength / pos.length().max(1.0));
-            star.velocity += suction_vector * dt;
+        let velocity_before = star.velocity;
+        for core in cores.iter() {
+            // Vector pointing to black hole
+            let to_center = core.position - pos;
+            let dist_sq = to_center.length_squared();
+
+            // Normalize and scale by inverse square (or just 1/r for fluid flow)
+            // In QQM, suction is high near the drain.
+            if dist_sq > 0.1 {
+                let suction_vector = to_center.normalize() * (core.suction / dist_sq.sqrt().max(1.0));
+                star.velocity += suction_vector * dt;
+            }
         }
 
         // 2. APPLY GOLDEN DRAG (The Spin)
@@ -177,9 +193,18 @@ fn update_stars(
         let new_vx = star.velocity.x * cos_mu - star.velocity.y * sin_mu;
         let new_vy = star.velocity.x * sin_mu + star.velocity.y * cos_mu;
 
+        let velocity_before_drag = star.velocity;
         star.velocity.x = new_vx;
         star.velocity.y = new_vy;
 
+        // Only the inspected star carries a probe
+        if let Some(mut probe) = probe {
+            probe.record(time.elapsed_seconds(), &[
+                ("gravity", (velocity_before_drag - velocity_before) / dt),
+                ("drag", (star.velocity - velocity_before_drag) / dt),
+            ]);
+        }
+
         // 3. UPDATE POSITION
         transform.translation += star.velocity * dt;
     }
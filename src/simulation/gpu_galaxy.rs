@@ -1,11 +1,26 @@
 use bevy::prelude::*;
 use bevy::render::render_resource::*;
 use bevy::render::renderer::{RenderDevice, RenderQueue};
-use rand::Rng;
-
-const NUM_PARTICLES: usize = 1_000_000;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+use crate::viz::colormap::{Colormap, STOPS};
+use crate::simulation::black_hole::{gather_cores, BlackHoleConfig, CoreState};
+use crate::simulation::color_map::{ColorField, ColorMapConfig, ColorMapRange};
+use crate::simulation::galaxy::{BlackHole, InitialConditions};
+use crate::simulation::jet::JetModel;
+
+pub const NUM_PARTICLES: usize = 1_000_000;
 const WORKGROUP_SIZE: u32 = 64;
+// Subset of the GPU population mirrored back to the CPU (entities, spatial index)
+pub const VISIBLE_PARTICLES: usize = 10_000;
+const READBACK_INTERVAL: u32 = 10; // Frames between read-backs
+// Where the staging buffer is in its map cycle
+const READBACK_IDLE: u8 = 0;
+const READBACK_MAPPING: u8 = 1;
+const READBACK_MAPPED: u8 = 2;
+const READBACK_FAILED: u8 = 3;
 const PHI: f32 = 1.6180339887498948482;
+const GPU_CORES: usize = 4; // Heaviest cores the GPU colors feel (the merger scenario has two)
 
 #[derive(ShaderType, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 #[repr(C)]
@@ -23,6 +38,82 @@ pub struct GalaxyUniforms {
     pub pinch_strength: f32,
     pub phi_value: f32,
     pub arms: f32,
+    // Quasar jet (see JetModel); the GPU galaxy sits at the origin spinning around +Y
+    pub jet_axis_x: f32,
+    pub jet_axis_y: f32,
+    pub jet_axis_z: f32,
+    pub jet_speed: f32,
+    pub jet_launch_radius: f32,
+    pub jet_opening_angle: f32,
+    pub jet_sheath_speed: f32,
+    pub jet_deceleration_length: f32,
+    pub jet_reentry_speed: f32,
+    pub jet_reentry_distance: f32,
+    pub jet_reentry_radius_min: f32,
+    pub jet_reentry_radius_max: f32,
+    // Color map (see ColorMapConfig); field 0 keeps the native speed colors
+    pub color_field: f32,
+    pub color_min: f32,
+    pub color_max: f32,
+    pub colormap: [Vec4; STOPS], // Linear RGB stops
+    // Cores for the energy colors, as on the CPU
+    pub cores: [Vec4; GPU_CORES],           // xyz position, w suction
+    pub core_velocities: [Vec4; GPU_CORES], // xyz velocity
+    pub core_count: f32,
+    pub _padding: [f32; 3], // The WGSL struct rounds up to 16 bytes
+}
+
+impl GalaxyUniforms {
+    fn new(time: f32, dt: f32, phi_value: f32, arms: u32, jet_model: &JetModel, jet_speed: f32) -> Self {
+        let jet_axis = jet_model.axis(Vec3::Y, time);
+        Self {
+            time,
+            dt,
+            pinch_strength: 0.1,
+            phi_value,
+            arms: arms as f32,
+            jet_axis_x: jet_axis.x,
+            jet_axis_y: jet_axis.y,
+            jet_axis_z: jet_axis.z,
+            jet_speed,
+            jet_launch_radius: jet_model.launch_radius,
+            jet_opening_angle: jet_model.opening_angle,
+            jet_sheath_speed: jet_model.sheath_speed,
+            jet_deceleration_length: jet_model.deceleration_length,
+            jet_reentry_speed: jet_model.reentry_speed,
+            jet_reentry_distance: jet_model.reentry_distance,
+            jet_reentry_radius_min: jet_model.reentry_radius_min,
+            jet_reentry_radius_max: jet_model.reentry_radius_max,
+            color_field: 0.0,
+            color_min: 0.0,
+            color_max: 1.0,
+            colormap: [Vec4::ZERO; STOPS],
+            cores: [Vec4::ZERO; GPU_CORES],
+            core_velocities: [Vec4::ZERO; GPU_CORES],
+            core_count: 0.0,
+            _padding: [0.0; 3],
+        }
+    }
+
+    fn with_color_map(mut self, field: ColorField, colormap: Colormap, range: &ColorMapRange) -> Self {
+        self.color_field = field.gpu_id();
+        self.color_min = range.min;
+        self.color_max = range.max;
+        self.colormap = colormap.stops_linear().map(|[r, g, b]| Vec4::new(r, g, b, 1.0));
+        self
+    }
+
+    fn with_cores(mut self, cores: &[CoreState]) -> Self {
+        let mut heaviest: Vec<&CoreState> = cores.iter().collect();
+        heaviest.sort_by(|a, b| b.mass.total_cmp(&a.mass));
+        heaviest.truncate(GPU_CORES);
+        for (i, core) in heaviest.iter().enumerate() {
+            self.cores[i] = core.position.extend(core.suction);
+            self.core_velocities[i] = core.velocity.extend(0.0);
+        }
+        self.core_count = heaviest.len() as f32;
+        self
+    }
 }
 
 #[derive(Resource)]
@@ -33,6 +124,15 @@ pub struct GpuGalaxyResources {
     pub bind_group: BindGroup,
 }
 
+// CPU copy of the first VISIBLE_PARTICLES of the GPU buffer.
+// Particles are initialised independently, so a contiguous prefix is an unbiased sample.
+#[derive(Resource)]
+pub struct GpuReadback {
+    pub particles: Vec<Particle>,
+    pub staging_buffer: Buffer,
+    state: Arc<AtomicU8>, // Set by the map callback, which runs whenever the device is next polled
+}
+
 #[derive(Component)]
 pub struct GpuParticle {
     pub entity_index: usize,
@@ -48,14 +148,21 @@ pub struct GpuGalaxyPlugin;
 impl Plugin for GpuGalaxyPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(PhiResource { phi_value: 1.618034 })
-            .add_systems(Startup, (setup_gpu_galaxy, spawn_gpu_particles))
-            .add_systems(Update, (update_phi_input, update_gpu_galaxy, update_particle_transforms));
+            // Headless runs have no render device: Φ still drives the CPU plasma
+            .add_systems(Startup, (setup_gpu_galaxy, spawn_gpu_particles).run_if(resource_exists::<RenderDevice>))
+            .add_systems(Update, update_phi_input)
+            .add_systems(Update, (
+                update_gpu_galaxy,
+                update_particle_transforms.after(update_gpu_galaxy),
+            ).run_if(resource_exists::<RenderDevice>));
     }
 }
 
 fn setup_gpu_galaxy(
     mut commands: Commands,
     render_device: Res<RenderDevice>,
+    black_hole_config: Res<BlackHoleConfig>,
+    initial_conditions: Res<InitialConditions>,
 ) {
     // Create particle buffer with initial spiral data
     let mut particles = Vec::with_capacity(NUM_PARTICLES);
@@ -64,46 +171,10 @@ fn setup_gpu_galaxy(
     let mut rng = rand::thread_rng();
 
     for i in 0..NUM_PARTICLES {
-        // 1. DISTRIBUTE RADIUS
-        // Use square root for even disk distribution, then spread out
-        let r: f32 = rng.gen_range(2.0..60.0);
-
-        // 2. CALCULATE PERFECT GOLDEN SPIRAL POSITION
-        let phi = 1.618034;
-        let b = 0.3; // Tightness of the spiral
-
-        // The fundamental spiral equation: Angle = ln(r) * phi
-        let base_theta = r.ln() * phi;
-
-        // 3. CREATE 2 ARMS (The Propeller)
-        // Even particles go to Arm A (0 rad), Odd particles go to Arm B (PI rad)
-        let arm_offset = if i % 2 == 0 { 0.0 } else { std::f32::consts::PI };
-
-        // 4. ADD "FUZZ" (Thickness)
-        // A galaxy isn't a thin line; it's a thick stream.
-        let fuzz = (rng.r#gen::<f32>() - 0.5) * 0.8;
-
-        let theta = base_theta + arm_offset + fuzz;
-        let y = rng.gen_range(-1.5..1.5); // Disk thickness
-
-        let pos = Vec4::new(
-            r * theta.cos(),
-            y,
-            r * theta.sin(),
-            1.0 // Life/Padding
-        );
-
-        // 5. ORBITAL VELOCITY
-        // Give them perfect tangent velocity so they are stable at T=0
-        let tangent = Vec3::new(-pos.z, 0.0, pos.x).normalize();
-        let speed = 15.0; // Adjust speed to match your pinch strength
-
-        let vel = Vec4::new(
-            tangent.x * speed,
-            0.0,
-            tangent.z * speed,
-            0.0
-        );
+        // Pre-formed spiral: the galaxy model with its disk on the arms
+        let body = initial_conditions.model.sample(i, &initial_conditions.gpu, &mut rng);
+        let pos = body.position.extend(1.0); // Life/Padding
+        let vel = body.velocity.extend(0.0); // w = 0: disk material (1 = jet)
 
         particles.push(Particle { pos, vel, color: Vec4::new(1.0, 1.0, 1.0, 1.0) });
     }
@@ -111,17 +182,20 @@ fn setup_gpu_galaxy(
     let particle_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
         label: Some("Particle Buffer"),
         contents: bytemuck::cast_slice(&particles),
-        usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        usage: BufferUsages::STORAGE | BufferUsages::COPY_DST | BufferUsages::COPY_SRC,
+    });
+
+    let staging_buffer = render_device.create_buffer(&BufferDescriptor {
+        label: Some("Particle Readback Buffer"),
+        size: (VISIBLE_PARTICLES * std::mem::size_of::<Particle>()) as u64,
+        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
     });
 
     // Create uniform buffer
-    let uniforms = GalaxyUniforms {
-        time: 0.0,
-        dt: 0.016, // ~60 FPS
-        pinch_strength: 0.1,
-        phi_value: 1.618034,
-        arms: 2.0,
-    };
+    let jet_model = JetModel::default();
+    let jet_speed = black_hole_config.max_jet_speed * 0.7; // Classic a* = 0.7 until the cores report in
+    let uniforms = GalaxyUniforms::new(0.0, 0.016, 1.618034, initial_conditions.model.arms, &jet_model, jet_speed); // ~60 FPS
 
     let uniform_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
         label: Some("Galaxy Uniforms"),
@@ -198,23 +272,46 @@ fn setup_gpu_galaxy(
         compute_pipeline,
         bind_group,
     });
+    commands.insert_resource(GpuReadback {
+        particles: Vec::with_capacity(VISIBLE_PARTICLES),
+        staging_buffer,
+        state: Arc::new(AtomicU8::new(READBACK_IDLE)),
+    });
 }
 
+#[allow(clippy::too_many_arguments)]
 fn update_gpu_galaxy(
     time: Res<Time>,
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
     mut resources: ResMut<GpuGalaxyResources>,
     phi_resource: Res<PhiResource>,
+    jet_model: Res<JetModel>,
+    black_hole_config: Res<BlackHoleConfig>,
+    black_hole_query: Query<(Entity, &Transform, &BlackHole)>,
+    initial_conditions: Res<InitialConditions>,
+    color_map: Res<ColorMapConfig>,
+    color_range: Res<ColorMapRange>,
 ) {
+    // The heaviest core powers the GPU galaxy's jet
+    let jet_speed = black_hole_query
+        .iter()
+        .map(|(_, _, black_hole)| black_hole)
+        .max_by(|a, b| a.stored_mass.cmp(&b.stored_mass))
+        .map_or(0.0, |black_hole| black_hole_config.jet_speed(black_hole));
+    let cores = gather_cores(&black_hole_config, black_hole_query.iter());
+
     // Update uniforms
-    let uniforms = GalaxyUniforms {
-        time: time.elapsed_seconds(),
-        dt: time.delta_seconds(),
-        pinch_strength: 0.1,
-        phi_value: phi_resource.phi_value,
-        arms: 2.0,
-    };
+    let uniforms = GalaxyUniforms::new(
+        time.elapsed_seconds(),
+        time.delta_seconds(),
+        phi_resource.phi_value,
+        initial_conditions.model.arms,
+        &jet_model,
+        jet_speed,
+    )
+    .with_color_map(color_map.field, color_map.colormap, &color_range)
+    .with_cores(&cores);
 
     render_queue.write_buffer(&resources.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
 
@@ -239,11 +336,7 @@ fn update_gpu_galaxy(
 
 fn spawn_gpu_particles(mut commands: Commands) {
     // Spawn a subset of particles for rendering (10,000 out of 1,000,000)
-    const VISIBLE_PARTICLES: usize = 10_000;
-    let step = NUM_PARTICLES / VISIBLE_PARTICLES;
-
-    for i in 0..VISIBLE_PARTICLES {
-        let particle_index = i * step;
+    for particle_index in 0..VISIBLE_PARTICLES {
         commands.spawn((
             GpuParticle { entity_index: particle_index },
             Transform::default(),
@@ -254,34 +347,74 @@ fn spawn_gpu_particles(mut commands: Commands) {
 fn update_particle_transforms(
     mut query: Query<(&GpuParticle, &mut Transform)>,
     resources: Res<GpuGalaxyResources>,
+    mut readback: ResMut<GpuReadback>,
     render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut frame: Local<u32>,
 ) {
-    // This is a simplified approach - in practice, you'd want to read back
-    // the buffer data and update transforms
-    // For now, we'll just demonstrate the concept
+    // Reading back 1M particles every frame would be very slow,
+    // so we copy the visible prefix every few frames only.
+    *frame += 1;
+    let readback = &mut *readback;
+
+    // 1. COPY the prefix into the staging buffer and ask for it to be mapped,
+    // without waiting for the GPU
+    match readback.state.load(Ordering::Acquire) {
+        READBACK_IDLE if frame.is_multiple_of(READBACK_INTERVAL) => {
+            let size = (VISIBLE_PARTICLES * std::mem::size_of::<Particle>()) as u64;
+            let mut command_encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+                label: Some("Galaxy Readback Encoder"),
+            });
+            command_encoder.copy_buffer_to_buffer(&resources.particle_buffer, 0, &readback.staging_buffer, 0, size);
+            render_queue.submit([command_encoder.finish()]);
+
+            readback.state.store(READBACK_MAPPING, Ordering::Release);
+            let state = readback.state.clone();
+            render_device.map_buffer(&readback.staging_buffer.slice(..), MapMode::Read, move |result| {
+                state.store(if result.is_ok() { READBACK_MAPPED } else { READBACK_FAILED }, Ordering::Release);
+            });
+            return;
+        }
+        READBACK_MAPPING => {
+            render_device.poll(Maintain::Poll);
+            return;
+        }
+        READBACK_MAPPED => {}
+        READBACK_FAILED => {
+            readback.state.store(READBACK_IDLE, Ordering::Release);
+            return;
+        }
+        _ => return,
+    }
 
-    // Note: Reading back 1M particles every frame would be very slow
-    // A proper implementation would use GPU-only rendering
+    // 2. COPY OUT once the map has completed
+    {
+        let data = readback.staging_buffer.slice(..).get_mapped_range();
+        readback.particles.clear();
+        readback.particles.extend_from_slice(bytemuck::cast_slice(&data));
+    }
+    readback.staging_buffer.unmap();
+    readback.state.store(READBACK_IDLE, Ordering::Release);
+
+    // 3. MIRROR into the entity transforms
+    for (gpu_particle, mut transform) in query.iter_mut() {
+        if let Some(particle) = readback.particles.get(gpu_particle.entity_index) {
+            transform.translation = particle.pos.truncate();
+        }
+    }
 }
 
 fn update_phi_input(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut phi_resource: ResMut<PhiResource>,
 ) {
-    let mut changed = false;
     if keyboard_input.just_pressed(KeyCode::ArrowRight) {
         phi_resource.phi_value += 0.01;
-        changed = true;
     }
     if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
         phi_resource.phi_value -= 0.01;
-        changed = true;
     }
     if keyboard_input.just_pressed(KeyCode::Space) {
         phi_resource.phi_value = 1.618034;
-        changed = true;
-    }
-    if changed {
-        println!("Phi value: {:.6}", phi_resource.phi_value);
     }
 }
\ No newline at end of file
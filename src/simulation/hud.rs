@@ -0,0 +1,317 @@
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::ecs::system::{Adapt, AdapterSystem, SystemParam};
+use bevy::prelude::*;
+use bevy::utils::Instant;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use crate::simulation::black_hole::{gather_cores, BlackHoleConfig};
+use crate::simulation::camera::CameraMode;
+use crate::simulation::color_map::{arm_coherence, ColorMapConfig};
+use crate::simulation::debug_viz::FlowVizConfig;
+use crate::simulation::escape::BindingCensus;
+use crate::simulation::ether_volume::EtherVolumeConfig;
+use crate::simulation::galaxy::{BlackHole, Star};
+use crate::simulation::gpu_galaxy::{GpuGalaxyResources, PhiResource, NUM_PARTICLES};
+use crate::simulation::jet::JetBorne;
+use crate::simulation::plasma::{resonance, PlasmaParticle, PATTERN_SPEED};
+use crate::simulation::sph::{SphConfig, SphGas};
+
+const TIMING_SMOOTHING: f32 = 0.1; // Blend of each new frame into the displayed system timings
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+        app.init_resource::<HudConfig>()
+            .init_resource::<SystemTimings>()
+            .add_systems(Startup, setup_hud)
+            .add_systems(Update, toggle_hud)
+            .add_systems(Last, (collect_system_timings, update_hud).chain());
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HudItem {
+    Phi,
+    Resonance,
+    Counts,
+    Time,
+    Fps,
+    Timings,
+    Escape,
+    Coherence,
+    Modes,
+}
+
+impl HudItem {
+    pub const ALL: [HudItem; 9] = [
+        HudItem::Phi,
+        HudItem::Resonance,
+        HudItem::Counts,
+        HudItem::Time,
+        HudItem::Fps,
+        HudItem::Timings,
+        HudItem::Escape,
+        HudItem::Coherence,
+        HudItem::Modes,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HudItem::Phi => "phi",
+            HudItem::Resonance => "resonance",
+            HudItem::Counts => "counts",
+            HudItem::Time => "time",
+            HudItem::Fps => "fps",
+            HudItem::Timings => "timings",
+            HudItem::Escape => "escape",
+            HudItem::Coherence => "coherence",
+            HudItem::Modes => "modes",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|item| item.name() == name)
+    }
+}
+
+// H shows or hides the HUD.
+// Picked on the command line: `cargo run -- --hud-items phi,resonance,coherence --hud-font-size 18 --hud-interval 0.5`, `--no-hud`
+#[derive(Resource)]
+pub struct HudConfig {
+    pub visible: bool,
+    pub items: Vec<HudItem>, // Shown in this order
+    pub font_size: f32,
+    pub interval: f32, // Seconds between refreshes
+}
+
+impl Default for HudConfig {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            items: HudItem::ALL.to_vec(),
+            font_size: 14.0,
+            interval: 0.25,
+        }
+    }
+}
+
+impl HudConfig {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        let mut config = Self {
+            visible: !args.iter().any(|arg| arg == "--no-hud"),
+            ..Self::default()
+        };
+        if let Some(items) = value("--hud-items") {
+            config.items = items
+                .split(',')
+                .filter_map(|name| HudItem::parse(name).or_else(|| {
+                    eprintln!("Unknown HUD item {name}");
+                    None
+                }))
+                .collect();
+        }
+        if let Some(size) = value("--hud-font-size").and_then(|v| v.parse::<f32>().ok()) {
+            config.font_size = size.max(4.0);
+        }
+        if let Some(interval) = value("--hud-interval").and_then(|v| v.parse::<f32>().ok()) {
+            config.interval = interval.max(0.0);
+        }
+        config
+    }
+}
+
+// Wall time of every run of a `timed` system, drained once per frame
+static SYSTEM_TIMES: Mutex<Vec<(&'static str, Duration)>> = Mutex::new(Vec::new());
+
+pub struct Timed(&'static str);
+
+impl<S: System> Adapt<S> for Timed {
+    type In = S::In;
+    type Out = S::Out;
+
+    fn adapt(&mut self, input: Self::In, run_system: impl FnOnce(S::In) -> S::Out) -> Self::Out {
+        let start = Instant::now();
+        let output = run_system(input);
+        if let Ok(mut times) = SYSTEM_TIMES.lock() {
+            times.push((self.0, start.elapsed()));
+        }
+        output
+    }
+}
+
+// Wraps a system so its run time shows in the HUD under `name`.
+// The wrapper keeps the system's own set, so `.before(system)` and `.after(system)` still apply.
+pub fn timed<M>(name: &'static str, system: impl IntoSystem<(), (), M>) -> AdapterSystem<Timed, impl System<In = (), Out = ()>> {
+    let system = IntoSystem::into_system(system);
+    let system_name = system.name();
+    AdapterSystem::new(Timed(name), system, system_name)
+}
+
+// Smoothed milliseconds per frame of each timed system
+#[derive(Resource, Default)]
+pub struct SystemTimings {
+    pub ms: BTreeMap<&'static str, f32>,
+}
+
+fn collect_system_timings(mut timings: ResMut<SystemTimings>) {
+    let Ok(mut times) = SYSTEM_TIMES.lock() else {
+        return;
+    };
+    let mut frame: BTreeMap<&'static str, f32> = timings.ms.keys().map(|name| (*name, 0.0)).collect();
+    for (name, duration) in times.drain(..) {
+        *frame.entry(name).or_default() += duration.as_secs_f32() * 1000.0;
+    }
+    for (name, ms) in frame {
+        let smoothed = timings.ms.entry(name).or_insert(ms);
+        *smoothed += (ms - *smoothed) * TIMING_SMOOTHING;
+    }
+}
+
+#[derive(Component)]
+struct HudText;
+
+fn setup_hud(mut commands: Commands, config: Res<HudConfig>) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+            ..default()
+        })
+        .with_children(|panel| {
+            panel.spawn((
+                TextBundle::from_section("", TextStyle { font_size: config.font_size, ..default() }),
+                HudText,
+            ));
+        });
+}
+
+fn toggle_hud(keys: Res<ButtonInput<KeyCode>>, mut config: ResMut<HudConfig>) {
+    if keys.just_pressed(KeyCode::KeyH) {
+        config.visible = !config.visible;
+    }
+}
+
+type PlasmaStats<'a> = (&'a Transform, &'a PlasmaParticle, Has<JetBorne>, Has<SphGas>);
+// The toggled settings listed under `modes`
+#[derive(SystemParam)]
+struct HudModes<'w> {
+    camera: Res<'w, CameraMode>,
+    sph: Res<'w, SphConfig>,
+    color_map: Res<'w, ColorMapConfig>,
+    ether: Res<'w, EtherVolumeConfig>,
+    flow: Res<'w, FlowVizConfig>,
+}
+
+type HudPanels<'w, 's> = Query<'w, 's, (&'static Parent, &'static mut Text), With<HudText>>;
+
+#[allow(clippy::too_many_arguments)]
+fn update_hud(
+    time: Res<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
+    config: Res<HudConfig>,
+    phi: Res<PhiResource>,
+    diagnostics: Res<DiagnosticsStore>,
+    timings: Res<SystemTimings>,
+    census: Res<BindingCensus>,
+    modes: HudModes,
+    gpu: Option<Res<GpuGalaxyResources>>,
+    black_hole_config: Res<BlackHoleConfig>,
+    black_holes: Query<(Entity, &Transform, &BlackHole)>,
+    stars: Query<&Transform, (With<Star>, Without<PlasmaParticle>)>,
+    plasma: Query<PlasmaStats, Without<Star>>,
+    mut texts: HudPanels,
+    mut panels: Query<&mut Visibility, Without<HudText>>,
+    mut since_refresh: Local<f32>,
+) {
+    for (parent, _) in texts.iter() {
+        if let Ok(mut visibility) = panels.get_mut(parent.get()) {
+            *visibility = if config.visible && !config.items.is_empty() { Visibility::Inherited } else { Visibility::Hidden };
+        }
+    }
+    *since_refresh += real_time.delta_seconds();
+    if !config.visible || (*since_refresh < config.interval && !config.is_changed()) {
+        return;
+    }
+    *since_refresh = 0.0;
+
+    let cores = gather_cores(&black_hole_config, black_holes.iter());
+    let pattern_angle = time.elapsed_seconds() * PATTERN_SPEED;
+    let mut lines = Vec::new();
+    for item in config.items.iter() {
+        match item {
+            HudItem::Phi => lines.push(format!("φ           {:.4}", phi.phi_value)),
+            HudItem::Resonance => lines.push(format!("Resonance   {:.1}%", resonance(phi.phi_value) * 100.0)),
+            HudItem::Counts => {
+                let (mut jet, mut gas) = (0, 0);
+                for (_, _, jet_borne, sph) in plasma.iter() {
+                    jet += jet_borne as usize;
+                    gas += sph as usize;
+                }
+                lines.push(format!("Stars       {}", stars.iter().len()));
+                lines.push(format!("Plasma      {} ({gas} gas, {jet} in jets)", plasma.iter().len()));
+                lines.push(format!("GPU         {}", gpu.as_ref().map_or(0, |_| NUM_PARTICLES)));
+                lines.push(format!("Black holes {}", cores.len()));
+            }
+            HudItem::Time => {
+                lines.push(format!("Sim time    {:.1} s", time.elapsed_seconds()));
+                lines.push(format!("Time scale  {:.2}×{}", time.relative_speed(), if time.is_paused() { " (paused)" } else { "" }));
+            }
+            HudItem::Fps => {
+                let fps = diagnostics.get(&FrameTimeDiagnosticsPlugin::FPS).and_then(|fps| fps.smoothed());
+                lines.push(fps.map_or_else(|| "FPS         -".to_string(), |fps| format!("FPS         {fps:.0}")));
+            }
+            HudItem::Timings => {
+                let mut slowest: Vec<_> = timings.ms.iter().collect();
+                slowest.sort_by(|a, b| b.1.total_cmp(a.1));
+                lines.extend(slowest.into_iter().map(|(name, ms)| format!("  {name:<14}{ms:>6.2} ms")));
+            }
+            HudItem::Escape => {
+                lines.push(format!(
+                    "Bound       {:.1}% ({:.1}% escaping, {:.1}% in jets)",
+                    census.fraction(census.bound) * 100.0,
+                    census.fraction(census.escaping) * 100.0,
+                    census.fraction(census.jet_borne) * 100.0
+                ));
+                lines.push(format!("Boundary    {} recycled, {} despawned", census.recycled, census.despawned));
+            }
+            HudItem::Coherence => {
+                let arms = plasma
+                    .iter()
+                    .filter(|(_, _, jet_borne, _)| !jet_borne)
+                    .map(|(transform, particle, ..)| (transform.translation, particle.arms));
+                let coherence = arm_coherence(&cores, arms, phi.phi_value, pattern_angle).unwrap_or(0.0);
+                lines.push(format!("Coherence   {coherence:.3}"));
+            }
+            HudItem::Modes => {
+                lines.push(format!("Camera      {}", modes.camera.name()));
+                lines.push(format!("SPH kernel  {}", modes.sph.kernel.name()));
+                lines.push(format!("Color by    {} ({})", modes.color_map.field.name(), modes.color_map.colormap.name()));
+                lines.push(format!("Ether view  {} ({})", modes.ether.view.name(), modes.ether.channel.name()));
+                lines.push(format!("Flow source {}", modes.flow.source.name()));
+            }
+        }
+    }
+
+    for (_, mut text) in texts.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+        text.sections[0].style.font_size = config.font_size;
+    }
+}
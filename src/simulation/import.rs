@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+use bevy::render::renderer::RenderQueue;
+use std::f32::consts::TAU;
+use std::path::PathBuf;
+use crate::io::gadget::read_snapshot;
+use crate::io::table::{parse_table, TableColumns};
+use crate::io::{ImportError, ImportedParticle, UnitConversion};
+use crate::simulation::black_hole::{gather_cores, nearest_core, BlackHoleConfig};
+use crate::simulation::emitter::{spawn_cpu_particle, write_gpu_particles, EmitterTarget, NewParticle};
+use crate::simulation::galaxy::{BlackHole, GalaxyMember, InitialConditions};
+use crate::simulation::gpu_galaxy::GpuGalaxyResources;
+
+pub struct ImportPlugin;
+
+impl Plugin for ImportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ImportConfig>()
+            .add_systems(Update, import_particles);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportFormat {
+    Gadget, // GADGET-2 snapshot binary
+    Table,  // CSV / ASCII x y z vx vy vz mass
+}
+
+impl ImportFormat {
+    fn from_path(path: &std::path::Path) -> Self {
+        let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+        match extension.to_ascii_lowercase().as_str() {
+            "csv" | "txt" | "dat" | "ascii" | "tsv" => ImportFormat::Table,
+            _ => ImportFormat::Gadget,
+        }
+    }
+}
+
+pub struct ImportSource {
+    pub path: PathBuf,
+    pub format: ImportFormat,
+    // None: GADGET gas becomes SPH plasma and every other type becomes stars; tables become stars
+    pub target: Option<EmitterTarget>,
+    pub units: UnitConversion,
+    pub columns: TableColumns,
+    pub cosmological: bool, // GADGET velocities are u = v/√a
+}
+
+impl ImportSource {
+    fn load(&self) -> Result<Vec<ImportedParticle>, ImportError> {
+        let particles = match self.format {
+            ImportFormat::Gadget => read_snapshot(&self.path, self.cosmological)?.particles,
+            ImportFormat::Table => parse_table(&std::fs::read_to_string(&self.path)?, &self.columns)?,
+        };
+        Ok(particles.into_iter().map(|particle| self.units.apply(particle)).collect())
+    }
+
+    fn target_for(&self, particle: &ImportedParticle) -> EmitterTarget {
+        self.target.unwrap_or(match (self.format, particle.kind) {
+            (ImportFormat::Gadget, 0) => EmitterTarget::Plasma,
+            _ => EmitterTarget::Stars,
+        })
+    }
+
+}
+
+// A source that loaded, with its particles waiting to be spawned
+pub struct LoadedImport {
+    pub source: ImportSource,
+    pub particles: Vec<ImportedParticle>,
+}
+
+// Files to load as starting states.
+// Picked on the command line:
+// `cargo run -- --import snapshot_010 --length-scale 10 --velocity-scale 0.1`
+// Files are read up front so the procedural spawners know which populations were actually filled;
+// a source that fails to load leaves its populations procedural.
+#[derive(Resource)]
+pub struct ImportConfig {
+    pub imports: Vec<LoadedImport>, // Emptied once spawned
+    // Imported stars / plasma stand in for the procedural ones instead of adding to them.
+    // (GPU imports overwrite the start of the buffer; the rest stays procedural.)
+    pub replace_procedural: bool,
+    filled: Vec<EmitterTarget>, // Populations the loaded files put particles into
+}
+
+impl Default for ImportConfig {
+    fn default() -> Self {
+        Self { imports: Vec::new(), replace_procedural: true, filled: Vec::new() }
+    }
+}
+
+impl ImportConfig {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+        let scale = |flag: &str| value(flag).and_then(|v| v.parse::<f32>().ok()).unwrap_or(1.0);
+
+        let units = UnitConversion {
+            length: scale("--length-scale"),
+            velocity: scale("--velocity-scale"),
+            mass: scale("--mass-scale"),
+        };
+        let target = match value("--import-target").map(String::as_str) {
+            Some("stars") => Some(EmitterTarget::Stars),
+            Some("plasma") => Some(EmitterTarget::Plasma),
+            Some("gpu") => Some(EmitterTarget::Gpu),
+            _ => None,
+        };
+        let format = match value("--import-format").map(String::as_str) {
+            Some("gadget") => Some(ImportFormat::Gadget),
+            Some("csv") | Some("table") => Some(ImportFormat::Table),
+            _ => None,
+        };
+
+        let imports: Vec<LoadedImport> = args
+            .iter()
+            .enumerate()
+            .filter(|(_, arg)| *arg == "--import")
+            .filter_map(|(i, _)| args.get(i + 1))
+            .map(|path| {
+                let path = PathBuf::from(path);
+                ImportSource {
+                    format: format.unwrap_or_else(|| ImportFormat::from_path(&path)),
+                    path,
+                    target,
+                    units,
+                    columns: TableColumns::default(),
+                    cosmological: args.iter().any(|arg| arg == "--cosmological"),
+                }
+            })
+            .filter_map(|source| match source.load() {
+                Ok(particles) => Some(LoadedImport { source, particles }),
+                Err(error) => {
+                    eprintln!("Import of {} failed: {error}", source.path.display());
+                    None
+                }
+            })
+            .collect();
+
+        let mut filled = Vec::new();
+        for import in imports.iter() {
+            for particle in import.particles.iter() {
+                let target = import.source.target_for(particle);
+                if !filled.contains(&target) {
+                    filled.push(target);
+                }
+            }
+        }
+
+        Self {
+            imports,
+            replace_procedural: !args.iter().any(|arg| arg == "--keep-procedural"),
+            filled,
+        }
+    }
+
+    // True if imported data stands in for the procedural `target` population
+    pub fn replaces(&self, target: EmitterTarget) -> bool {
+        self.replace_procedural && self.filled.contains(&target)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn import_particles(
+    mut commands: Commands,
+    mut config: ResMut<ImportConfig>,
+    initial_conditions: Res<InitialConditions>,
+    black_hole_query: Query<(Entity, &Transform, &BlackHole)>,
+    black_hole_config: Res<BlackHoleConfig>,
+    gpu_resources: Option<Res<GpuGalaxyResources>>,
+    render_queue: Option<Res<RenderQueue>>, // Absent in headless runs
+) {
+    if config.imports.is_empty() { return; }
+    let imports = std::mem::take(&mut config.imports);
+
+    let cores = gather_cores(&black_hole_config, black_hole_query.iter());
+    let model = &initial_conditions.model;
+    let arms = model.arms.max(1);
+    let mut gpu_batch = Vec::new();
+
+    for LoadedImport { source, particles } in imports.iter() {
+        // Particle masses are dropped: stars and plasma all carry unit mass in this simulation
+        for particle in particles.iter() {
+            // 1. GALAXY GEOMETRY relative to the nearest core (drives the Z-Pinch target)
+            let core = nearest_core(&cores, particle.position);
+            let local = core.map_or(particle.position, |core| {
+                core.disk_frame().inverse() * (particle.position - core.position)
+            });
+            let radius = Vec2::new(local.x, local.z).length().max(1.0);
+            let phase = local.z.atan2(local.x) - model.arm_angle(0, radius);
+            let arm = (phase / (TAU / arms as f32)).round().rem_euclid(arms as f32) as u32;
+
+            let new_particle = NewParticle {
+                position: particle.position,
+                velocity: particle.velocity,
+                color: if radius < 15.0 {
+                    Color::srgb(1.0, 0.84, 0.0) // Gold Core
+                } else {
+                    Color::srgb(0.0, 1.0, 1.0) // Cyan Arms
+                },
+                original_radius: radius,
+                arm,
+                arms,
+                gas: source.format == ImportFormat::Gadget && particle.kind == 0,
+            };
+
+            // 2. SPAWN into the mapped population
+            match spawn_cpu_particle(&mut commands, source.target_for(particle), &new_particle) {
+                Some(mut entity) => {
+                    if let Some(core) = core {
+                        entity.insert(GalaxyMember { black_hole: core.entity });
+                    }
+                }
+                None => gpu_batch.push(new_particle.to_gpu()),
+            }
+        }
+    }
+
+    // 3. GPU imports go to the start of the buffer, where the read-back can see them
+    if let (Some(gpu_resources), Some(render_queue)) = (gpu_resources, render_queue) {
+        write_gpu_particles(&render_queue, &gpu_resources.particle_buffer, 0, &gpu_batch);
+    }
+}
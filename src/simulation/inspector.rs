@@ -0,0 +1,317 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::window::{CursorGrabMode, PrimaryWindow};
+use std::collections::VecDeque;
+use crate::viz::plot::{draw_line_plot, PlotSeries};
+use crate::simulation::camera::FlyCamera;
+use crate::simulation::galaxy::{BlackHole, Star};
+use crate::simulation::gpu_galaxy::{GpuParticle, GpuReadback};
+use crate::simulation::jet::JetBorne;
+use crate::simulation::paths::PathTrail;
+use crate::simulation::plasma::PlasmaParticle;
+use crate::simulation::spatial_index::{Population, SpatialIndex};
+use crate::simulation::sph::SphGas;
+use crate::simulation::trails::Trails;
+
+const PROBE_SAMPLES: usize = 240; // Four seconds at 60 Hz
+const PATH_LIFETIME: f32 = 120.0; // Seconds of the inspected particle's path kept on screen
+const PLOT_WIDTH: u32 = 240;
+const PLOT_HEIGHT: u32 = 100;
+
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Inspection>()
+            .add_systems(Startup, setup_inspector)
+            .add_systems(Update, (stamp_births, pick_particle))
+            .add_systems(PostUpdate, (update_inspector, draw_inspected_marker));
+    }
+}
+
+// Simulation time a star or plasma particle appeared
+#[derive(Component)]
+pub struct Birth {
+    pub time: f32,
+}
+
+// Attached to the inspected particle only; the physics systems fill it in
+#[derive(Component, Default)]
+pub struct ForceProbe {
+    pub samples: VecDeque<(f32, Vec<(&'static str, Vec3)>)>,
+}
+
+impl ForceProbe {
+    pub fn record(&mut self, time: f32, forces: &[(&'static str, Vec3)]) {
+        if self.samples.len() == PROBE_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((time, forces.to_vec()));
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PickedParticle {
+    pub entity: Entity,
+    pub population: Population,
+}
+
+// Left click picks the particle under the cursor (the crosshair while the cursor is grabbed)
+#[derive(Resource, Default)]
+pub struct Inspection {
+    pub target: Option<PickedParticle>,
+    pub position: Option<Vec3>, // Latest position, whatever the population
+}
+
+#[derive(Component)]
+struct InspectorPanel;
+
+#[derive(Component)]
+struct InspectorText;
+
+#[derive(Resource)]
+struct InspectorPlot(Handle<Image>);
+
+fn force_color(name: &str) -> [u8; 4] {
+    match name {
+        "pinch" => [0, 255, 255, 255],
+        "drag" => [255, 150, 0, 255],
+        "gas" => [80, 255, 80, 255],
+        "lorentz" => [255, 60, 255, 255],
+        "gravity" => [255, 230, 0, 255],
+        _ => [220, 220, 220, 255],
+    }
+}
+
+fn setup_inspector(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let plot = images.add(Image::new_fill(
+        Extent3d { width: PLOT_WIDTH, height: PLOT_HEIGHT, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        &[0, 0, 0, 160],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    ));
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(8.0)),
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            InspectorPanel,
+        ))
+        .with_children(|panel| {
+            panel.spawn((TextBundle::default(), InspectorText));
+            panel.spawn(ImageBundle {
+                image: UiImage::new(plot.clone()),
+                style: Style {
+                    width: Val::Px(PLOT_WIDTH as f32),
+                    height: Val::Px(PLOT_HEIGHT as f32),
+                    ..default()
+                },
+                ..default()
+            });
+        });
+    commands.insert_resource(InspectorPlot(plot));
+}
+
+type Newborn = Or<(Added<Star>, Added<PlasmaParticle>)>;
+
+fn stamp_births(mut commands: Commands, time: Res<Time>, newborn: Query<Entity, Newborn>) {
+    for entity in newborn.iter() {
+        // The particle may be swallowed before the command applies
+        commands.entity(entity).try_insert(Birth { time: time.elapsed_seconds() });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn pick_particle(
+    mut commands: Commands,
+    time: Res<Time>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<FlyCamera>>,
+    index: Res<SpatialIndex>,
+    trails: Res<Trails>,
+    mut inspection: ResMut<Inspection>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let (Ok(window), Ok((camera, camera_transform))) = (windows.get_single(), cameras.get_single()) else { return };
+
+    // 1. RAY through the cursor, or the window centre while the cursor is locked
+    let centre = Vec2::new(window.width(), window.height()) * 0.5;
+    let cursor = match window.cursor.grab_mode {
+        CursorGrabMode::None => window.cursor_position().unwrap_or(centre),
+        _ => centre,
+    };
+    let Some(ray) = camera.viewport_to_world(camera_transform, cursor) else { return };
+
+    // 2. CAST against every CPU population and the GPU read-back mirrors
+    let populations = [Population::Star, Population::Plasma, Population::Gas, Population::Gpu];
+    let hit = index.raycast(&populations, ray.origin, *ray.direction, 2000.0, 0.5, 0.004);
+
+    let previous = inspection.target.take();
+    if let Some(mut entity) = previous.and_then(|previous| commands.get_entity(previous.entity)) {
+        entity.remove::<(ForceProbe, PathTrail)>();
+    }
+    inspection.position = None;
+    if let Some((population, entry)) = hit {
+        if population != Population::Gpu {
+            commands.entity(entry.entity).try_insert(ForceProbe::default());
+        }
+        // The path starts from the plasma's ribbon trail
+        let path = PathTrail::new(LinearRgba::rgb(1.0, 0.2, 0.8))
+            .with_lifetime(PATH_LIFETIME)
+            .seeded(time.elapsed_seconds(), trails.history(entry.entity));
+        commands.entity(entry.entity).try_insert(path);
+        inspection.target = Some(PickedParticle { entity: entry.entity, population });
+        inspection.position = Some(entry.position);
+    }
+}
+
+type InspectedStar<'a> = (&'a Transform, &'a Star, Option<&'a Birth>, Option<&'a ForceProbe>);
+type InspectedPlasma<'a> = (
+    &'a Transform,
+    &'a PlasmaParticle,
+    Option<&'a Birth>,
+    Option<&'a ForceProbe>,
+    Option<&'a SphGas>,
+    Option<&'a JetBorne>,
+);
+
+#[allow(clippy::too_many_arguments)]
+fn update_inspector(
+    time: Res<Time>,
+    mut inspection: ResMut<Inspection>,
+    stars: Query<InspectedStar>,
+    plasma: Query<InspectedPlasma>,
+    gpu_particles: Query<&GpuParticle>,
+    readback: Option<Res<GpuReadback>>,
+    black_holes: Query<&Transform, With<BlackHole>>,
+    index: Res<SpatialIndex>,
+    plot: Option<Res<InspectorPlot>>,
+    mut images: ResMut<Assets<Image>>,
+    mut panels: Query<&mut Visibility, With<InspectorPanel>>,
+    mut texts: Query<&mut Text, With<InspectorText>>,
+) {
+    let Some(picked) = inspection.target else {
+        for mut visibility in panels.iter_mut() {
+            *visibility = Visibility::Hidden;
+        }
+        return;
+    };
+
+    // 1. STATE of the picked particle, whatever its population
+    let mut lines = Vec::new();
+    let mut probe = None;
+    let state = if let Ok((transform, star, birth, force_probe)) = stars.get(picked.entity) {
+        probe = force_probe;
+        lines.push("Star".to_string());
+        Some((transform.translation, star.velocity, birth))
+    } else if let Ok((transform, particle, birth, force_probe, gas, jet)) = plasma.get(picked.entity) {
+        probe = force_probe;
+        let kind = match (jet, gas) {
+            (Some(_), _) => "Plasma (jet)",
+            (None, Some(_)) => "Plasma (SPH gas)",
+            (None, None) => "Plasma",
+        };
+        lines.push(kind.to_string());
+        lines.push(format!("arm {} of {}   original radius {:.2}", particle.arm, particle.arms, particle.original_radius));
+        lines.push(format!("charge q/m {:.3}", particle.charge));
+        Some((transform.translation, particle.velocity, birth))
+    } else if picked.population == Population::Gpu {
+        gpu_particles
+            .get(picked.entity)
+            .ok()
+            .and_then(|gpu| readback.as_ref()?.particles.get(gpu.entity_index))
+            .map(|particle| {
+                lines.push(format!("GPU particle ({})", if particle.vel.w > 0.5 { "jet" } else { "disk" }));
+                (particle.pos.truncate(), particle.vel.truncate(), None)
+            })
+    } else {
+        None
+    };
+    let Some((position, velocity, birth)) = state else {
+        // Swallowed by a black hole, or expired
+        inspection.target = None;
+        return;
+    };
+
+    let radius = black_holes
+        .iter()
+        .map(|transform| transform.translation.distance(position))
+        .min_by(|a, b| a.total_cmp(b))
+        .unwrap_or(position.length());
+    lines.push(format!("position ({:.2}, {:.2}, {:.2})", position.x, position.y, position.z));
+    lines.push(format!("velocity ({:.2}, {:.2}, {:.2})   |v| {:.2}", velocity.x, velocity.y, velocity.z, velocity.length()));
+    lines.push(format!("radius {:.2}", radius));
+
+    // Nearest other body of the same population (the index is a tick old, so skip the particle itself)
+    let neighbour = index
+        .k_nearest(&[picked.population], position, 2)
+        .into_iter()
+        .find(|(_, entry)| entry.entity != picked.entity);
+    if let Some((_, entry)) = neighbour {
+        lines.push(format!("nearest neighbour {:.2}", entry.position.distance(position)));
+    }
+    if let Some(birth) = birth {
+        lines.push(format!("age {:.1}s", time.elapsed_seconds() - birth.time));
+    }
+
+    inspection.position = Some(position);
+
+    // 2. FORCE PLOT of |F| per contribution, with the latest values as the legend
+    let mut sections = vec![TextSection::new(lines.join("\n"), TextStyle { font_size: 14.0, ..default() })];
+    if let (Some(probe), Some(plot)) = (probe, plot) {
+        let names: Vec<&'static str> = probe.samples.back().map_or(Vec::new(), |(_, forces)| {
+            forces.iter().map(|(name, _)| *name).collect()
+        });
+        let magnitudes: Vec<Vec<f32>> = names
+            .iter()
+            .enumerate()
+            .map(|(i, _)| probe.samples.iter().map(|(_, forces)| forces.get(i).map_or(0.0, |(_, f)| f.length())).collect())
+            .collect();
+        let series: Vec<PlotSeries> = names
+            .iter()
+            .zip(magnitudes.iter())
+            .map(|(name, values)| PlotSeries { values, color: force_color(name) })
+            .collect();
+        if let Some(image) = images.get_mut(&plot.0) {
+            image.data = draw_line_plot(PLOT_WIDTH, PLOT_HEIGHT, &series, [0, 0, 0, 160]);
+        }
+        sections.push(TextSection::new("\n|F| over the last 4s:", TextStyle { font_size: 14.0, ..default() }));
+        for (name, values) in names.iter().zip(magnitudes.iter()) {
+            let [r, g, b, _] = force_color(name);
+            sections.push(TextSection::new(
+                format!("\n  {name} {:.3}", values.last().copied().unwrap_or(0.0)),
+                TextStyle { font_size: 14.0, color: Color::srgb_u8(r, g, b), ..default() },
+            ));
+        }
+    }
+
+    for mut text in texts.iter_mut() {
+        text.sections = sections.clone();
+    }
+    for mut visibility in panels.iter_mut() {
+        *visibility = Visibility::Inherited;
+    }
+}
+
+// The path itself is a PathTrail on the particle
+fn draw_inspected_marker(mut gizmos: Gizmos, inspection: Res<Inspection>) {
+    if let Some(head) = inspection.position.filter(|_| inspection.target.is_some()) {
+        gizmos.sphere(head, Quat::IDENTITY, 0.6, Color::srgb(1.0, 1.0, 1.0));
+    }
+}
@@ -0,0 +1,158 @@
+use bevy::prelude::*;
+use crate::simulation::black_hole::{gather_cores, nearest_core, BlackHoleConfig, CoreState};
+use crate::simulation::current_field::SelfField;
+use crate::simulation::galaxy::{BlackHole, GalaxyMember};
+use crate::simulation::hud::timed;
+use crate::simulation::magnetic_field::MagneticField;
+use crate::simulation::plasma::{update_galaxy_physics, PlasmaParticle};
+use crate::physics::electromagnetism::boris_push;
+
+pub struct JetPlugin;
+
+impl Plugin for JetPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(JetModel::default())
+            .add_systems(Update, timed("jets", update_jets).after(update_galaxy_physics));
+    }
+}
+
+// Marks plasma riding a quasar jet. The disk physics skips these particles
+// until the jet stalls and they rain back onto the outer disk.
+#[derive(Component)]
+pub struct JetBorne;
+
+// The Quasar Jet.
+// Shared by the CPU plasma and the GPU kernel (galaxy_sim.wgsl mirrors every formula here).
+#[derive(Resource)]
+pub struct JetModel {
+    pub launch_radius: f32,       // Material closer than this to the core is launched
+    pub opening_angle: f32,       // Half-angle of the jet cone (rad)
+    pub precession_angle: f32,    // Tilt of the jet axis away from the spin axis (rad)
+    pub precession_period: f32,   // Seconds per precession cycle
+    pub sheath_speed: f32,        // Speed at the cone edge as a fraction of the spine speed
+    pub deceleration_length: f32, // Distance over which the jet loses 1/e of its speed
+    pub reentry_speed: f32,       // Jet material slower than this stalls...
+    pub reentry_distance: f32,    // ...as does material that gets this far from the core
+    pub reentry_radius_min: f32,  // Stalled material rains back onto the disk
+    pub reentry_radius_max: f32,  // between these two radii
+}
+
+impl Default for JetModel {
+    fn default() -> Self {
+        Self {
+            launch_radius: 3.0,
+            opening_angle: 0.06,
+            precession_angle: 0.12,
+            precession_period: 30.0,
+            sheath_speed: 0.6,
+            deceleration_length: 120.0,
+            reentry_speed: 8.0,
+            reentry_distance: 300.0,
+            reentry_radius_min: 60.0,
+            reentry_radius_max: 100.0,
+        }
+    }
+}
+
+impl JetModel {
+    // The jet axis precesses around the spin axis (a cone of half-angle precession_angle)
+    pub fn axis(&self, spin_axis: Vec3, elapsed: f32) -> Vec3 {
+        let phase = std::f32::consts::TAU * elapsed / self.precession_period;
+        let tilt = Vec3::new(
+            self.precession_angle.sin() * phase.cos(),
+            self.precession_angle.cos(),
+            self.precession_angle.sin() * phase.sin(),
+        );
+        Quat::from_rotation_arc(Vec3::Y, spin_axis) * tilt
+    }
+
+    // Launch velocity for random numbers `u` in [0, 1)³:
+    // u.x picks the lobe, u.y and u.z place the particle in the cone.
+    // The spine (on axis) is fastest; the sheath at the cone edge is slower.
+    pub fn launch_velocity(&self, axis: Vec3, jet_speed: f32, u: Vec3) -> Vec3 {
+        let lobe = if u.x < 0.5 { axis } else { -axis };
+        let edge = u.y.sqrt(); // Uniform over the cone's cross-section
+        let theta = self.opening_angle * edge;
+        let azimuth = std::f32::consts::TAU * u.z;
+        let local = Vec3::new(theta.sin() * azimuth.cos(), theta.cos(), theta.sin() * azimuth.sin());
+        let direction = Quat::from_rotation_arc(Vec3::Y, lobe) * local;
+
+        let profile = 1.0 - (1.0 - self.sheath_speed) * edge * edge;
+        direction * jet_speed * profile
+    }
+
+    // The jet sweeps up ether as it travels: speed decays with distance covered
+    pub fn decelerate(&self, velocity: Vec3, dt: f32) -> Vec3 {
+        velocity * (-velocity.length() * dt / self.deceleration_length).exp()
+    }
+
+    // `offset` and `velocity` are relative to the core
+    pub fn stalled(&self, offset: Vec3, velocity: Vec3) -> bool {
+        velocity.length() < self.reentry_speed || offset.length() > self.reentry_distance
+    }
+
+    // Where stalled material lands, in the galaxy's local frame (disk in XZ)
+    pub fn reentry_position(&self, u: Vec2) -> Vec3 {
+        let radius = self.reentry_radius_min + (self.reentry_radius_max - self.reentry_radius_min) * u.x;
+        let angle = std::f32::consts::TAU * u.y;
+        Vec3::new(radius * angle.cos(), 0.0, radius * angle.sin())
+    }
+
+    // Launch one particle from `core`. Returns the new position and velocity (world frame).
+    pub fn launch(&self, core: &CoreState, elapsed: f32) -> (Vec3, Vec3) {
+        let axis = self.axis(core.spin_axis, elapsed);
+        let u = Vec3::new(rand::random(), rand::random(), rand::random());
+        let jet_velocity = self.launch_velocity(axis, core.jet_speed, u);
+        let position = core.position + jet_velocity.normalize_or_zero() * self.launch_radius;
+        (position, jet_velocity)
+    }
+}
+
+type JetParticles = (With<JetBorne>, Without<BlackHole>);
+
+#[allow(clippy::too_many_arguments)]
+fn update_jets(
+    mut commands: Commands,
+    mut particles: Query<(Entity, &mut PlasmaParticle, &mut Transform), JetParticles>,
+    black_hole_query: Query<(Entity, &Transform, &BlackHole)>,
+    time: Res<Time>,
+    jet_model: Res<JetModel>,
+    magnetic_field: Res<MagneticField>,
+    self_field: Res<SelfField>,
+    black_hole_config: Res<BlackHoleConfig>,
+) {
+    let cores = gather_cores(&black_hole_config, black_hole_query.iter());
+    let dt = time.delta_seconds();
+
+    for (entity, mut particle, mut transform) in particles.iter_mut() {
+        let pos = transform.translation;
+        let Some(core) = nearest_core(&cores, pos) else {
+            continue;
+        };
+
+        // 1. RE-ENTRY: a stalled jet rains back onto the outer disk of the nearest galaxy
+        if jet_model.stalled(pos - core.position, particle.velocity - core.velocity) {
+            let local_pos = jet_model.reentry_position(Vec2::new(rand::random(), rand::random()));
+            let tangent = Vec3::new(-local_pos.z, 0.0, local_pos.x).normalize();
+            let frame = core.disk_frame();
+
+            transform.translation = core.position + frame * local_pos;
+            particle.velocity = core.velocity + frame * tangent * 15.0;
+            particle.original_radius = local_pos.length();
+            particle.color = Color::srgb(0.0, 1.0, 1.0); // Cyan Arms
+            commands.entity(entity)
+                .remove::<JetBorne>()
+                .insert(GalaxyMember { black_hole: core.entity });
+            continue;
+        }
+
+        // 2. COLLIMATION: the toroidal field wraps the jet (Lorentz force only)
+        let b_field = magnetic_field.field_from_cores(&cores, pos) + self_field.field_at(pos);
+        let relative = boris_push(particle.velocity - core.velocity, Vec3::ZERO, b_field, particle.charge, dt);
+
+        // 3. DECELERATION: entrainment of the surrounding ether
+        particle.velocity = core.velocity + jet_model.decelerate(relative, dt);
+
+        transform.translation += particle.velocity * dt;
+    }
+}
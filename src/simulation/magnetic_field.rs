@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+use crate::physics::electromagnetism::{dipole_field, toroidal_field};
+use crate::simulation::black_hole::CoreState;
+
+pub struct MagneticFieldPlugin;
+
+impl Plugin for MagneticFieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(MagneticField::default());
+    }
+}
+
+// The Dynamo: the field generated by the spinning core.
+// A dipole aligned with the BlackHole spin axis, plus the toroidal
+// wrap produced by the current flowing up the rotation axis.
+#[derive(Resource)]
+pub struct MagneticField {
+    pub enabled: bool,
+    pub dipole_moment: f32,
+    pub toroidal_current: f32,
+    pub core_radius: f32,
+}
+
+impl Default for MagneticField {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dipole_moment: 3000.0,
+            toroidal_current: 20.0,
+            core_radius: 2.0,
+        }
+    }
+}
+
+impl MagneticField {
+    // Total field at `pos` for a core sitting at `center` spinning around `spin_axis`.
+    pub fn field_at(&self, pos: Vec3, center: Vec3, spin_axis: Vec3) -> Vec3 {
+        if !self.enabled {
+            return Vec3::ZERO;
+        }
+        let offset = pos - center;
+        let axis = spin_axis.normalize_or_zero();
+
+        dipole_field(offset, axis * self.dipole_moment, self.core_radius)
+            + toroidal_field(offset, axis, self.toroidal_current, self.core_radius)
+    }
+
+    // Sum of every core's dynamo at `pos`
+    pub fn field_from_cores(&self, cores: &[CoreState], pos: Vec3) -> Vec3 {
+        cores
+            .iter()
+            .map(|core| self.field_at(pos, core.position, core.spin_axis))
+            .sum()
+    }
+}
@@ -1,7 +1,29 @@
+pub mod black_hole;
 pub mod camera;
+pub mod camera_path;
+pub mod capture;
+pub mod color_map;
+pub mod current_field;
 pub mod emitter;
+pub mod escape;
+pub mod ether;
+pub mod ether_volume;
+pub mod export;
 pub mod galaxy;
 pub mod gpu_galaxy;
+pub mod hud;
+pub mod import;
+pub mod inspector;
+pub mod jet;
 pub mod debug_viz;
+pub mod magnetic_field;
+pub mod particle_render;
+pub mod paths;
 pub mod plasma;
-pub mod render_settings;
\ No newline at end of file
+pub mod plots;
+pub mod render_settings;
+pub mod scenario;
+pub mod software_render;
+pub mod spatial_index;
+pub mod sph;
+pub mod trails;
\ No newline at end of file
@@ -0,0 +1,321 @@
+use bevy::prelude::*;
+use bevy::core_pipeline::core_3d::Transparent3d;
+use bevy::ecs::query::QueryItem;
+use bevy::ecs::system::lifetimeless::{Read, SRes};
+use bevy::ecs::system::SystemParamItem;
+use bevy::pbr::{MeshPipeline, MeshPipelineKey, RenderMeshInstances, SetMeshBindGroup, SetMeshViewBindGroup};
+use bevy::render::extract_component::{ExtractComponent, ExtractComponentPlugin};
+use bevy::render::mesh::{GpuBufferInfo, GpuMesh, MeshVertexBufferLayoutRef};
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::render_phase::{
+    AddRenderCommand, DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand, RenderCommandResult,
+    SetItemPipeline, TrackedRenderPass, ViewSortedRenderPhases,
+};
+use bevy::render::render_resource::{
+    Buffer, BufferDescriptor, BufferUsages, PipelineCache, RenderPipelineDescriptor, SpecializedMeshPipeline,
+    SpecializedMeshPipelineError, SpecializedMeshPipelines, VertexAttribute, VertexBufferLayout, VertexFormat,
+    VertexStepMode,
+};
+use bevy::render::renderer::{RenderDevice, RenderQueue};
+use bevy::render::view::{ExtractedView, NoFrustumCulling};
+use bevy::render::{Render, RenderApp, RenderSet};
+use crate::simulation::color_map::{ParticleColor, STAR_COLOR};
+use crate::simulation::galaxy::Star;
+use crate::simulation::hud::timed;
+use crate::simulation::plasma::PlasmaParticle;
+
+// Draws every Star and PlasmaParticle in one instanced call (assets/shaders/instance.wgsl)
+pub struct ParticleRenderPlugin;
+
+impl Plugin for ParticleRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ParticleRenderConfig>()
+            .add_plugins(ExtractComponentPlugin::<ParticleInstances>::default())
+            .add_systems(Startup, setup_particle_batch)
+            .add_systems(PostUpdate, timed("instances", gather_particle_instances));
+
+        // Headless runs have no render app
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else { return };
+        render_app
+            .add_render_command::<Transparent3d, DrawParticleInstances>()
+            .init_resource::<SpecializedMeshPipelines<InstancePipeline>>()
+            .init_resource::<InstanceBufferCache>()
+            .add_systems(
+                Render,
+                (
+                    queue_particle_instances.in_set(RenderSet::QueueMeshes),
+                    prepare_instance_buffer.in_set(RenderSet::PrepareResources),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.init_resource::<InstancePipeline>();
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct ParticleRenderConfig {
+    pub enabled: bool,
+    pub star_size: f32,
+    pub plasma_size: f32,
+    pub brightness: f32, // HDR multiplier so dense regions bloom
+}
+
+impl Default for ParticleRenderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            star_size: 0.15,
+            plasma_size: 0.2,
+            brightness: 2.0,
+        }
+    }
+}
+
+// Matches the per-instance attributes of instance.wgsl (locations 3-7)
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+pub struct InstanceData {
+    pub transform: [Vec4; 4], // Columns
+    pub color: Vec4,          // Alpha 0: blended additively
+}
+
+impl InstanceData {
+    fn new(position: Vec3, size: f32, color: LinearRgba, brightness: f32) -> Self {
+        let matrix = Mat4::from_scale_rotation_translation(Vec3::splat(size), Quat::IDENTITY, position);
+        Self {
+            transform: [matrix.x_axis, matrix.y_axis, matrix.z_axis, matrix.w_axis],
+            color: Vec4::new(color.red, color.green, color.blue, 0.0) * brightness,
+        }
+    }
+}
+
+// The one batch entity: a unit sphere mesh plus every particle's instance
+#[derive(Component, Default, Clone)]
+pub struct ParticleInstances(pub Vec<InstanceData>);
+
+impl ExtractComponent for ParticleInstances {
+    type QueryData = &'static ParticleInstances;
+    type QueryFilter = ();
+    type Out = Self;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self> {
+        Some(item.clone())
+    }
+}
+
+fn setup_particle_batch(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    commands.spawn((
+        meshes.add(Sphere::new(1.0).mesh().ico(1).unwrap()),
+        SpatialBundle::INHERITED_IDENTITY,
+        ParticleInstances::default(),
+        // The mesh sits at the origin; its instances are everywhere
+        NoFrustumCulling,
+    ));
+}
+
+pub fn gather_particle_instances(
+    config: Res<ParticleRenderConfig>,
+    stars: Query<(&Transform, Option<&ParticleColor>), With<Star>>,
+    plasma: Query<(&PlasmaParticle, &Transform, Option<&ParticleColor>)>,
+    mut batches: Query<&mut ParticleInstances>,
+) {
+    for mut batch in batches.iter_mut() {
+        let instances = &mut batch.0;
+        instances.clear();
+        if !config.enabled {
+            continue;
+        }
+        // Colors come from the color map (native colors until it first runs)
+        instances.extend(stars.iter().map(|(transform, color)| {
+            let color = color.map_or(STAR_COLOR, |color| color.0);
+            InstanceData::new(transform.translation, config.star_size, color, config.brightness)
+        }));
+        instances.extend(plasma.iter().map(|(particle, transform, color)| {
+            let color = color.map_or_else(|| particle.color.to_linear(), |color| color.0);
+            InstanceData::new(transform.translation, config.plasma_size, color, config.brightness)
+        }));
+    }
+}
+
+// Render world: one vertex buffer, grown by doubling and rewritten each frame
+#[derive(Resource, Default)]
+struct InstanceBufferCache {
+    buffer: Option<Buffer>,
+    capacity: usize,
+}
+
+#[derive(Component)]
+struct InstanceBuffer {
+    buffer: Buffer,
+    length: usize,
+}
+
+fn prepare_instance_buffer(
+    mut commands: Commands,
+    batches: Query<(Entity, &ParticleInstances)>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut cache: ResMut<InstanceBufferCache>,
+) {
+    for (entity, instances) in batches.iter() {
+        if instances.0.is_empty() {
+            continue;
+        }
+        if cache.buffer.is_none() || cache.capacity < instances.0.len() {
+            cache.capacity = instances.0.len().next_power_of_two();
+            cache.buffer = Some(render_device.create_buffer(&BufferDescriptor {
+                label: Some("Particle Instance Buffer"),
+                size: (cache.capacity * std::mem::size_of::<InstanceData>()) as u64,
+                usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+        }
+        let Some(buffer) = cache.buffer.clone() else { continue };
+        render_queue.write_buffer(&buffer, 0, bytemuck::cast_slice(&instances.0));
+        commands.entity(entity).insert(InstanceBuffer { buffer, length: instances.0.len() });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_particle_instances(
+    draw_functions: Res<DrawFunctions<Transparent3d>>,
+    instance_pipeline: Res<InstancePipeline>,
+    msaa: Res<Msaa>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<InstancePipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    meshes: Res<RenderAssets<GpuMesh>>,
+    render_mesh_instances: Res<RenderMeshInstances>,
+    batches: Query<Entity, With<ParticleInstances>>,
+    mut transparent_phases: ResMut<ViewSortedRenderPhases<Transparent3d>>,
+    views: Query<(Entity, &ExtractedView)>,
+) {
+    let draw_function = draw_functions.read().id::<DrawParticleInstances>();
+    // Premultiplied blending with zero alpha adds the particles' light together
+    let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples()) | MeshPipelineKey::BLEND_PREMULTIPLIED_ALPHA;
+
+    for (view_entity, view) in views.iter() {
+        let Some(transparent_phase) = transparent_phases.get_mut(&view_entity) else { continue };
+        let view_key = msaa_key | MeshPipelineKey::from_hdr(view.hdr);
+        let rangefinder = view.rangefinder3d();
+        for entity in batches.iter() {
+            let Some(mesh_instance) = render_mesh_instances.render_mesh_queue_data(entity) else { continue };
+            let Some(mesh) = meshes.get(mesh_instance.mesh_asset_id) else { continue };
+            let key = view_key | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology());
+            let pipeline = match pipelines.specialize(&pipeline_cache, &instance_pipeline, key, &mesh.layout) {
+                Ok(pipeline) => pipeline,
+                Err(error) => {
+                    error!("Particle instance pipeline: {error}");
+                    continue;
+                }
+            };
+            transparent_phase.add(Transparent3d {
+                entity,
+                pipeline,
+                draw_function,
+                distance: rangefinder.distance_translation(&mesh_instance.translation),
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::NONE,
+            });
+        }
+    }
+}
+
+#[derive(Resource)]
+struct InstancePipeline {
+    shader: Handle<Shader>,
+    mesh_pipeline: MeshPipeline,
+}
+
+impl FromWorld for InstancePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let mesh_pipeline = world.resource::<MeshPipeline>().clone();
+        Self {
+            shader: world.load_asset("shaders/instance.wgsl"),
+            mesh_pipeline,
+        }
+    }
+}
+
+impl SpecializedMeshPipeline for InstancePipeline {
+    type Key = MeshPipelineKey;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayoutRef,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let mut descriptor = self.mesh_pipeline.specialize(key, layout)?;
+        descriptor.label = Some("particle_instance_pipeline".into());
+        descriptor.vertex.shader = self.shader.clone();
+
+        // Locations 0-2 are the mesh's position, normal and UV
+        let vec4 = VertexFormat::Float32x4;
+        descriptor.vertex.buffers.push(VertexBufferLayout {
+            array_stride: std::mem::size_of::<InstanceData>() as u64,
+            step_mode: VertexStepMode::Instance,
+            attributes: (0..5)
+                .map(|i| VertexAttribute {
+                    format: vec4,
+                    offset: i * vec4.size(),
+                    shader_location: 3 + i as u32,
+                })
+                .collect(),
+        });
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            fragment.shader = self.shader.clone();
+        }
+        Ok(descriptor)
+    }
+}
+
+type DrawParticleInstances = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetMeshBindGroup<1>,
+    DrawMeshInstanced,
+);
+
+struct DrawMeshInstanced;
+
+impl<P: PhaseItem> RenderCommand<P> for DrawMeshInstanced {
+    type Param = (SRes<RenderAssets<GpuMesh>>, SRes<RenderMeshInstances>);
+    type ViewQuery = ();
+    type ItemQuery = Read<InstanceBuffer>;
+
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        instance_buffer: Option<&'w InstanceBuffer>,
+        (meshes, render_mesh_instances): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(mesh_instance) = render_mesh_instances.render_mesh_queue_data(item.entity()) else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(gpu_mesh) = meshes.into_inner().get(mesh_instance.mesh_asset_id) else {
+            return RenderCommandResult::Failure;
+        };
+        // Nothing to draw this frame
+        let Some(instance_buffer) = instance_buffer else {
+            return RenderCommandResult::Success;
+        };
+
+        pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
+        pass.set_vertex_buffer(1, instance_buffer.buffer.slice(..));
+        let instances = 0..instance_buffer.length as u32;
+        match &gpu_mesh.buffer_info {
+            GpuBufferInfo::Indexed { buffer, index_format, count } => {
+                pass.set_index_buffer(buffer.slice(..), 0, *index_format);
+                pass.draw_indexed(0..*count, 0, instances);
+            }
+            GpuBufferInfo::NonIndexed => {
+                pass.draw(0..gpu_mesh.vertex_count, instances);
+            }
+        }
+        RenderCommandResult::Success
+    }
+}
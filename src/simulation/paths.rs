@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use crate::simulation::plasma::CloudCentroid;
+
+// Fading polylines behind individual moving bodies: black holes, the plasma centroid,
+// the inspected particle. Each body keeps one reused point buffer; nothing is spawned per sample.
+pub struct PathPlugin;
+
+impl Plugin for PathPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PathConfig>()
+            .add_systems(Startup, spawn_centroid_tracker)
+            .add_systems(PostUpdate, (follow_centroid, sample_paths, draw_paths).chain());
+    }
+}
+
+// Picked on the command line: `cargo run -- --path-lifetime 10 --path-interval 0.05`, `--no-paths`
+#[derive(Resource)]
+pub struct PathConfig {
+    pub enabled: bool,
+    pub lifetime: f32, // Seconds a point stays visible, unless the body overrides it
+    pub interval: f32, // Simulation seconds between points
+}
+
+impl Default for PathConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            lifetime: 5.0,
+            interval: 0.1,
+        }
+    }
+}
+
+impl PathConfig {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+                .and_then(|v| v.parse::<f32>().ok())
+        };
+
+        let mut config = Self {
+            enabled: !args.iter().any(|arg| arg == "--no-paths"),
+            ..Self::default()
+        };
+        if let Some(lifetime) = value("--path-lifetime") {
+            config.lifetime = lifetime.max(0.0);
+        }
+        if let Some(interval) = value("--path-interval") {
+            config.interval = interval.max(0.0);
+        }
+        config
+    }
+}
+
+#[derive(Component)]
+pub struct PathTrail {
+    pub color: LinearRgba,
+    pub lifetime: Option<f32>, // None uses PathConfig::lifetime
+    points: VecDeque<(f32, Vec3)>, // (sample time, position), oldest first
+}
+
+impl PathTrail {
+    pub fn new(color: LinearRgba) -> Self {
+        Self { color, lifetime: None, points: VecDeque::new() }
+    }
+
+    pub fn with_lifetime(mut self, lifetime: f32) -> Self {
+        self.lifetime = Some(lifetime);
+        self
+    }
+
+    // Starts the path from an earlier history (oldest first), stamped `time`
+    pub fn seeded(mut self, time: f32, history: impl IntoIterator<Item = Vec3>) -> Self {
+        self.points.extend(history.into_iter().map(|position| (time, position)));
+        self
+    }
+}
+
+// Carries the plasma centroid's path
+#[derive(Component)]
+struct CentroidTracker;
+
+fn spawn_centroid_tracker(mut commands: Commands) {
+    commands.spawn((
+        CentroidTracker,
+        TransformBundle::default(),
+        PathTrail::new(LinearRgba::rgb(0.0, 1.0, 1.0)).with_lifetime(20.0),
+    ));
+}
+
+fn follow_centroid(centroid: Res<CloudCentroid>, mut trackers: Query<&mut Transform, With<CentroidTracker>>) {
+    for mut transform in trackers.iter_mut() {
+        transform.translation = centroid.position;
+    }
+}
+
+fn sample_paths(time: Res<Time>, config: Res<PathConfig>, mut bodies: Query<(&Transform, &mut PathTrail)>) {
+    let now = time.elapsed_seconds();
+    for (transform, mut path) in bodies.iter_mut() {
+        let lifetime = path.lifetime.unwrap_or(config.lifetime);
+
+        // 1. EXPIRE from the front; the buffer's capacity is kept for reuse
+        while path.points.front().is_some_and(|&(time, _)| now - time > lifetime) {
+            path.points.pop_front();
+        }
+
+        // 2. SAMPLE when the interval has passed and the body has moved
+        let due = path.points.back().is_none_or(|&(time, position)| {
+            now - time >= config.interval && position != transform.translation
+        });
+        if due {
+            path.points.push_back((now, transform.translation));
+        }
+    }
+}
+
+fn draw_paths(mut gizmos: Gizmos, time: Res<Time>, config: Res<PathConfig>, bodies: Query<(&Transform, &PathTrail)>) {
+    if !config.enabled {
+        return;
+    }
+    let now = time.elapsed_seconds();
+    for (transform, path) in bodies.iter() {
+        let lifetime = path.lifetime.unwrap_or(config.lifetime).max(1e-3);
+        let fade = |time: f32| path.color.with_alpha((1.0 - (now - time) / lifetime).clamp(0.0, 1.0));
+
+        // Oldest point to the body's current position, fading with age
+        let points = path.points.iter().map(|&(time, position)| (position, fade(time)));
+        gizmos.linestrip_gradient(points.chain(std::iter::once((transform.translation, path.color))));
+    }
+}
@@ -1,10 +1,20 @@
 use bevy::prelude::*;
-use std::collections::VecDeque;
-use crate::simulation::galaxy::BlackHole;
+use crate::simulation::galaxy::{BlackHole, GalaxyMember};
+use crate::simulation::black_hole::{gather_cores, nearest_core, owner_core, AccretionLedger, BlackHoleConfig, CoreState};
 use crate::simulation::gpu_galaxy::PhiResource;
+use crate::simulation::magnetic_field::MagneticField;
+use crate::simulation::current_field::{PicConfig, SelfField};
+use crate::simulation::sph::SphGas;
+use crate::simulation::inspector::ForceProbe;
+use crate::simulation::jet::{JetBorne, JetModel};
+use crate::physics::electromagnetism::boris_push;
+use crate::simulation::hud::timed;
 
 const RESONANCE_SENSITIVITY: f32 = 1000.0;
 const GOLDEN_RATIO: f32 = 1.61803398875;
+pub const PATTERN_SPEED: f32 = 0.1; // Rad/s the spiral pattern turns
+pub const PINCH_STIFFNESS: f32 = 10.0; // Pull per unit distance from the ideal spiral at full resonance
+pub const PLASMA_DRAG: f32 = 0.5; // Linear drag on the plasma velocity
 
 pub struct PlasmaPlugin;
 
@@ -12,19 +22,19 @@ impl Plugin for PlasmaPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(PlasmaConfig::default())
             .insert_resource(CloudCentroid::default())
-            .add_systems(Update, update_galaxy_physics)
-            .add_systems(Update, calculate_centroid)
-            .add_systems(Update, draw_trails);
+            .add_systems(Update, timed("plasma", update_galaxy_physics))
+            .add_systems(Update, calculate_centroid);
     }
 }
 
 #[derive(Component)]
 pub struct PlasmaParticle {
     pub velocity: Vec3,
-    pub history: VecDeque<Vec3>,
     pub color: Color,
     pub original_radius: f32,
     pub arm: u32,
+    pub arms: u32, // Arm count of the spiral the particle was born into
+    pub charge: f32, // Charge-to-mass ratio (q/m), particles have unit mass
 }
 
 #[derive(Resource)]
@@ -48,6 +58,24 @@ impl Default for CloudCentroid {
 }
 
 
+// The Ether only vibrates effectively at the Golden Ratio: a Gaussian in the detuning.
+// Small deviations cause a massive loss of the pinch force.
+pub fn resonance(phi: f32) -> f32 {
+    let deviation = (phi - GOLDEN_RATIO).abs();
+    (-deviation * deviation * RESONANCE_SENSITIVITY).exp()
+}
+
+// Where the Z-Pinch pulls a particle: its birth radius on its arm of the home spiral
+pub fn pinch_target(particle: &PlasmaParticle, home: &CoreState, phi: f32, galaxy_angle: f32) -> Vec3 {
+    let arm_offset = std::f32::consts::TAU * particle.arm as f32 / particle.arms.max(1) as f32;
+    let angle = (particle.original_radius.ln() * phi) + arm_offset + galaxy_angle;
+    home.position + home.disk_frame() * Vec3::new(
+        particle.original_radius * angle.cos(),
+        0.0,
+        particle.original_radius * angle.sin()
+    )
+}
+
 fn ideal_spiral_pos(r: f32, b: f32) -> Vec3 {
     let theta = b * r.ln();
     Vec3::new(r * theta.cos(), 0.0, r * theta.sin())
@@ -76,103 +104,108 @@ fn calculate_centroid(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn update_galaxy_physics(
+    mut commands: Commands,
     mut param_set: ParamSet<(
-        Query<(&mut PlasmaParticle, &mut Transform)>,
-        Query<&Transform, With<BlackHole>>,
+        Query<(Entity, &mut PlasmaParticle, &mut Transform, Option<&GalaxyMember>, Option<&SphGas>, Option<&mut ForceProbe>), Without<JetBorne>>,
+        Query<(Entity, &Transform, &BlackHole)>,
     )>,
     time: Res<Time>,
     phi_res: Res<PhiResource>, // <--- INJECT THE RESOURCE
+    magnetic_field: Res<MagneticField>,
+    self_field: Res<SelfField>,
+    pic_config: Res<PicConfig>,
+    black_hole_config: Res<BlackHoleConfig>,
+    mut ledger: ResMut<AccretionLedger>,
+    jet_model: Res<JetModel>,
 ) {
-    let black_hole_pos = param_set.p1().single().translation;
+    let cores = gather_cores(&black_hole_config, param_set.p1().iter());
     let dt = time.delta_seconds();
-    let galaxy_angle = time.elapsed_seconds() * 0.1;
+    let galaxy_angle = time.elapsed_seconds() * PATTERN_SPEED;
 
     // READ THE DYNAMIC PHI FROM KEYBOARD INPUT
     let current_phi = phi_res.phi_value;
 
-    for (mut particle, mut transform) in param_set.p0().iter_mut() {
+    for (entity, mut particle, mut transform, member, gas, probe) in param_set.p0().iter_mut() {
         let pos = transform.translation;
-        let delta_pos = pos - black_hole_pos;
+
+        // The closest core owns the jet and the horizon; the spiral belongs to the home galaxy
+        let Some(core) = nearest_core(&cores, pos) else {
+            continue;
+        };
+        let delta_pos = pos - core.position;
         let r = delta_pos.length();
 
-        // Jet Physics: If in jet (y > 10.0), disable spiral physics
-        if pos.y.abs() > 10.0 {
-            // Just update position, no forces
+        // ACCRETION: inside the event horizon the particle is swallowed
+        if r < core.event_horizon {
+            ledger.capture(core, delta_pos, particle.velocity - core.velocity);
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        // DYNAMO DRIVE: q(v x B) from every core's magnetic field
+        // plus the Z-Pinch field of the plasma's own currents
+        let b_field = magnetic_field.field_from_cores(&cores, pos) + self_field.field_at(pos);
+
+        // GAS PRESSURE: SPH particles also feel pressure and shock viscosity
+        let gas_force = gas.map_or(Vec3::ZERO, |gas| gas.acceleration);
+
+        // Quasar Logic: inside the launch radius, material is fired up the precessing jet
+        if r < jet_model.launch_radius {
+            let (launch_pos, jet_velocity) = jet_model.launch(core, time.elapsed_seconds());
+            ledger.launch(core, jet_velocity);
+            transform.translation = launch_pos;
+            particle.velocity = core.velocity + jet_velocity;
+            particle.color = Color::srgb(0.0, 1.0, 1.0); // Cyan
+            commands.entity(entity).insert(JetBorne);
+            continue;
+        }
+
+        // --- RESONANCE CHECK (The Fix) ---
+        // If the system is detuned, the "Pinch" loses coherence.
+        let resonance = resonance(current_phi);
+
+        // --- Z-PINCH LOGIC ---
+        // The pinch normally comes from the deposited currents through b_field.
+        // The analytic pull toward the ideal spiral is only applied when asked for
+        // (or when the PIC grid is off), and its STRENGTH depends on Resonance.
+        let pinch_force = if pic_config.analytic_pinch() {
+            let home = member
+                .and_then(|member| owner_core(&cores, member.black_hole, pos))
+                .unwrap_or(core);
+            let ideal_pos = pinch_target(&particle, home, current_phi, galaxy_angle);
+
+            // APPLY RESONANCE TO THE FORCE
+            // If resonance is 1.0 (Tuned), force is 10.0.
+            // If resonance is 0.0 (Detuned), force is 0.0 -> Galaxy flies apart.
+            (ideal_pos - pos) * PINCH_STIFFNESS * resonance
         } else {
-            // Quasar Logic: If r < 3.0, eject vertically
-            if r < 3.0 {
-                transform.translation.x = black_hole_pos.x;
-                transform.translation.z = black_hole_pos.z;
-                particle.velocity = if rand::random::<bool>() {
-                    Vec3::Y * 80.0
-                } else {
-                    Vec3::NEG_Y * 80.0
-                };
-                particle.color = Color::srgb(0.0, 1.0, 1.0); // Cyan
-            } else {
-                // --- RESONANCE CHECK (The Fix) ---
-                // The Ether only vibrates effectively at the Golden Ratio.
-                // If the system is detuned, the "Pinch" loses coherence.
-                let golden_ratio = GOLDEN_RATIO;
-                let deviation = (current_phi - golden_ratio).abs();
-
-                // Gaussian Falloff: High sensitivity (RESONANCE_SENSITIVITY).
-                // Small deviations causes massive loss of force.
-                let resonance = (-deviation * deviation * RESONANCE_SENSITIVITY).exp();
-
-                // --- Z-PINCH LOGIC ---
-                let arm_offset = if particle.arm == 0 { 0.0 } else { std::f32::consts::PI };
-
-                // We still calculate target based on current input to visualize the "attempt",
-                // but the STRENGTH of the result depends on Resonance.
-                let angle = (particle.original_radius.ln() * current_phi) + arm_offset + galaxy_angle;
-
-                let ideal_pos = black_hole_pos + Vec3::new(
-                    particle.original_radius * angle.cos(),
-                    0.0,
-                    particle.original_radius * angle.sin()
-                );
-
-                // Apply Forces
-                let pinch_vector = ideal_pos - pos;
-
-                // APPLY RESONANCE TO THE FORCE
-                // If resonance is 1.0 (Tuned), force is 10.0.
-                // If resonance is 0.0 (Detuned), force is 0.0 -> Galaxy flies apart.
-                let pinch_force = pinch_vector * 10.0 * resonance;
-
-                let drag_force = -particle.velocity * 0.5;
-
-                particle.velocity += (pinch_force + drag_force) * dt;
-            }
+            Vec3::ZERO
+        };
+
+        let drag_force = -particle.velocity * PLASMA_DRAG;
+
+        // Only the inspected particle carries a probe
+        if let Some(mut probe) = probe {
+            probe.record(time.elapsed_seconds(), &[
+                ("pinch", pinch_force),
+                ("drag", drag_force),
+                ("gas", gas_force),
+                ("lorentz", particle.charge * particle.velocity.cross(b_field)),
+            ]);
         }
 
+        // Integrate the Lorentz force together with pinch and drag (Boris pusher)
+        particle.velocity = boris_push(
+            particle.velocity,
+            pinch_force + drag_force + gas_force,
+            b_field,
+            particle.charge,
+            dt,
+        );
+
         // Update position
         transform.translation += particle.velocity * dt;
-
-        // Update history
-        particle.history.push_back(transform.translation);
-        if particle.history.len() > 20 {
-            particle.history.pop_front();
-        }
     }
 }
-
-fn draw_trails(mut gizmos: Gizmos, query: Query<&PlasmaParticle>) {
-    for particle in query.iter() {
-        if particle.history.len() < 2 {
-            continue;
-        }
-        let positions: Vec<Vec3> = particle.history.iter().cloned().collect();
-        let num_points = positions.len();
-        for i in 0..num_points - 1 {
-            let start = positions[i];
-            let end = positions[i + 1];
-            let t = i as f32 / (num_points - 1) as f32;
-            // Fade from White (head, t=0) to Blue (tail, t=1)
-            let color = Color::srgb(1.0 - t * 0.5, 1.0 - t * 0.5, 1.0);
-            gizmos.line(start, end, color);
-        }
-    }
-}
\ No newline at end of file
@@ -0,0 +1,393 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use std::path::PathBuf;
+use crate::io::diagnostic_log::DiagnosticLog;
+use crate::viz::plot::{draw_line_plot, draw_scatter_plot, PlotSeries};
+use crate::simulation::black_hole::{gather_cores, nearest_core, owner_core, suction_potential, BlackHoleConfig};
+use crate::simulation::color_map::arm_coherence;
+use crate::simulation::current_field::PicConfig;
+use crate::simulation::escape::BindingCensus;
+use crate::simulation::galaxy::{BlackHole, GalaxyMember, InitialConditions, Star};
+use crate::simulation::gpu_galaxy::PhiResource;
+use crate::simulation::jet::JetBorne;
+use crate::simulation::plasma::{pinch_target, resonance, PlasmaParticle, PATTERN_SPEED, PINCH_STIFFNESS};
+
+const PLOT_WIDTH: u32 = 260;
+const PLOT_HEIGHT: u32 = 70;
+const BACKGROUND: [u8; 4] = [0, 0, 0, 160];
+const ROTATION_BINS: usize = 20;
+const ROTATION_BIN_WIDTH: f32 = 5.0; // The rotation curve spans 0..100
+const ARM_BINS: usize = 8;
+const ARM_BIN_WIDTH: f32 = 7.5; // Arm amplitude is measured in rings out to 60
+
+const COLUMNS: [&str; 14] = [
+    "time",
+    "phi",
+    "resonance",
+    "kinetic",
+    "potential",
+    "energy",
+    "angular_momentum",
+    "arm_amplitude",
+    "coherence",
+    "bound",
+    "escaping",
+    "jet_borne",
+    "recycled",
+    "despawned",
+];
+
+pub struct PlotsPlugin;
+
+impl Plugin for PlotsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlotConfig>()
+            .add_systems(Startup, setup_plots)
+            .add_systems(Update, (control_plots, sample_diagnostics, update_plots).chain());
+    }
+}
+
+// P shows the plots, Z pauses them, E writes them to CSV.
+// Picked on the command line: `cargo run -- --plots --plot-interval 0.05 --plot-history 120 --plot-dir plots`
+#[derive(Resource)]
+pub struct PlotConfig {
+    pub visible: bool,
+    pub interval: f32, // Simulation seconds between samples
+    pub history: f32,  // Seconds of samples kept
+    pub directory: PathBuf,
+}
+
+impl Default for PlotConfig {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            interval: 0.1,
+            history: 60.0,
+            directory: PathBuf::from("."),
+        }
+    }
+}
+
+impl PlotConfig {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        let mut config = Self {
+            visible: args.iter().any(|arg| arg == "--plots"),
+            ..Self::default()
+        };
+        if let Some(interval) = value("--plot-interval").and_then(|v| v.parse::<f32>().ok()) {
+            config.interval = interval.max(1e-3);
+        }
+        if let Some(history) = value("--plot-history").and_then(|v| v.parse::<f32>().ok()) {
+            config.history = history.max(1.0);
+        }
+        if let Some(directory) = value("--plot-dir") {
+            config.directory = PathBuf::from(directory);
+        }
+        config
+    }
+
+    fn capacity(&self) -> usize {
+        (self.history / self.interval).ceil() as usize
+    }
+}
+
+// Ring-buffered diagnostics behind the plots, plus the latest rotation curve
+#[derive(Resource)]
+pub struct DiagnosticPlots {
+    pub log: DiagnosticLog,
+    pub rotation_curve: Vec<f32>, // Mean circular speed per ROTATION_BIN_WIDTH ring
+    pub paused: bool,
+    pub exports: usize,           // Numbers the exported files so none is overwritten
+    pub status: String,           // Result of the last export, shown under the plots
+}
+
+#[derive(Clone, Copy)]
+enum Plot {
+    Energy,
+    AngularMomentum,
+    RotationCurve,
+    ArmAmplitude,
+    Coherence,
+}
+
+const PLOTS: [Plot; 5] = [Plot::Energy, Plot::AngularMomentum, Plot::RotationCurve, Plot::ArmAmplitude, Plot::Coherence];
+
+#[derive(Component)]
+struct PlotPanel;
+
+#[derive(Component)]
+struct PlotTitle(Plot);
+
+#[derive(Component)]
+struct PlotImage(Plot);
+
+#[derive(Component)]
+struct PlotStatus;
+
+fn setup_plots(mut commands: Commands, config: Res<PlotConfig>, mut images: ResMut<Assets<Image>>) {
+    commands.insert_resource(DiagnosticPlots {
+        log: DiagnosticLog::new(&COLUMNS, config.capacity()),
+        rotation_curve: Vec::new(),
+        paused: false,
+        exports: 0,
+        status: String::new(),
+    });
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(8.0)),
+                    row_gap: Val::Px(2.0),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            PlotPanel,
+        ))
+        .with_children(|panel| {
+            for plot in PLOTS {
+                let image = images.add(Image::new_fill(
+                    Extent3d { width: PLOT_WIDTH, height: PLOT_HEIGHT, depth_or_array_layers: 1 },
+                    TextureDimension::D2,
+                    &BACKGROUND,
+                    TextureFormat::Rgba8UnormSrgb,
+                    RenderAssetUsages::default(),
+                ));
+                panel.spawn((TextBundle::from_section("", TextStyle { font_size: 13.0, ..default() }), PlotTitle(plot)));
+                panel.spawn((
+                    ImageBundle {
+                        image: UiImage::new(image),
+                        style: Style { width: Val::Px(PLOT_WIDTH as f32), height: Val::Px(PLOT_HEIGHT as f32), ..default() },
+                        ..default()
+                    },
+                    PlotImage(plot),
+                ));
+            }
+            panel.spawn((TextBundle::from_section("", TextStyle { font_size: 13.0, ..default() }), PlotStatus));
+        });
+}
+
+fn control_plots(
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut config: ResMut<PlotConfig>,
+    plots: Option<ResMut<DiagnosticPlots>>,
+) {
+    if keys.just_pressed(KeyCode::KeyP) {
+        config.visible = !config.visible;
+    }
+    let Some(mut plots) = plots else {
+        return;
+    };
+    if keys.just_pressed(KeyCode::KeyZ) {
+        plots.paused = !plots.paused;
+    }
+    if keys.just_pressed(KeyCode::KeyE) && !plots.log.is_empty() {
+        plots.exports += 1;
+        let status = match export_plots(&config, &plots, time.elapsed_seconds()) {
+            Ok(path) => format!("Wrote {} samples to {}", plots.log.len(), path.display()),
+            Err(error) => format!("Export failed: {error}"),
+        };
+        plots.status = status;
+    }
+}
+
+// The time series and the latest rotation curve, numbered and stamped with the simulation time
+fn export_plots(config: &PlotConfig, plots: &DiagnosticPlots, time: f32) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(&config.directory)?;
+    let stamp = format!("{:03}_{time:.1}s", plots.exports);
+    let path = config.directory.join(format!("diagnostics_{stamp}.csv"));
+    plots.log.write_csv(&mut std::io::BufWriter::new(std::fs::File::create(&path)?))?;
+
+    let mut curve = String::from("radius,circular_speed\n");
+    for (i, speed) in plots.rotation_curve.iter().enumerate() {
+        curve.push_str(&format!("{},{speed}\n", (i as f32 + 0.5) * ROTATION_BIN_WIDTH));
+    }
+    std::fs::write(config.directory.join(format!("rotation_curve_{stamp}.csv")), curve)?;
+    Ok(path)
+}
+
+type StarSample<'a> = (&'a Star, &'a Transform);
+type PlasmaSample<'a> = (&'a PlasmaParticle, &'a Transform, Option<&'a GalaxyMember>);
+
+#[allow(clippy::too_many_arguments)]
+fn sample_diagnostics(
+    time: Res<Time>,
+    config: Res<PlotConfig>,
+    phi: Res<PhiResource>,
+    pic_config: Res<PicConfig>,
+    initial_conditions: Res<InitialConditions>,
+    black_hole_config: Res<BlackHoleConfig>,
+    black_holes: Query<(Entity, &Transform, &BlackHole)>,
+    stars: Query<StarSample, Without<PlasmaParticle>>,
+    plasma: Query<PlasmaSample, (Without<Star>, Without<JetBorne>)>,
+    census: Res<BindingCensus>,
+    plots: Option<ResMut<DiagnosticPlots>>,
+    mut since_sample: Local<f32>,
+) {
+    let Some(mut plots) = plots.filter(|plots| !plots.paused) else {
+        return;
+    };
+    *since_sample += time.delta_seconds();
+    if *since_sample < config.interval {
+        return;
+    }
+    *since_sample = 0.0;
+
+    // 1. BODIES relative to their nearest core: energy, angular momentum, rotation curve.
+    // Each population sits in the potential that moves it, as in the escape classification:
+    // stars in the suction of every core, plasma in the Z-Pinch spring when the analytic pinch is on.
+    let cores = gather_cores(&black_hole_config, black_holes.iter());
+    let stiffness = if pic_config.analytic_pinch() { PINCH_STIFFNESS * resonance(phi.phi_value) } else { 0.0 };
+    let galaxy_angle = time.elapsed_seconds() * PATTERN_SPEED;
+    let star_bodies = stars.iter().map(|(star, transform)| {
+        let pos = transform.translation;
+        (pos, star.velocity, suction_potential(&cores, pos))
+    });
+    let plasma_bodies = plasma.iter().filter_map(|(particle, transform, member)| {
+        let pos = transform.translation;
+        let home = member
+            .and_then(|member| owner_core(&cores, member.black_hole, pos))
+            .or_else(|| nearest_core(&cores, pos))?;
+        let target = pinch_target(particle, home, phi.phi_value, galaxy_angle);
+        Some((pos, particle.velocity, 0.5 * stiffness * target.distance_squared(pos)))
+    });
+    let (mut kinetic, mut potential, mut angular_momentum) = (0.0, 0.0, 0.0);
+    let mut rings = [(0.0f32, 0usize); ROTATION_BINS];
+    for (pos, velocity, body_potential) in star_bodies.chain(plasma_bodies) {
+        let Some(core) = nearest_core(&cores, pos) else { continue };
+        let (offset, relative) = (pos - core.position, velocity - core.velocity);
+        kinetic += 0.5 * relative.length_squared();
+        potential += body_potential;
+        angular_momentum += offset.cross(relative).dot(core.spin_axis);
+
+        let planar = offset - core.spin_axis * offset.dot(core.spin_axis);
+        let ring = (planar.length() / ROTATION_BIN_WIDTH) as usize;
+        if let (Some(ring), Some(tangent)) = (rings.get_mut(ring), core.spin_axis.cross(planar).try_normalize()) {
+            ring.0 += relative.dot(tangent);
+            ring.1 += 1;
+        }
+    }
+    plots.rotation_curve = rings.iter().map(|&(sum, count)| if count > 0 { sum / count as f32 } else { f32::NAN }).collect();
+
+    // 2. ARMS: the m = arms Fourier amplitude of the plasma in rings, and the spiral coherence
+    let arms = initial_conditions.model.arms.max(1) as f32;
+    let mut modes = [(Vec2::ZERO, 0usize); ARM_BINS];
+    for (_, transform, _) in plasma.iter() {
+        let Some(core) = nearest_core(&cores, transform.translation) else { continue };
+        let local = core.disk_frame().inverse() * (transform.translation - core.position);
+        let radius = Vec2::new(local.x, local.z).length();
+        if let Some(ring) = modes.get_mut((radius / ARM_BIN_WIDTH) as usize) {
+            let angle = local.z.atan2(local.x) * arms;
+            ring.0 += Vec2::new(angle.cos(), angle.sin());
+            ring.1 += 1;
+        }
+    }
+    let filled: Vec<f32> = modes.iter().filter(|ring| ring.1 > 0).map(|ring| ring.0.length() / ring.1 as f32).collect();
+    let arm_amplitude = filled.iter().sum::<f32>() / filled.len().max(1) as f32;
+    let particles = plasma.iter().map(|(particle, transform, _)| (transform.translation, particle.arms));
+    let coherence = arm_coherence(&cores, particles, phi.phi_value, galaxy_angle).unwrap_or(f32::NAN);
+
+    plots.log.push(&[
+        time.elapsed_seconds(),
+        phi.phi_value,
+        resonance(phi.phi_value),
+        kinetic,
+        potential,
+        kinetic + potential,
+        angular_momentum,
+        arm_amplitude,
+        coherence,
+        census.fraction(census.bound),
+        census.fraction(census.escaping),
+        census.fraction(census.jet_borne),
+        census.recycled as f32,
+        census.despawned as f32,
+    ]);
+}
+
+fn update_plots(
+    config: Res<PlotConfig>,
+    plots: Option<Res<DiagnosticPlots>>,
+    mut images: ResMut<Assets<Image>>,
+    mut panels: Query<&mut Visibility, With<PlotPanel>>,
+    mut titles: Query<(&mut Text, &PlotTitle), Without<PlotStatus>>,
+    mut statuses: Query<&mut Text, With<PlotStatus>>,
+    canvases: Query<(&UiImage, &PlotImage)>,
+) {
+    for mut visibility in panels.iter_mut() {
+        *visibility = if config.visible { Visibility::Inherited } else { Visibility::Hidden };
+    }
+    let Some(plots) = plots.filter(|plots| config.visible && plots.is_changed()) else {
+        return;
+    };
+    let log = &plots.log;
+    let latest = |name: &str| log.column(name).last().copied().unwrap_or(f32::NAN);
+    let paused = if plots.paused { " (paused)" } else { "" };
+    for mut text in statuses.iter_mut() {
+        text.sections[0].value.clone_from(&plots.status);
+    }
+
+    for (mut text, title) in titles.iter_mut() {
+        text.sections[0].value = match title.0 {
+            Plot::Energy => format!(
+                "Energy: kinetic {:.3e}, potential {:.3e}, total {:.3e}{paused}",
+                latest("kinetic"),
+                latest("potential"),
+                latest("energy")
+            ),
+            Plot::AngularMomentum => format!("Angular momentum {:.3e}", latest("angular_momentum")),
+            Plot::RotationCurve => format!("Rotation curve, r = 0..{:.0}", ROTATION_BINS as f32 * ROTATION_BIN_WIDTH),
+            Plot::ArmAmplitude => format!("Arm amplitude {:.3}", latest("arm_amplitude")),
+            Plot::Coherence => format!("Coherence {:.3} vs φ {:.4}", latest("coherence"), latest("phi")),
+        };
+    }
+
+    let red = [255, 110, 80, 255];
+    let blue = [90, 160, 255, 255];
+    let white = [230, 230, 230, 255];
+    for (image, canvas) in canvases.iter() {
+        let Some(target) = images.get_mut(&image.texture) else { continue };
+        target.data = match canvas.0 {
+            Plot::Energy => {
+                let (kinetic, potential, total) = (log.column("kinetic"), log.column("potential"), log.column("energy"));
+                let series = [
+                    PlotSeries { values: &kinetic, color: red },
+                    PlotSeries { values: &potential, color: blue },
+                    PlotSeries { values: &total, color: white },
+                ];
+                draw_line_plot(PLOT_WIDTH, PLOT_HEIGHT, &series, BACKGROUND)
+            }
+            Plot::AngularMomentum => {
+                let values = log.column("angular_momentum");
+                draw_line_plot(PLOT_WIDTH, PLOT_HEIGHT, &[PlotSeries { values: &values, color: white }], BACKGROUND)
+            }
+            Plot::RotationCurve => {
+                let series = [PlotSeries { values: &plots.rotation_curve, color: blue }];
+                draw_line_plot(PLOT_WIDTH, PLOT_HEIGHT, &series, BACKGROUND)
+            }
+            Plot::ArmAmplitude => {
+                let values = log.column("arm_amplitude");
+                draw_line_plot(PLOT_WIDTH, PLOT_HEIGHT, &[PlotSeries { values: &values, color: red }], BACKGROUND)
+            }
+            Plot::Coherence => {
+                let points: Vec<(f32, f32)> = log.column("phi").into_iter().zip(log.column("coherence")).collect();
+                draw_scatter_plot(PLOT_WIDTH, PLOT_HEIGHT, &points, blue, BACKGROUND)
+            }
+        };
+    }
+}
@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use crate::physics::initial_conditions::{Bulge, ComponentMix, GalaxyModel, SpheroidProfile};
+use crate::simulation::emitter::{
+    jet_cone_emitter, EmissionMode, Emitter, EmitterColor, EmitterShape, EmitterTarget, VelocityProfile,
+};
+
+// Which initial galaxies to build.
+// Picked on the command line: `cargo run -- --scenario merger`
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Scenario {
+    #[default]
+    SingleGalaxy,
+    Merger, // Two galaxies on a collision course (tidal tails)
+    Emitters, // One galaxy surrounded by a sample of every emitter shape
+}
+
+// One galaxy: its black hole, where it starts and how it moves.
+pub struct GalaxySpec {
+    pub center: Vec3,
+    pub velocity: Vec3,
+    pub spin_axis: Vec3,
+    pub spin: f32,
+}
+
+impl Scenario {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let name = args
+            .iter()
+            .position(|arg| arg == "--scenario")
+            .and_then(|i| args.get(i + 1));
+        match name.map(String::as_str) {
+            Some("merger") => Scenario::Merger,
+            Some("emitters") => Scenario::Emitters,
+            _ => Scenario::SingleGalaxy,
+        }
+    }
+
+    pub fn galaxies(&self) -> Vec<GalaxySpec> {
+        match self {
+            Scenario::SingleGalaxy | Scenario::Emitters => vec![GalaxySpec {
+                center: Vec3::ZERO,
+                velocity: Vec3::ZERO,
+                spin_axis: Vec3::Y,
+                spin: 0.7,
+            }],
+            Scenario::Merger => vec![
+                GalaxySpec {
+                    center: Vec3::new(-90.0, 0.0, -25.0),
+                    velocity: Vec3::new(6.0, 0.0, 0.0),
+                    spin_axis: Vec3::Y,
+                    spin: 0.7,
+                },
+                // Inclined and counter-rotating: the classic recipe for long tidal tails
+                GalaxySpec {
+                    center: Vec3::new(90.0, 10.0, 25.0),
+                    velocity: Vec3::new(-6.0, 0.0, 0.0),
+                    spin_axis: Vec3::new(0.0, -1.0, 0.6).normalize(),
+                    spin: 0.5,
+                },
+            ],
+        }
+    }
+
+    // Extra particle sources placed in the scene (the galaxies bring their own genesis burst)
+    pub fn emitters(&self) -> Vec<(Transform, Emitter)> {
+        match self {
+            Scenario::SingleGalaxy | Scenario::Merger => Vec::new(),
+            Scenario::Emitters => vec![
+                // A halo of stars above the disk
+                (
+                    Transform::from_xyz(0.0, 60.0, 0.0),
+                    Emitter {
+                        shape: EmitterShape::Sphere { radius: 20.0 },
+                        mode: EmissionMode::Burst { count: 2000 },
+                        velocity: VelocityProfile::Isotropic { speed: 2.0 },
+                        target: EmitterTarget::Stars,
+                        ..default()
+                    },
+                ),
+                // A Plummer globular cluster falling in from the side
+                (
+                    Transform::from_xyz(0.0, 20.0, 150.0),
+                    Emitter {
+                        shape: EmitterShape::Galaxy {
+                            model: GalaxyModel {
+                                bulge: Some(Bulge { profile: SpheroidProfile::Plummer, mass: 300.0, scale_radius: 4.0 }),
+                                ..GalaxyModel::default()
+                            },
+                            mix: ComponentMix { disk: 0.0, bulge: 1.0, halo: 0.0, arm_fraction: 0.0 },
+                        },
+                        mode: EmissionMode::Burst { count: 1000 },
+                        velocity: VelocityProfile::Equilibrium,
+                        target: EmitterTarget::Stars,
+                        ..default()
+                    },
+                ),
+                // Fresh material fed into the GPU galaxy's outer ring
+                (
+                    Transform::IDENTITY,
+                    Emitter {
+                        shape: EmitterShape::Disk { inner_radius: 60.0, outer_radius: 80.0, thickness: 2.0 },
+                        mode: EmissionMode::Rate { per_second: 5000.0 },
+                        velocity: VelocityProfile::Orbital { speed: 15.0 },
+                        target: EmitterTarget::Gpu,
+                        ..default()
+                    },
+                ),
+                // A plasma fountain off to one side
+                (
+                    Transform::from_xyz(-120.0, 0.0, 0.0),
+                    Emitter {
+                        mode: EmissionMode::Rate { per_second: 50.0 },
+                        velocity: VelocityProfile::Directed { velocity: Vec3::new(0.0, 20.0, 0.0) },
+                        dispersion: 3.0,
+                        color: EmitterColor::Constant(Color::srgb(1.0, 0.4, 0.1)),
+                        lifetime: Some(8.0),
+                        ..default()
+                    },
+                ),
+                // A three-armed plasma spiral seeded below the disk
+                (
+                    Transform::from_xyz(0.0, -60.0, 0.0),
+                    Emitter {
+                        shape: EmitterShape::LogSpiral {
+                            arms: 3,
+                            pitch: 1.618034,
+                            inner_radius: 5.0,
+                            outer_radius: 40.0,
+                            fuzz: 0.3,
+                            thickness: 2.0,
+                        },
+                        mode: EmissionMode::Burst { count: 3000 },
+                        velocity: VelocityProfile::Orbital { speed: 10.0 },
+                        ..default()
+                    },
+                ),
+                // A tilted second quasar
+                (
+                    Transform::from_xyz(120.0, 0.0, 0.0).with_rotation(Quat::from_rotation_z(0.5)),
+                    jet_cone_emitter(100.0, 40.0),
+                ),
+            ],
+        }
+    }
+}
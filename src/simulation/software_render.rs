@@ -0,0 +1,158 @@
+use bevy::prelude::*;
+use std::path::PathBuf;
+use crate::io::png::write_png;
+use crate::viz::splat::{HdrFrame, SplatCamera};
+use crate::simulation::camera::FlyCamera;
+use crate::simulation::color_map::{ParticleColor, STAR_COLOR};
+use crate::simulation::galaxy::Star;
+use crate::simulation::gpu_galaxy::GpuReadback;
+use crate::simulation::plasma::PlasmaParticle;
+
+pub struct SoftwareRenderPlugin;
+
+impl Plugin for SoftwareRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SoftwareRenderConfig>()
+            .add_systems(Last, render_software_frames); // After every physics system of the tick
+    }
+}
+
+// CPU point splatting, for machines without a GPU.
+// Picked on the command line:
+// `cargo run -- --headless --software-render renders --render-size 1920x1080 --render-every 0.5 --render-frames 120`
+#[derive(Resource)]
+pub struct SoftwareRenderConfig {
+    pub enabled: bool,
+    pub directory: PathBuf,
+    pub width: u32,
+    pub height: u32,
+    pub interval: f32,       // Simulation seconds between images
+    pub frames: Option<u32>, // Exit after this many images
+    pub exposure: f32,
+    pub point_radius: f32,   // World units
+    pub brightness: f32,     // Energy deposited per particle
+    pub bloom_threshold: f32,
+    pub bloom_strength: f32,
+}
+
+impl Default for SoftwareRenderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: PathBuf::from("renders"),
+            width: 1280,
+            height: 720,
+            interval: 1.0,
+            frames: None,
+            exposure: 1.0,
+            point_radius: 0.3,
+            brightness: 0.6,
+            bloom_threshold: 1.0,
+            bloom_strength: 0.5,
+        }
+    }
+}
+
+impl SoftwareRenderConfig {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        let mut config = Self::default();
+        if let Some(directory) = value("--software-render") {
+            config.enabled = true;
+            config.directory = PathBuf::from(directory);
+        }
+        let size = value("--render-size").and_then(|size| size.split_once('x'));
+        if let Some((Ok(width), Ok(height))) = size.map(|(w, h)| (w.parse::<u32>(), h.parse::<u32>())) {
+            config.width = width.max(1);
+            config.height = height.max(1);
+        }
+        if let Some(interval) = value("--render-every").and_then(|v| v.parse().ok()) {
+            config.interval = interval;
+        }
+        config.frames = value("--render-frames").and_then(|v| v.parse().ok());
+        if let Some(exposure) = value("--render-exposure").and_then(|v| v.parse().ok()) {
+            config.exposure = exposure;
+        }
+        config
+    }
+}
+
+#[derive(Default)]
+struct SoftwareRenderState {
+    next_time: f32,
+    frame: u32,
+    failed: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_software_frames(
+    config: Res<SoftwareRenderConfig>,
+    time: Res<Time>,
+    cameras: Query<(&Transform, Option<&Projection>), With<FlyCamera>>,
+    stars: Query<(&Transform, Option<&ParticleColor>), With<Star>>,
+    plasma: Query<(&PlasmaParticle, &Transform, Option<&ParticleColor>)>,
+    readback: Option<Res<GpuReadback>>,
+    mut app_exit_events: EventWriter<AppExit>,
+    mut state: Local<SoftwareRenderState>,
+) {
+    if !config.enabled || state.failed || time.elapsed_seconds() < state.next_time {
+        return;
+    }
+    let Some((camera_transform, projection)) = cameras.iter().next() else { return };
+    state.next_time = time.elapsed_seconds() + config.interval;
+
+    // 1. CAMERA from the fly camera's pose and lens
+    let (fov_y, near) = match projection {
+        Some(Projection::Perspective(perspective)) => (perspective.fov, perspective.near),
+        _ => {
+            let perspective = PerspectiveProjection::default();
+            (perspective.fov, perspective.near)
+        }
+    };
+    let camera = SplatCamera::new(camera_transform.compute_matrix(), fov_y, near, config.width, config.height);
+
+    // 2. SPLAT every population additively
+    let mut frame = HdrFrame::new(config.width, config.height);
+    let mut splat = |position: Vec3, color: Vec3| {
+        if let Some((pixel, depth)) = camera.project(position) {
+            frame.splat(pixel, camera.pixel_radius(config.point_radius, depth), color * config.brightness);
+        }
+    };
+    for (transform, color) in stars.iter() {
+        let color = color.map_or(STAR_COLOR, |color| color.0);
+        splat(transform.translation, Vec3::new(color.red, color.green, color.blue));
+    }
+    for (particle, transform, color) in plasma.iter() {
+        let color = color.map_or_else(|| particle.color.to_linear(), |color| color.0);
+        splat(transform.translation, Vec3::new(color.red, color.green, color.blue));
+    }
+    if let Some(readback) = readback.as_ref() {
+        for particle in readback.particles.iter() {
+            splat(particle.pos.truncate(), particle.color.truncate());
+        }
+    }
+
+    // 3. BLOOM, tone map and write
+    frame.bloom(config.bloom_threshold, config.bloom_strength, (config.height / 120).max(1));
+    if let Err(error) = std::fs::create_dir_all(&config.directory) {
+        eprintln!("Software render disabled, cannot create {}: {error}", config.directory.display());
+        state.failed = true;
+        return;
+    }
+    let path = config.directory.join(format!("frame_{:06}.png", state.frame));
+    if let Err(error) = write_png(&path, config.width, config.height, &frame.to_srgb8(config.exposure)) {
+        eprintln!("Software render of {} failed: {error}", path.display());
+    }
+    state.frame += 1;
+
+    if config.frames.is_some_and(|frames| state.frame >= frames) {
+        println!("Rendered {} frames to {}", state.frame, config.directory.display());
+        app_exit_events.send(AppExit::Success);
+    }
+}
@@ -0,0 +1,325 @@
+use bevy::prelude::*;
+use crate::physics::spatial_hash::SpatialHash;
+use crate::simulation::galaxy::Star;
+use crate::simulation::gpu_galaxy::GpuParticle;
+use crate::simulation::hud::timed;
+use crate::simulation::plasma::PlasmaParticle;
+use crate::simulation::sph::SphGas;
+
+pub struct SpatialIndexPlugin;
+
+impl Plugin for SpatialIndexPlugin {
+    fn build(&self, app: &mut App) {
+        let config = SpatialIndexConfig::default();
+        app.insert_resource(SpatialIndex::new(config.cell_size))
+            .insert_resource(config)
+            .add_systems(PreUpdate, timed("spatial index", rebuild_spatial_index));
+    }
+}
+
+#[derive(Resource)]
+pub struct SpatialIndexConfig {
+    pub cell_size: f32, // Default matches the SPH kernel support (2h)
+}
+
+impl Default for SpatialIndexConfig {
+    fn default() -> Self {
+        Self { cell_size: 8.0 }
+    }
+}
+
+// Every indexed body belongs to exactly one population.
+// SPH gas is kept apart from the rest of the plasma so the gas solver only sees gas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Population {
+    Star,
+    Plasma,
+    Gas,
+    Gpu, // Read-back GpuParticle mirrors
+}
+
+impl Population {
+    fn slot(self) -> usize {
+        match self {
+            Population::Star => 0,
+            Population::Plasma => 1,
+            Population::Gas => 2,
+            Population::Gpu => 3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IndexEntry {
+    pub entity: Entity,
+    pub position: Vec3,
+}
+
+struct IndexLayer {
+    hash: SpatialHash,
+    entries: Vec<IndexEntry>,
+}
+
+// "Which particles are near here?"
+// Rebuilt at the start of every tick from the positions the previous tick ended with.
+#[derive(Resource)]
+pub struct SpatialIndex {
+    layers: [IndexLayer; 4],
+    bounds: Option<(Vec3, Vec3)>,
+}
+
+impl SpatialIndex {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            layers: std::array::from_fn(|_| IndexLayer {
+                hash: SpatialHash::new(cell_size),
+                entries: Vec::new(),
+            }),
+            bounds: None,
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.layers[0].hash.cell_size
+    }
+
+    pub fn clear(&mut self) {
+        for layer in self.layers.iter_mut() {
+            layer.hash.clear();
+            layer.entries.clear();
+        }
+        self.bounds = None;
+    }
+
+    pub fn insert(&mut self, population: Population, entity: Entity, position: Vec3) {
+        let layer = &mut self.layers[population.slot()];
+        layer.hash.insert(layer.entries.len(), position);
+        layer.entries.push(IndexEntry { entity, position });
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.min(position), max.max(position)),
+            None => (position, position),
+        });
+    }
+
+    pub fn entries(&self, population: Population) -> &[IndexEntry] {
+        &self.layers[population.slot()].entries
+    }
+
+    // Calls `visit` with (population, index into `entries(population)`, entry)
+    // for every body within `radius` of `pos`.
+    pub fn for_each_in_radius(
+        &self,
+        populations: &[Population],
+        pos: Vec3,
+        radius: f32,
+        mut visit: impl FnMut(Population, usize, &IndexEntry),
+    ) {
+        let radius_sq = radius * radius;
+        for &population in populations {
+            let layer = &self.layers[population.slot()];
+            layer.hash.for_each_candidate(pos, radius, |i| {
+                let entry = &layer.entries[i];
+                if entry.position.distance_squared(pos) <= radius_sq {
+                    visit(population, i, entry);
+                }
+            });
+        }
+    }
+
+    pub fn query_radius(&self, populations: &[Population], pos: Vec3, radius: f32) -> Vec<(Population, IndexEntry)> {
+        let mut found = Vec::new();
+        self.for_each_in_radius(populations, pos, radius, |population, _, entry| {
+            found.push((population, *entry));
+        });
+        found
+    }
+
+    // The body nearest the ray origin among those within `tolerance` of the ray.
+    // The tolerance grows with distance (`angular` radians) so far bodies stay pickable.
+    pub fn raycast(
+        &self,
+        populations: &[Population],
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        tolerance: f32,
+        angular: f32,
+    ) -> Option<(Population, IndexEntry)> {
+        let step = self.cell_size();
+        let width = |along: f32| tolerance + along * angular;
+        let mut best: Option<(f32, Population, IndexEntry)> = None;
+
+        // March in cell-sized steps; stop one step past the first hit
+        let mut s = 0.0;
+        while s <= max_distance && best.is_none_or(|(along, _, _)| s <= along + step) {
+            let center = origin + direction * s;
+            for (population, entry) in self.query_radius(populations, center, step * 0.5 + width(s + step)) {
+                let offset = entry.position - origin;
+                let along = offset.dot(direction);
+                if along > 0.0
+                    && (offset - direction * along).length() <= width(along)
+                    && best.is_none_or(|(nearest, _, _)| along < nearest)
+                {
+                    best = Some((along, population, entry));
+                }
+            }
+            s += step;
+        }
+        best.map(|(_, population, entry)| (population, entry))
+    }
+
+    // The `k` bodies closest to `pos`, nearest first.
+    // Grows the search sphere until it holds k bodies or covers the whole index.
+    pub fn k_nearest(&self, populations: &[Population], pos: Vec3, k: usize) -> Vec<(Population, IndexEntry)> {
+        let Some((min, max)) = self.bounds else {
+            return Vec::new();
+        };
+        if k == 0 {
+            return Vec::new();
+        }
+        let farthest = pos.distance(pos.clamp(min, max)) + (max - min).length();
+
+        let mut radius = self.cell_size();
+        loop {
+            let mut found = self.query_radius(populations, pos, radius);
+            if found.len() >= k || radius >= farthest {
+                found.sort_by(|a, b| {
+                    a.1.position
+                        .distance_squared(pos)
+                        .total_cmp(&b.1.position.distance_squared(pos))
+                });
+                found.truncate(k);
+                return found;
+            }
+            radius *= 2.0;
+        }
+    }
+}
+
+type PlasmaWithoutGas = (With<PlasmaParticle>, Without<SphGas>);
+
+fn rebuild_spatial_index(
+    config: Res<SpatialIndexConfig>,
+    mut index: ResMut<SpatialIndex>,
+    stars: Query<(Entity, &Transform), With<Star>>,
+    plasma: Query<(Entity, &Transform), PlasmaWithoutGas>,
+    gas: Query<(Entity, &Transform), With<SphGas>>,
+    gpu_particles: Query<(Entity, &Transform), With<GpuParticle>>,
+) {
+    if index.cell_size() != config.cell_size {
+        *index = SpatialIndex::new(config.cell_size);
+    }
+    index.clear();
+
+    for (entity, transform) in stars.iter() {
+        index.insert(Population::Star, entity, transform.translation);
+    }
+    for (entity, transform) in plasma.iter() {
+        index.insert(Population::Plasma, entity, transform.translation);
+    }
+    for (entity, transform) in gas.iter() {
+        index.insert(Population::Gas, entity, transform.translation);
+    }
+    for (entity, transform) in gpu_particles.iter() {
+        index.insert(Population::Gpu, entity, transform.translation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POPULATIONS: [Population; 2] = [Population::Star, Population::Plasma];
+
+    // A fixed scatter of 200 bodies, alternating stars and plasma
+    fn scattered() -> (SpatialIndex, Vec<(Population, IndexEntry)>) {
+        let mut index = SpatialIndex::new(4.0);
+        let mut bodies = Vec::new();
+        for i in 0..200u32 {
+            let position = Vec3::new(
+                (i * 37 % 41) as f32 - 20.0,
+                (i * 17 % 23) as f32 - 11.0,
+                (i * 53 % 31) as f32 - 15.0,
+            ) * 0.7;
+            let population = POPULATIONS[i as usize % 2];
+            let entity = Entity::from_raw(i);
+            index.insert(population, entity, position);
+            bodies.push((population, IndexEntry { entity, position }));
+        }
+        (index, bodies)
+    }
+
+    fn sorted_entities(found: impl IntoIterator<Item = (Population, IndexEntry)>) -> Vec<u32> {
+        let mut ids: Vec<u32> = found.into_iter().map(|(_, entry)| entry.entity.index()).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn radius_queries_match_brute_force() {
+        let (index, bodies) = scattered();
+        for populations in [&POPULATIONS[..], &POPULATIONS[..1]] {
+            for (pos, radius) in [(Vec3::ZERO, 3.0), (Vec3::new(5.0, -2.0, 7.0), 6.5), (Vec3::splat(30.0), 12.0)] {
+                let expected = bodies
+                    .iter()
+                    .filter(|(population, entry)| populations.contains(population) && entry.position.distance(pos) <= radius)
+                    .copied();
+                assert_eq!(
+                    sorted_entities(index.query_radius(populations, pos, radius)),
+                    sorted_entities(expected),
+                    "{populations:?} within {radius} of {pos}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn k_nearest_is_ordered_and_matches_brute_force() {
+        let (index, bodies) = scattered();
+        let pos = Vec3::new(2.0, 1.0, -3.0);
+        let mut distances: Vec<f32> = bodies.iter().map(|(_, entry)| entry.position.distance(pos)).collect();
+        distances.sort_by(f32::total_cmp);
+
+        for k in [1, 5, 40, 500] {
+            let found: Vec<f32> = index
+                .k_nearest(&POPULATIONS, pos, k)
+                .iter()
+                .map(|(_, entry)| entry.position.distance(pos))
+                .collect();
+            assert_eq!(found, distances[..k.min(bodies.len())], "k = {k}");
+        }
+        assert!(index.k_nearest(&POPULATIONS, pos, 0).is_empty());
+        assert!(SpatialIndex::new(4.0).k_nearest(&POPULATIONS, pos, 3).is_empty());
+    }
+
+    #[test]
+    fn raycast_hits_the_nearest_body_along_the_ray() {
+        let (index, bodies) = scattered();
+        let (tolerance, angular) = (0.6, 0.01);
+        // Aimed past a few bodies, so something is always in the way
+        let diagonal = Vec3::ONE.normalize();
+        let rays = [
+            (Vec3::new(-40.0, 0.3, 0.2), Vec3::X),
+            (bodies[7].1.position + Vec3::new(0.3, 30.0, 0.0), Vec3::NEG_Y),
+            (bodies[50].1.position - diagonal * 40.0, diagonal),
+        ];
+        for (origin, direction) in rays {
+            let expected = bodies
+                .iter()
+                .filter_map(|(_, entry)| {
+                    let offset = entry.position - origin;
+                    let along = offset.dot(direction);
+                    let within = (offset - direction * along).length() <= tolerance + along * angular;
+                    (along > 0.0 && within).then_some((along, entry.entity))
+                })
+                .min_by(|a, b| a.0.total_cmp(&b.0))
+                .map(|(_, entity)| entity);
+            assert!(expected.is_some(), "ray from {origin} should hit something");
+            let hit = index.raycast(&POPULATIONS, origin, direction, 200.0, tolerance, angular);
+            assert_eq!(hit.map(|(_, entry)| entry.entity), expected, "ray from {origin}");
+        }
+
+        // Pointing away from every body
+        assert!(index.raycast(&POPULATIONS, Vec3::new(-40.0, 0.0, 0.0), Vec3::NEG_X, 200.0, tolerance, angular).is_none());
+    }
+}
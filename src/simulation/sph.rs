@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use crate::physics::sph_kernel::{artificial_viscosity, SphKernel};
+use crate::simulation::hud::timed;
+use crate::simulation::plasma::{update_galaxy_physics, PlasmaParticle};
+use crate::simulation::spatial_index::{Population, SpatialIndex};
+
+pub struct SphPlugin;
+
+impl Plugin for SphPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SphConfig::default())
+            .add_systems(Update, (cycle_sph_kernel, timed("sph", update_sph_forces).before(update_galaxy_physics)));
+    }
+}
+
+#[derive(Resource)]
+pub struct SphConfig {
+    pub enabled: bool,
+    pub kernel: SphKernel,
+    pub smoothing_length: f32,
+    pub particle_mass: f32,
+    pub sound_speed: f32, // Isothermal gas: P = c_s² ρ
+    pub viscosity_alpha: f32,
+    pub viscosity_beta: f32,
+    // Share of the plasma population spawned as SPH gas
+    pub gas_fraction: f32,
+}
+
+impl Default for SphConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            kernel: SphKernel::CubicSpline,
+            smoothing_length: 4.0,
+            particle_mass: 1.0,
+            sound_speed: 5.0,
+            viscosity_alpha: 1.0,
+            viscosity_beta: 2.0,
+            gas_fraction: 0.1,
+        }
+    }
+}
+
+// Marks a PlasmaParticle as an SPH gas element.
+// The spiral potential still acts on it; the pressure and viscosity
+// acceleration computed here is added on top by update_galaxy_physics.
+#[derive(Component, Default)]
+pub struct SphGas {
+    pub density: f32,
+    pub pressure: f32,
+    pub acceleration: Vec3,
+}
+
+fn cycle_sph_kernel(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut config: ResMut<SphConfig>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyK) {
+        config.kernel = match config.kernel {
+            SphKernel::CubicSpline => SphKernel::WendlandC2,
+            SphKernel::WendlandC2 => SphKernel::CubicSpline,
+        };
+    }
+}
+
+fn update_sph_forces(
+    config: Res<SphConfig>,
+    index: Res<SpatialIndex>,
+    mut query: Query<(&PlasmaParticle, &mut SphGas)>,
+) {
+    let h = config.smoothing_length;
+    let support = config.kernel.support_radius(h);
+    let mass = config.particle_mass;
+    let c_s = config.sound_speed;
+
+    if !config.enabled {
+        for (_, mut gas) in query.iter_mut() {
+            gas.acceleration = Vec3::ZERO;
+        }
+        return;
+    }
+
+    // 1. GATHER (the gas layer of the spatial index, in index order)
+    let entries = index.entries(Population::Gas);
+    let velocities: Vec<Vec3> = entries
+        .iter()
+        .map(|entry| query.get(entry.entity).map_or(Vec3::ZERO, |(particle, _)| particle.velocity))
+        .collect();
+
+    // 2. DENSITY & PRESSURE (neighbors from the spatial index)
+    let mut densities = vec![0.0; entries.len()];
+    for (i, entry) in entries.iter().enumerate() {
+        let mut density = 0.0;
+        index.for_each_in_radius(&[Population::Gas], entry.position, support, |_, _, neighbor| {
+            density += mass * config.kernel.value(entry.position.distance(neighbor.position), h);
+        });
+        densities[i] = density;
+    }
+    let pressures: Vec<f32> = densities.iter().map(|rho| c_s * c_s * rho).collect();
+
+    // 3. MOMENTUM EQUATION (Pressure gradient + Artificial viscosity)
+    let mut accelerations = vec![Vec3::ZERO; entries.len()];
+    for (i, entry) in entries.iter().enumerate() {
+        let mut acceleration = Vec3::ZERO;
+        index.for_each_in_radius(&[Population::Gas], entry.position, support, |_, j, neighbor| {
+            if i == j {
+                return;
+            }
+            let offset = entry.position - neighbor.position;
+            let viscosity = artificial_viscosity(
+                offset,
+                velocities[i] - velocities[j],
+                h,
+                c_s,
+                0.5 * (densities[i] + densities[j]),
+                config.viscosity_alpha,
+                config.viscosity_beta,
+            );
+            let pressure_term = pressures[i] / (densities[i] * densities[i])
+                + pressures[j] / (densities[j] * densities[j]);
+            acceleration -= config.kernel.gradient(offset, h) * (mass * (pressure_term + viscosity));
+        });
+        accelerations[i] = acceleration;
+    }
+
+    // 4. WRITE BACK
+    for (i, entry) in entries.iter().enumerate() {
+        if let Ok((_, mut gas)) = query.get_mut(entry.entity) {
+            gas.density = densities[i];
+            gas.pressure = pressures[i];
+            gas.acceleration = accelerations[i];
+        }
+    }
+}
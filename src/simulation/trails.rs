@@ -0,0 +1,506 @@
+use bevy::prelude::*;
+use bevy::core_pipeline::core_3d::{Transparent3d, CORE_3D_DEPTH_FORMAT};
+use bevy::ecs::system::lifetimeless::SRes;
+use bevy::ecs::system::SystemParamItem;
+use bevy::pbr::{MeshPipeline, MeshPipelineKey, SetMeshViewBindGroup};
+use bevy::render::extract_component::{ExtractComponent, ExtractComponentPlugin};
+use bevy::render::extract_resource::{ExtractResource, ExtractResourcePlugin};
+use bevy::render::render_phase::{
+    AddRenderCommand, DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand, RenderCommandResult,
+    SetItemPipeline, TrackedRenderPass, ViewSortedRenderPhases,
+};
+use bevy::render::render_resource::binding_types::{storage_buffer_read_only_sized, uniform_buffer_sized};
+use bevy::render::render_resource::{
+    BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, BlendComponent, BlendFactor,
+    BlendOperation, BlendState, Buffer, BufferDescriptor, BufferUsages, ColorTargetState, ColorWrites,
+    CompareFunction, DepthStencilState, FragmentState, MultisampleState, PipelineCache, PrimitiveState,
+    PrimitiveTopology, RenderPipelineDescriptor, ShaderStages, SpecializedRenderPipeline,
+    SpecializedRenderPipelines, TextureFormat, VertexState,
+};
+use bevy::render::renderer::{RenderDevice, RenderQueue};
+use bevy::render::texture::BevyDefault;
+use bevy::render::view::{ExtractedView, ViewTarget};
+use bevy::render::{Render, RenderApp, RenderSet};
+use bevy::utils::HashMap;
+use crate::viz::trail_ring::{TrailChanges, TrailRing};
+use crate::simulation::jet::JetBorne;
+use crate::simulation::plasma::PlasmaParticle;
+
+const TRAIL_SHADER: &str = "shaders/trails.wgsl";
+
+// Plasma trails: sampled into one ring buffer, drawn as camera-facing ribbons.
+// One instanced draw, one triangle strip per trail, expanded in the vertex shader.
+pub struct TrailPlugin;
+
+impl Plugin for TrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TrailConfig>()
+            .init_resource::<Trails>()
+            .add_plugins(ExtractResourcePlugin::<TrailConfig>::default())
+            .add_plugins(ExtractResourcePlugin::<ExtractedTrails>::default())
+            .add_plugins(ExtractComponentPlugin::<TrailBatch>::default())
+            .add_systems(Startup, |mut commands: Commands| {
+                commands.spawn(TrailBatch);
+            })
+            .add_systems(First, clear_trail_changes)
+            .add_systems(PostUpdate, (assign_trail_slots, sample_trails).chain());
+
+        // Headless runs have no render app
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else { return };
+        render_app
+            .add_render_command::<Transparent3d, DrawTrails>()
+            .init_resource::<SpecializedRenderPipelines<TrailPipeline>>()
+            .init_resource::<TrailGpu>()
+            .add_systems(
+                Render,
+                (
+                    queue_trails.in_set(RenderSet::Queue),
+                    prepare_trail_buffers.in_set(RenderSet::PrepareResources),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.init_resource::<TrailPipeline>();
+        }
+    }
+}
+
+// Picked on the command line:
+// `cargo run -- --trail-length 40 --trail-interval 0.05 --trail-fade 2 --trail-width 0.1`, `--no-trails`
+#[derive(Resource, Clone, ExtractResource)]
+pub struct TrailConfig {
+    pub enabled: bool,
+    pub length: usize,        // Points per trail
+    pub sample_interval: f32, // Simulation seconds between points; 0 samples every tick
+    pub fade: f32,            // Brightness falls as (1 - age)^fade
+    pub width: f32,           // World units
+    pub head_color: LinearRgba,
+    pub tail_color: LinearRgba,
+}
+
+impl Default for TrailConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            length: 20,
+            sample_interval: 0.0,
+            fade: 1.0,
+            width: 0.06,
+            head_color: LinearRgba::rgb(1.0, 1.0, 1.0), // White head
+            tail_color: LinearRgba::rgb(0.25, 0.25, 1.0), // Blue tail
+        }
+    }
+}
+
+impl TrailConfig {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+                .and_then(|v| v.parse::<f32>().ok())
+        };
+
+        let mut config = Self {
+            enabled: !args.iter().any(|arg| arg == "--no-trails"),
+            ..Self::default()
+        };
+        if let Some(length) = value("--trail-length") {
+            config.length = (length as usize).max(2);
+        }
+        if let Some(interval) = value("--trail-interval") {
+            config.sample_interval = interval.max(0.0);
+        }
+        if let Some(fade) = value("--trail-fade") {
+            config.fade = fade.max(0.0);
+        }
+        if let Some(width) = value("--trail-width") {
+            config.width = width;
+        }
+        config
+    }
+}
+
+// Matches `TrailParams` in trails.wgsl (std140)
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+struct TrailParams {
+    head_color: Vec4,
+    tail_color: Vec4,
+    width: f32,
+    fade: f32,
+    length: u32,
+    _padding: u32,
+}
+
+#[derive(Resource)]
+pub struct Trails {
+    ring: TrailRing,
+    slots: HashMap<Entity, u32>,
+}
+
+impl Default for Trails {
+    fn default() -> Self {
+        Self {
+            ring: TrailRing::new(TrailConfig::default().length),
+            slots: HashMap::new(),
+        }
+    }
+}
+
+impl Trails {
+    // Oldest first; empty for bodies without a trail
+    pub fn history(&self, entity: Entity) -> Vec<Vec3> {
+        self.slots.get(&entity).map_or(Vec::new(), |&slot| self.ring.history(slot))
+    }
+}
+
+// The previous frame's writes have been extracted by now.
+// Forgetting them is not a change, or the ring would be extracted every frame.
+fn clear_trail_changes(mut trails: ResMut<Trails>) {
+    trails.bypass_change_detection().ring.clear_changes();
+}
+
+// Trails are only touched when something changes, so the ring is extracted once per sample
+fn assign_trail_slots(
+    config: Res<TrailConfig>,
+    mut trails: ResMut<Trails>,
+    newborn: Query<Entity, Added<PlasmaParticle>>,
+    mut removed: RemovedComponents<PlasmaParticle>,
+) {
+    // A new length re-lays the whole buffer; histories start over
+    if config.length.max(2) != trails.ring.length() {
+        let trails = &mut *trails;
+        trails.ring = TrailRing::new(config.length);
+        for slot in trails.slots.values_mut() {
+            *slot = trails.ring.allocate();
+        }
+    }
+
+    let removed: Vec<Entity> = removed.read().collect();
+    if removed.is_empty() && newborn.is_empty() {
+        return;
+    }
+    let trails = &mut *trails;
+    for entity in removed {
+        if let Some(slot) = trails.slots.remove(&entity) {
+            trails.ring.release(slot);
+        }
+    }
+    for entity in newborn.iter() {
+        let slot = trails.ring.allocate();
+        trails.slots.insert(entity, slot);
+    }
+}
+
+fn sample_trails(
+    time: Res<Time>,
+    config: Res<TrailConfig>,
+    mut trails: ResMut<Trails>,
+    particles: Query<(Entity, &Transform), With<PlasmaParticle>>,
+    launched: Query<Entity, Added<JetBorne>>,
+    mut landed: RemovedComponents<JetBorne>,
+    mut since_sample: Local<f32>,
+) {
+    // 1. TELEPORTS: launching up the jet and raining back onto the disk break the trail
+    for entity in launched.iter().chain(landed.read()) {
+        if let Some(&slot) = trails.slots.get(&entity) {
+            trails.ring.clear(slot);
+        }
+    }
+
+    // 2. SAMPLE on the configured cadence
+    *since_sample += time.delta_seconds();
+    if *since_sample < config.sample_interval {
+        return;
+    }
+    *since_sample = 0.0;
+    let trails = &mut *trails;
+    for (entity, transform) in particles.iter() {
+        if let Some(&slot) = trails.slots.get(&entity) {
+            trails.ring.push(slot, transform.translation);
+        }
+    }
+}
+
+// What changed in the ring this frame, extracted whenever `Trails` changes.
+// The render world keeps its own copy of the ring up to date with it.
+#[derive(Resource)]
+struct ExtractedTrails {
+    length: u32,
+    slots: usize,
+    changes: TrailChanges,
+}
+
+impl ExtractResource for ExtractedTrails {
+    type Source = Trails;
+
+    fn extract_resource(trails: &Trails) -> Self {
+        Self {
+            length: trails.ring.length() as u32,
+            slots: trails.ring.slots(),
+            changes: trails.ring.changes().clone(),
+        }
+    }
+}
+
+// The phase item the ribbons are queued under
+#[derive(Component, Clone, Copy, ExtractComponent)]
+struct TrailBatch;
+
+// Render world: storage buffers grown by doubling, patched with the runs of the ring written each frame
+#[derive(Resource, Default)]
+struct TrailGpu {
+    length: u32,
+    points_copy: Vec<Vec4>,
+    meta_copy: Vec<UVec2>,
+    params: Option<Buffer>,
+    points: Option<Buffer>,
+    meta: Option<Buffer>,
+    capacity: usize, // Slots
+    bind_group: Option<BindGroup>,
+    vertices: u32,
+    slots: u32,
+}
+
+fn prepare_trail_buffers(
+    trails: Option<Res<ExtractedTrails>>,
+    config: Option<Res<TrailConfig>>,
+    pipeline: Res<TrailPipeline>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut gpu: ResMut<TrailGpu>,
+) {
+    let (Some(trails), Some(config)) = (trails, config) else { return };
+    let params = TrailParams {
+        head_color: config.head_color.to_vec4(),
+        tail_color: config.tail_color.to_vec4(),
+        width: config.width,
+        fade: config.fade,
+        length: trails.length,
+        _padding: 0,
+    };
+    let params_buffer = gpu.params.get_or_insert_with(|| {
+        render_device.create_buffer(&BufferDescriptor {
+            label: Some("Trail Params"),
+            size: std::mem::size_of::<TrailParams>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    });
+    render_queue.write_buffer(params_buffer, 0, bytemuck::bytes_of(&params));
+    if !trails.is_changed() {
+        return;
+    }
+
+    // A new length re-lays the ring, which starts over empty
+    let gpu = &mut *gpu;
+    let relaid = gpu.length != trails.length;
+    if relaid {
+        gpu.length = trails.length;
+        gpu.points_copy.clear();
+        gpu.meta_copy.clear();
+    }
+    trails.changes.apply(trails.length as usize, trails.slots, &mut gpu.points_copy, &mut gpu.meta_copy);
+
+    let slots = trails.slots;
+    gpu.slots = slots as u32;
+    gpu.vertices = trails.length * 2;
+    if slots == 0 {
+        return;
+    }
+    let length = trails.length as usize;
+    let regrow = relaid || gpu.points.is_none() || gpu.capacity < slots;
+    if regrow {
+        gpu.capacity = slots.next_power_of_two();
+        let storage = |label: &'static str, size: usize| {
+            render_device.create_buffer(&BufferDescriptor {
+                label: Some(label),
+                size: size as u64,
+                usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        };
+        gpu.points = Some(storage("Trail Points", gpu.capacity * length * std::mem::size_of::<Vec4>()));
+        gpu.meta = Some(storage("Trail Meta", gpu.capacity * std::mem::size_of::<UVec2>()));
+        gpu.bind_group = None;
+    }
+    let (Some(params_buffer), Some(points), Some(meta)) = (gpu.params.clone(), gpu.points.clone(), gpu.meta.clone()) else { return };
+    if regrow {
+        // A fresh buffer holds nothing yet, so it takes the whole copy
+        render_queue.write_buffer(&points, 0, bytemuck::cast_slice(&gpu.points_copy));
+        render_queue.write_buffer(&meta, 0, bytemuck::cast_slice(&gpu.meta_copy));
+    } else if !trails.changes.is_empty() {
+        // Otherwise only the runs written this frame
+        for run in trails.changes.point_runs() {
+            let offset = (run.start * std::mem::size_of::<Vec4>()) as u64;
+            render_queue.write_buffer(&points, offset, bytemuck::cast_slice(&gpu.points_copy[run]));
+        }
+        for run in trails.changes.meta_runs() {
+            let offset = (run.start * std::mem::size_of::<UVec2>()) as u64;
+            render_queue.write_buffer(&meta, offset, bytemuck::cast_slice(&gpu.meta_copy[run]));
+        }
+    }
+    if gpu.bind_group.is_none() {
+        gpu.bind_group = Some(render_device.create_bind_group(
+            "Trail Bind Group",
+            &pipeline.trail_layout,
+            &BindGroupEntries::sequential((
+                params_buffer.as_entire_binding(),
+                points.as_entire_binding(),
+                meta.as_entire_binding(),
+            )),
+        ));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_trails(
+    draw_functions: Res<DrawFunctions<Transparent3d>>,
+    trail_pipeline: Res<TrailPipeline>,
+    msaa: Res<Msaa>,
+    config: Option<Res<TrailConfig>>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<TrailPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    batches: Query<Entity, With<TrailBatch>>,
+    mut transparent_phases: ResMut<ViewSortedRenderPhases<Transparent3d>>,
+    views: Query<(Entity, &ExtractedView)>,
+) {
+    if !config.is_some_and(|config| config.enabled) {
+        return;
+    }
+    let draw_function = draw_functions.read().id::<DrawTrails>();
+    let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples());
+
+    for (view_entity, view) in views.iter() {
+        let Some(transparent_phase) = transparent_phases.get_mut(&view_entity) else { continue };
+        let key = msaa_key | MeshPipelineKey::from_hdr(view.hdr);
+        let pipeline = pipelines.specialize(&pipeline_cache, &trail_pipeline, key);
+        for entity in batches.iter() {
+            transparent_phase.add(Transparent3d {
+                entity,
+                pipeline,
+                draw_function,
+                distance: 0.0, // Additive, so order doesn't matter
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::NONE,
+            });
+        }
+    }
+}
+
+#[derive(Resource)]
+struct TrailPipeline {
+    shader: Handle<Shader>,
+    mesh_pipeline: MeshPipeline, // For the view bind group layouts
+    trail_layout: BindGroupLayout,
+}
+
+impl FromWorld for TrailPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let trail_layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "Trail Layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::VERTEX,
+                (
+                    uniform_buffer_sized(false, None),
+                    storage_buffer_read_only_sized(false, None),
+                    storage_buffer_read_only_sized(false, None),
+                ),
+            ),
+        );
+        Self {
+            shader: world.load_asset(TRAIL_SHADER),
+            mesh_pipeline: world.resource::<MeshPipeline>().clone(),
+            trail_layout,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for TrailPipeline {
+    type Key = MeshPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let format = if key.contains(MeshPipelineKey::HDR) {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+        let additive = BlendComponent {
+            src_factor: BlendFactor::One,
+            dst_factor: BlendFactor::One,
+            operation: BlendOperation::Add,
+        };
+        RenderPipelineDescriptor {
+            label: Some("trail_ribbon_pipeline".into()),
+            layout: vec![
+                self.mesh_pipeline.get_view_layout(key.into()).clone(),
+                self.trail_layout.clone(),
+            ],
+            push_constant_ranges: vec![],
+            vertex: VertexState {
+                shader: self.shader.clone(),
+                shader_defs: vec![],
+                entry_point: "vertex".into(),
+                buffers: vec![], // Everything comes from the storage buffers
+            },
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState { color: additive, alpha: BlendComponent::OVER }),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState {
+                topology: PrimitiveTopology::TriangleStrip,
+                cull_mode: None,
+                ..default()
+            },
+            // Depth-tested against the scene, but ribbons don't occlude each other
+            depth_stencil: Some(DepthStencilState {
+                format: CORE_3D_DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: CompareFunction::GreaterEqual,
+                stencil: default(),
+                bias: default(),
+            }),
+            multisample: MultisampleState {
+                count: key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+        }
+    }
+}
+
+type DrawTrails = (SetItemPipeline, SetMeshViewBindGroup<0>, DrawTrailRibbons);
+
+struct DrawTrailRibbons;
+
+impl<P: PhaseItem> RenderCommand<P> for DrawTrailRibbons {
+    type Param = SRes<TrailGpu>;
+    type ViewQuery = ();
+    type ItemQuery = ();
+
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        _entity: Option<()>,
+        gpu: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let gpu = gpu.into_inner();
+        let Some(bind_group) = gpu.bind_group.as_ref().filter(|_| gpu.slots > 0) else {
+            return RenderCommandResult::Success;
+        };
+        pass.set_bind_group(1, bind_group, &[]);
+        pass.draw(0..gpu.vertices, 0..gpu.slots);
+        RenderCommandResult::Success
+    }
+}
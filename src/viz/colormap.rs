@@ -0,0 +1,118 @@
+// Perceptual colormaps, sampled in linear RGB.
+// The same stops are uploaded to the GPU so both paths interpolate identically.
+
+pub const STOPS: usize = 9;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Colormap {
+    Viridis,
+    Magma,
+}
+
+// Linear RGB at t = 0, 1/8, ..., 1, converted once from the matplotlib sRGB stops
+const VIRIDIS: [[f32; 3]; STOPS] = [
+    [0.057805, 0.000304, 0.088656], // #440154
+    [0.063010, 0.026241, 0.198069], // #472d7b
+    [0.043735, 0.084376, 0.258183], // #3b528b
+    [0.025187, 0.168269, 0.270498], // #2c728e
+    [0.015209, 0.283149, 0.262251], // #21918c
+    [0.021219, 0.423268, 0.215861], // #28ae80
+    [0.111932, 0.584078, 0.122139], // #5ec962
+    [0.417885, 0.715694, 0.029557], // #addc30
+    [0.982251, 0.799103, 0.018500], // #fde725
+];
+const MAGMA: [[f32; 3]; STOPS] = [
+    [0.000000, 0.000000, 0.001214], // #000004
+    [0.011612, 0.005182, 0.057805], // #1c1044
+    [0.078187, 0.006049, 0.198069], // #4f127b
+    [0.219526, 0.018500, 0.219526], // #812581
+    [0.462077, 0.036889, 0.194618], // #b5367a
+    [0.783538, 0.080220, 0.127438], // #e55064
+    [0.964686, 0.242281, 0.119538], // #fb8761
+    [0.991102, 0.539479, 0.242281], // #fec287
+    [0.973445, 0.982251, 0.520996], // #fcfdbf
+];
+
+impl Colormap {
+    pub fn name(self) -> &'static str {
+        match self {
+            Colormap::Viridis => "viridis",
+            Colormap::Magma => "magma",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "viridis" => Some(Colormap::Viridis),
+            "magma" => Some(Colormap::Magma),
+            _ => None,
+        }
+    }
+
+    pub fn stops_linear(self) -> &'static [[f32; 3]; STOPS] {
+        match self {
+            Colormap::Viridis => &VIRIDIS,
+            Colormap::Magma => &MAGMA,
+        }
+    }
+
+    // `t` is clamped to 0..1
+    pub fn sample(self, t: f32) -> [f32; 3] {
+        let stops = self.stops_linear();
+        let x = if t.is_finite() { t.clamp(0.0, 1.0) } else { 0.0 } * (STOPS - 1) as f32;
+        let i = (x.floor() as usize).min(STOPS - 2);
+        let f = x - i as f32;
+        [0, 1, 2].map(|c| stops[i][c] + (stops[i + 1][c] - stops[i][c]) * f)
+    }
+}
+
+// Where `value` falls in min..max, 0..1
+pub fn normalize(value: f32, (min, max): (f32, f32)) -> f32 {
+    if max - min > 1e-12 { (value - min) / (max - min) } else { 0.5 }
+}
+
+// The `low` and `high` quantiles (0..1) of `values`, ignoring non-finite ones
+pub fn quantile_range(values: &[f32], low: f32, high: f32) -> Option<(f32, f32)> {
+    let mut finite: Vec<f32> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if finite.is_empty() {
+        return None;
+    }
+    finite.sort_by(|a, b| a.total_cmp(b));
+    let at = |q: f32| finite[((finite.len() - 1) as f32 * q.clamp(0.0, 1.0)).round() as usize];
+    Some((at(low), at(high)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colormaps_run_dark_to_bright_and_clamp() {
+        for map in [Colormap::Viridis, Colormap::Magma] {
+            let luminance = |[r, g, b]: [f32; 3]| 0.2126 * r + 0.7152 * g + 0.0722 * b;
+            let samples: Vec<f32> = (0..=16).map(|i| luminance(map.sample(i as f32 / 16.0))).collect();
+            assert!(samples.windows(2).all(|pair| pair[1] >= pair[0]), "{} is not monotonic", map.name());
+            assert_eq!(map.sample(-1.0), map.sample(0.0));
+            assert_eq!(map.sample(2.0), map.stops_linear()[STOPS - 1]);
+            assert_eq!(Colormap::parse(map.name()), Some(map));
+        }
+        let values = [5.0, f32::NAN, 1.0, 3.0, 2.0, 4.0];
+        assert_eq!(quantile_range(&values, 0.0, 1.0), Some((1.0, 5.0)));
+        assert_eq!(normalize(3.0, (1.0, 5.0)), 0.5);
+    }
+
+    #[test]
+    fn linear_stops_match_the_srgb_hex_codes() {
+        let srgb_to_linear = |c: f32| if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) };
+        let viridis = [0x440154, 0x472d7b, 0x3b528b, 0x2c728e, 0x21918c, 0x28ae80, 0x5ec962, 0xaddc30, 0xfde725];
+        let magma = [0x000004, 0x1c1044, 0x4f127b, 0x812581, 0xb5367a, 0xe55064, 0xfb8761, 0xfec287, 0xfcfdbf];
+        for (map, hex) in [(Colormap::Viridis, viridis), (Colormap::Magma, magma)] {
+            for (stop, rgb) in map.stops_linear().iter().zip(hex) {
+                for (channel, shift) in stop.iter().zip([16, 8, 0]) {
+                    let expected = srgb_to_linear(((rgb >> shift) & 0xff) as f32 / 255.0);
+                    assert!((channel - expected).abs() < 1e-5, "{} #{rgb:06x}", map.name());
+                }
+            }
+        }
+    }
+}
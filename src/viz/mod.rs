@@ -0,0 +1,4 @@
+pub mod colormap;
+pub mod plot;
+pub mod splat;
+pub mod trail_ring;
@@ -0,0 +1,138 @@
+// Small CPU line plots (RGBA8) for the inspector and diagnostic panels
+
+pub struct PlotSeries<'a> {
+    pub values: &'a [f32],
+    pub color: [u8; 4],
+}
+
+fn put(pixels: &mut [u8], width: usize, height: usize, x: i32, y: i32, color: [u8; 4]) {
+    if x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height {
+        let i = (y as usize * width + x as usize) * 4;
+        pixels[i..i + 4].copy_from_slice(&color);
+    }
+}
+
+// Bresenham
+fn line(pixels: &mut [u8], width: usize, height: usize, (x0, y0): (i32, i32), (x1, y1): (i32, i32), color: [u8; 4]) {
+    let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+    let (sx, sy) = (if x0 < x1 { 1 } else { -1 }, if y0 < y1 { 1 } else { -1 });
+    let (mut x, mut y, mut error) = (x0, y0, dx + dy);
+    loop {
+        put(pixels, width, height, x, y, color);
+        if x == x1 && y == y1 {
+            break;
+        }
+        let e2 = 2 * error;
+        if e2 >= dy {
+            error += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            error += dx;
+            y += sy;
+        }
+    }
+}
+
+// Value range shared by every series (always includes 0), padded so flat lines stay visible
+pub fn plot_range(series: &[PlotSeries]) -> (f32, f32) {
+    let values = series.iter().flat_map(|s| s.values.iter().copied()).filter(|v| v.is_finite());
+    let (min, max) = values.fold((0.0f32, 0.0f32), |(min, max), v| (min.min(v), max.max(v)));
+    if max - min < 1e-6 { (min - 1.0, max + 1.0) } else { (min, max) }
+}
+
+// Each series spans the full width (oldest sample on the left); the zero line is drawn grey
+pub fn draw_line_plot(width: u32, height: u32, series: &[PlotSeries], background: [u8; 4]) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let mut pixels: Vec<u8> = background.iter().copied().cycle().take(w * h * 4).collect();
+    let (min, max) = plot_range(series);
+    let row = |v: f32| ((1.0 - (v - min) / (max - min)) * (h as f32 - 1.0)).round() as i32;
+
+    let zero = row(0.0);
+    line(&mut pixels, w, h, (0, zero), (w as i32 - 1, zero), [80, 80, 80, 255]);
+
+    for s in series {
+        let n = s.values.len();
+        let column = |i: usize| if n > 1 { (i as f32 / (n - 1) as f32 * (w as f32 - 1.0)).round() as i32 } else { 0 };
+        let points: Vec<(i32, i32)> = s
+            .values
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.is_finite())
+            .map(|(i, v)| (column(i), row(*v)))
+            .collect();
+        for pair in points.windows(2) {
+            line(&mut pixels, w, h, pair[0], pair[1], s.color);
+        }
+        if let [only] = points[..] {
+            put(&mut pixels, w, h, only.0, only.1, s.color);
+        }
+    }
+    pixels
+}
+
+// Extent of the finite values, padded so a single value still has a span
+pub fn finite_range(values: impl Iterator<Item = f32>) -> (f32, f32) {
+    let (min, max) = values
+        .filter(|v| v.is_finite())
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), v| (min.min(v), max.max(v)));
+    if min > max {
+        (0.0, 1.0)
+    } else if max - min < 1e-6 {
+        (min - 0.5, max + 0.5)
+    } else {
+        (min, max)
+    }
+}
+
+// Points (x, y) as 2×2 dots over their own extent; the last point is drawn white
+pub fn draw_scatter_plot(width: u32, height: u32, points: &[(f32, f32)], color: [u8; 4], background: [u8; 4]) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let mut pixels: Vec<u8> = background.iter().copied().cycle().take(w * h * 4).collect();
+    let finite: Vec<&(f32, f32)> = points.iter().filter(|(x, y)| x.is_finite() && y.is_finite()).collect();
+    let (x_min, x_max) = finite_range(finite.iter().map(|p| p.0));
+    let (y_min, y_max) = finite_range(finite.iter().map(|p| p.1));
+    for (i, (x, y)) in finite.iter().enumerate() {
+        let column = ((x - x_min) / (x_max - x_min) * (w as f32 - 2.0)).round() as i32;
+        let row = ((1.0 - (y - y_min) / (y_max - y_min)) * (h as f32 - 2.0)).round() as i32;
+        let dot = if i + 1 == finite.len() { [255, 255, 255, 255] } else { color };
+        for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            put(&mut pixels, w, h, column + dx, row + dy, dot);
+        }
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn series_span_the_plot_and_share_a_scale() {
+        let rising = [0.0, 5.0, 10.0];
+        let falling = [10.0, 0.0];
+        let red = [255, 0, 0, 255];
+        let blue = [0, 0, 255, 255];
+        let pixels = draw_line_plot(
+            11,
+            11,
+            &[PlotSeries { values: &rising, color: red }, PlotSeries { values: &falling, color: blue }],
+            [0, 0, 0, 255],
+        );
+        let at = |x: usize, y: usize| &pixels[(y * 11 + x) * 4..][..4];
+        // Rising from bottom-left to top-right, falling the other way
+        assert_eq!(at(10, 0), &red);
+        assert_eq!(at(0, 0), &blue);
+        assert_eq!(at(10, 10), &blue);
+        assert_eq!(at(5, 5), &blue); // Drawn last where they cross
+        assert_eq!(at(2, 0), &[0, 0, 0, 255]);
+        assert_eq!(plot_range(&[PlotSeries { values: &[3.0, 3.0], color: red }]), (0.0, 3.0));
+
+        // Scatter: the extremes land in opposite corners, the newest point is white
+        let scatter = draw_scatter_plot(12, 12, &[(1.6, 0.0), (f32::NAN, 5.0), (1.7, 1.0)], red, [0, 0, 0, 255]);
+        let at = |x: usize, y: usize| &scatter[(y * 12 + x) * 4..][..4];
+        assert_eq!(at(0, 11), &red);
+        assert_eq!(at(11, 0), &[255, 255, 255, 255]);
+        assert_eq!(finite_range([2.0, f32::NAN].into_iter()), (1.5, 2.5));
+    }
+}
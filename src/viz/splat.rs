@@ -0,0 +1,176 @@
+use bevy::math::{Mat4, Vec2, Vec3};
+
+// Pinhole camera matching Bevy's perspective projection (vertical FOV, -Z forward)
+#[derive(Clone, Copy, Debug)]
+pub struct SplatCamera {
+    pub world_to_view: Mat4,
+    pub fov_y: f32,
+    pub near: f32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl SplatCamera {
+    pub fn new(camera_to_world: Mat4, fov_y: f32, near: f32, width: u32, height: u32) -> Self {
+        Self { world_to_view: camera_to_world.inverse(), fov_y, near, width, height }
+    }
+
+    // Pixels per world unit at unit depth
+    fn focal_length(&self) -> f32 {
+        self.height as f32 * 0.5 / (self.fov_y * 0.5).tan()
+    }
+
+    // Pixel position (origin top-left) and view depth; None behind the near plane
+    pub fn project(&self, point: Vec3) -> Option<(Vec2, f32)> {
+        let view = self.world_to_view.transform_point3(point);
+        let depth = -view.z;
+        if depth < self.near {
+            return None;
+        }
+        let focal = self.focal_length();
+        let pixel = Vec2::new(
+            self.width as f32 * 0.5 + view.x / depth * focal,
+            self.height as f32 * 0.5 - view.y / depth * focal,
+        );
+        Some((pixel, depth))
+    }
+
+    pub fn pixel_radius(&self, world_radius: f32, depth: f32) -> f32 {
+        world_radius * self.focal_length() / depth
+    }
+}
+
+// Linear HDR framebuffer; splats add up, so dense regions glow
+pub struct HdrFrame {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<Vec3>,
+}
+
+impl HdrFrame {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height, pixels: vec![Vec3::ZERO; (width * height) as usize] }
+    }
+
+    fn add(&mut self, x: i32, y: i32, color: Vec3) {
+        if x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height {
+            self.pixels[(y as u32 * self.width + x as u32) as usize] += color;
+        }
+    }
+
+    // Deposits `color` as total energy: a Gaussian of the given pixel radius,
+    // or a bilinear point for footprints smaller than a pixel
+    pub fn splat(&mut self, center: Vec2, radius: f32, color: Vec3) {
+        // Pixel centres sit at half-integers
+        let p = center - Vec2::splat(0.5);
+        if radius < 1.0 {
+            let (x, y) = (p.x.floor(), p.y.floor());
+            let (fx, fy) = (p.x - x, p.y - y);
+            let (x, y) = (x as i32, y as i32);
+            self.add(x, y, color * (1.0 - fx) * (1.0 - fy));
+            self.add(x + 1, y, color * fx * (1.0 - fy));
+            self.add(x, y + 1, color * (1.0 - fx) * fy);
+            self.add(x + 1, y + 1, color * fx * fy);
+            return;
+        }
+
+        let sigma = radius * 0.5;
+        let extent = (radius * 1.5).ceil() as i32;
+        let (cx, cy) = (p.x.round() as i32, p.y.round() as i32);
+        let mut weights = Vec::with_capacity(((2 * extent + 1) * (2 * extent + 1)) as usize);
+        for dy in -extent..=extent {
+            for dx in -extent..=extent {
+                let d = Vec2::new((cx + dx) as f32, (cy + dy) as f32) - p;
+                weights.push(((cx + dx, cy + dy), (-d.length_squared() / (2.0 * sigma * sigma)).exp()));
+            }
+        }
+        let total: f32 = weights.iter().map(|(_, w)| w).sum();
+        for ((x, y), weight) in weights {
+            self.add(x, y, color * (weight / total));
+        }
+    }
+
+    // Bright-pass, blurred by three box passes (≈ Gaussian) and added back
+    pub fn bloom(&mut self, threshold: f32, strength: f32, radius: u32) {
+        let (w, h) = (self.width as usize, self.height as usize);
+        let mut glow: Vec<Vec3> = self
+            .pixels
+            .iter()
+            .map(|c| (*c - Vec3::splat(threshold)).max(Vec3::ZERO))
+            .collect();
+        for _ in 0..3 {
+            glow = box_blur(&glow, w, h, radius as usize, true);
+            glow = box_blur(&glow, w, h, radius as usize, false);
+        }
+        for (pixel, glow) in self.pixels.iter_mut().zip(glow) {
+            *pixel += glow * strength;
+        }
+    }
+
+    // Exponential tone map then the sRGB curve, as 8-bit RGBA
+    pub fn to_srgb8(&self, exposure: f32) -> Vec<u8> {
+        let encode = |c: f32| {
+            let c = 1.0 - (-c * exposure).exp();
+            let c = if c <= 0.003_130_8 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 };
+            (c.clamp(0.0, 1.0) * 255.0 + 0.5) as u8
+        };
+        self.pixels
+            .iter()
+            .flat_map(|c| [encode(c.x), encode(c.y), encode(c.z), 255])
+            .collect()
+    }
+}
+
+// Running-sum box blur along rows (`horizontal`) or columns, clamped at the edges
+fn box_blur(src: &[Vec3], w: usize, h: usize, radius: usize, horizontal: bool) -> Vec<Vec3> {
+    let (lines, len) = if horizontal { (h, w) } else { (w, h) };
+    let index = |line: usize, i: usize| if horizontal { line * w + i } else { i * w + line };
+    let scale = 1.0 / (2 * radius + 1) as f32;
+    let mut out = vec![Vec3::ZERO; src.len()];
+    for line in 0..lines {
+        let at = |i: isize| src[index(line, i.clamp(0, len as isize - 1) as usize)];
+        let mut sum: Vec3 = (-(radius as isize)..=radius as isize).map(at).sum();
+        for i in 0..len {
+            out[index(line, i)] = sum * scale;
+            sum += at(i as isize + radius as isize + 1) - at(i as isize - radius as isize);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn projects_through_the_camera_like_bevy() {
+        // Bevy cameras look down -Z
+        let transform = Mat4::from_translation(Vec3::new(0.0, 0.0, 10.0));
+        let camera = SplatCamera::new(transform, std::f32::consts::FRAC_PI_4, 0.1, 200, 100);
+        let (pixel, depth) = camera.project(Vec3::ZERO).unwrap();
+        assert!((pixel - Vec2::new(100.0, 50.0)).length() < 1e-4 && (depth - 10.0).abs() < 1e-4);
+
+        // Up is up, right is right, and the top edge of the frustum maps to row 0
+        let top = 10.0 * (std::f32::consts::FRAC_PI_8).tan();
+        let (pixel, _) = camera.project(Vec3::new(1.0, top, 0.0)).unwrap();
+        assert!(pixel.x > 100.0 && pixel.y.abs() < 1e-3);
+        assert!(camera.project(Vec3::new(0.0, 0.0, 20.0)).is_none());
+    }
+
+    #[test]
+    fn splats_conserve_energy_and_bloom_spreads_it() {
+        let mut frame = HdrFrame::new(64, 64);
+        frame.splat(Vec2::new(20.3, 30.7), 0.5, Vec3::ONE);
+        frame.splat(Vec2::new(40.0, 30.0), 3.0, Vec3::new(2.0, 1.0, 0.0));
+        let total: Vec3 = frame.pixels.iter().copied().sum();
+        assert!((total - Vec3::new(3.0, 2.0, 1.0)).length() < 1e-4);
+
+        let mut bloomed = HdrFrame::new(64, 64);
+        bloomed.splat(Vec2::new(32.5, 32.5), 0.5, Vec3::splat(50.0));
+        bloomed.bloom(1.0, 1.0, 2);
+        assert!(bloomed.pixels[32 * 64 + 36].x > 0.0);
+        let rgba = bloomed.to_srgb8(1.0);
+        assert_eq!(rgba.len(), 64 * 64 * 4);
+        assert_eq!(&rgba[(32 * 64 + 32) * 4..][..4], &[255, 255, 255, 255]);
+    }
+}
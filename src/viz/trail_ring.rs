@@ -0,0 +1,202 @@
+use bevy::math::{UVec2, Vec3, Vec4};
+use std::ops::Range;
+
+// Every trail's history in one contiguous array, ready to upload as a storage buffer.
+// Trail `slot` owns points[slot * length..][..length], written round-robin;
+// meta[slot] is (index of the newest point, number of points held).
+pub struct TrailRing {
+    length: usize,
+    points: Vec<Vec4>, // xyz, w unused
+    meta: Vec<UVec2>,
+    free: Vec<u32>,
+    changes: TrailChanges,
+}
+
+// Everything written to a ring since `clear_changes`, so a copy can be kept in step
+// without copying the whole ring
+#[derive(Clone, Default)]
+pub struct TrailChanges {
+    pub points: Vec<(u32, Vec4)>, // (index into points, value)
+    pub meta: Vec<(u32, UVec2)>,  // (slot, value)
+}
+
+impl TrailChanges {
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty() && self.meta.is_empty()
+    }
+
+    // Brings a copy of a ring with `slots` slots of `length` points up to date.
+    // Slots added since the copy was made start out empty, as they do in the ring.
+    pub fn apply(&self, length: usize, slots: usize, points: &mut Vec<Vec4>, meta: &mut Vec<UVec2>) {
+        points.resize(slots * length, Vec4::ZERO);
+        meta.resize(slots, UVec2::ZERO);
+        for &(index, point) in self.points.iter() {
+            points[index as usize] = point;
+        }
+        for &(slot, value) in self.meta.iter() {
+            meta[slot as usize] = value;
+        }
+    }
+
+    // The point indices written, merged into ascending runs of consecutive indices
+    pub fn point_runs(&self) -> Vec<Range<usize>> {
+        runs(self.points.iter().map(|&(index, _)| index))
+    }
+
+    pub fn meta_runs(&self) -> Vec<Range<usize>> {
+        runs(self.meta.iter().map(|&(slot, _)| slot))
+    }
+}
+
+fn runs(indices: impl Iterator<Item = u32>) -> Vec<Range<usize>> {
+    let mut indices: Vec<usize> = indices.map(|index| index as usize).collect();
+    indices.sort_unstable();
+    indices.dedup();
+    let mut runs: Vec<Range<usize>> = Vec::new();
+    for index in indices {
+        match runs.last_mut() {
+            Some(run) if run.end == index => run.end += 1,
+            _ => runs.push(index..index + 1),
+        }
+    }
+    runs
+}
+
+impl TrailRing {
+    pub fn new(length: usize) -> Self {
+        Self {
+            length: length.max(2),
+            points: Vec::new(),
+            meta: Vec::new(),
+            free: Vec::new(),
+            changes: TrailChanges::default(),
+        }
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn slots(&self) -> usize {
+        self.meta.len()
+    }
+
+    pub fn changes(&self) -> &TrailChanges {
+        &self.changes
+    }
+
+    pub fn clear_changes(&mut self) {
+        self.changes.points.clear();
+        self.changes.meta.clear();
+    }
+
+    // Reuses a released slot before growing the buffer
+    pub fn allocate(&mut self) -> u32 {
+        if let Some(slot) = self.free.pop() {
+            return slot;
+        }
+        self.points.resize(self.points.len() + self.length, Vec4::ZERO);
+        self.meta.push(UVec2::ZERO);
+        self.meta.len() as u32 - 1
+    }
+
+    pub fn release(&mut self, slot: u32) {
+        self.clear(slot);
+        self.free.push(slot);
+    }
+
+    pub fn clear(&mut self, slot: u32) {
+        self.meta[slot as usize] = UVec2::ZERO;
+        self.changes.meta.push((slot, UVec2::ZERO));
+    }
+
+    pub fn push(&mut self, slot: u32, position: Vec3) {
+        let meta = &mut self.meta[slot as usize];
+        let head = if meta.y == 0 { 0 } else { (meta.x + 1) % self.length as u32 };
+        *meta = UVec2::new(head, (meta.y + 1).min(self.length as u32));
+        let index = slot as usize * self.length + head as usize;
+        self.points[index] = position.extend(1.0);
+        self.changes.meta.push((slot, *meta));
+        self.changes.points.push((index as u32, self.points[index]));
+    }
+
+    // Oldest first
+    pub fn history(&self, slot: u32) -> Vec<Vec3> {
+        let meta = self.meta[slot as usize];
+        let (length, base) = (self.length as u32, slot as usize * self.length);
+        (0..meta.y)
+            .rev()
+            .map(|age| self.points[base + ((meta.x + length - age) % length) as usize].truncate())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trails_wrap_and_reuse_released_slots() {
+        let mut ring = TrailRing::new(3);
+        let a = ring.allocate();
+        let b = ring.allocate();
+        for x in 0..5 {
+            ring.push(a, Vec3::new(x as f32, 0.0, 0.0));
+        }
+        ring.push(b, Vec3::Y);
+
+        // Only the newest three survive, still oldest first
+        let xs: Vec<f32> = ring.history(a).iter().map(|p| p.x).collect();
+        assert_eq!(xs, vec![2.0, 3.0, 4.0]);
+        assert_eq!(ring.history(b), vec![Vec3::Y]);
+        assert_eq!(ring.points.len(), 6);
+
+        ring.release(a);
+        assert!(ring.history(a).is_empty());
+        assert_eq!(ring.allocate(), a);
+        assert_eq!(ring.meta.len(), 2);
+    }
+
+    #[test]
+    fn changes_keep_a_copy_in_step() {
+        let mut ring = TrailRing::new(4);
+        let (mut points, mut meta) = (Vec::new(), Vec::new());
+        let mut sync = |ring: &mut TrailRing| {
+            ring.changes().apply(ring.length(), ring.slots(), &mut points, &mut meta);
+            ring.clear_changes();
+            assert_eq!(points, ring.points);
+            assert_eq!(meta, ring.meta);
+        };
+
+        let a = ring.allocate();
+        for x in 0..6 {
+            ring.push(a, Vec3::new(x as f32, 0.0, 0.0));
+        }
+        sync(&mut ring);
+
+        // Only what was written since the last sync is carried
+        let b = ring.allocate();
+        ring.push(b, Vec3::Y);
+        ring.push(a, Vec3::Z);
+        assert_eq!(ring.changes().points.len(), 2);
+        sync(&mut ring);
+
+        ring.release(a);
+        assert!(!ring.changes().is_empty());
+        sync(&mut ring);
+        assert!(ring.changes().is_empty());
+    }
+
+    #[test]
+    fn changed_indices_coalesce_into_runs() {
+        let mut ring = TrailRing::new(4);
+        let (a, b, c) = (ring.allocate(), ring.allocate(), ring.allocate());
+        ring.push(c, Vec3::X);
+        ring.push(a, Vec3::X);
+        ring.push(b, Vec3::X);
+        ring.push(a, Vec3::Y);
+        // Points 0, 1 (slot a), 4 (slot b) and 8 (slot c); every slot's meta
+        assert_eq!(ring.changes().point_runs(), vec![0..2, 4..5, 8..9]);
+        assert_eq!(ring.changes().meta_runs(), vec![0..3]);
+    }
+}